@@ -0,0 +1,39 @@
+CREATE TABLE search_documents
+(
+    item_id             CHAR(10)       PRIMARY KEY,
+    item_type           VARCHAR(3)     NOT NULL,
+    def_id              CHAR(10),
+    title               TEXT           NOT NULL   DEFAULT '',
+    content             TEXT           NOT NULL   DEFAULT '',
+    tsv                 TSVECTOR       GENERATED ALWAYS AS (
+                            setweight(to_tsvector('simple', title), 'A') || setweight(to_tsvector('simple', content), 'B')
+                        ) STORED
+);
+
+CREATE INDEX search_documents___tsv_idx       ON search_documents USING GIN (tsv);
+CREATE INDEX search_documents___item_type_idx ON search_documents (item_type);
+
+COMMENT ON TABLE  search_documents           is 'The full-text search index of entities, entity links and definitions. It is kept in sync by the repositories.';
+COMMENT ON COLUMN search_documents.item_type is 'The type of the indexed item, as per ItemType values (eni, enl, end, eld, atd).';
+COMMENT ON COLUMN search_documents.def_id    is 'The definition id of the indexed item, in case of entities and entity links.';
+
+-- Index the existing data.
+
+INSERT INTO search_documents (item_id, item_type, def_id, title, content)
+    SELECT e.id, 'eni', e.def_id, COALESCE(e.listing_attr_value, ''),
+           COALESCE((SELECT string_agg(ta.value, ' ') FROM text_attributes ta WHERE ta.owner_id = e.id), '')
+    FROM entities e;
+
+INSERT INTO search_documents (item_id, item_type, def_id, title, content)
+    SELECT el.id, 'enl', el.def_id, COALESCE(eld.name, ''),
+           COALESCE((SELECT string_agg(ta.value, ' ') FROM text_attributes ta WHERE ta.owner_id = el.id), '')
+    FROM entity_links el JOIN entity_link_defs eld ON el.def_id = eld.id;
+
+INSERT INTO search_documents (item_id, item_type, title, content)
+    SELECT id, 'end', name, COALESCE(description, '') FROM entity_defs;
+
+INSERT INTO search_documents (item_id, item_type, title, content)
+    SELECT id, 'eld', COALESCE(name, ''), COALESCE(description, '') FROM entity_link_defs;
+
+INSERT INTO search_documents (item_id, item_type, title, content)
+    SELECT id, 'atd', name, COALESCE(description, '') FROM attribute_defs;
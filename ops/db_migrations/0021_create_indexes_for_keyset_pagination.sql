@@ -0,0 +1,8 @@
+-- The listing value is part of the keyset pagination's sort key, thus it cannot be null.
+UPDATE entities SET listing_attr_value = '' WHERE listing_attr_value IS NULL;
+ALTER TABLE entities ALTER COLUMN listing_attr_value SET DEFAULT '';
+ALTER TABLE entities ALTER COLUMN listing_attr_value SET NOT NULL;
+
+CREATE INDEX entities___listing_attr_value_id_idx        ON entities (listing_attr_value, id);
+CREATE INDEX entities___def_id_listing_attr_value_id_idx ON entities (def_id, listing_attr_value, id);
+CREATE INDEX entity_links___def_id_id_idx                ON entity_links (def_id, id);
@@ -0,0 +1,18 @@
+CREATE TABLE saved_views
+(
+    id              CHAR(10)       PRIMARY KEY,
+    name            VARCHAR(64)    NOT NULL,
+    owner_id        CHAR(10)       NOT NULL    REFERENCES user_accounts(id) ON DELETE CASCADE,
+    shared          BOOLEAN        NOT NULL    DEFAULT FALSE,
+    def_id          CHAR(10)                   REFERENCES entity_defs(id) ON DELETE CASCADE,
+    filter          JSONB,
+    sort_by         VARCHAR(64),
+    sort_desc       BOOLEAN        NOT NULL    DEFAULT FALSE,
+    columns         CHAR(10)[]     NOT NULL    DEFAULT '{}'
+);
+
+CREATE INDEX saved_views___owner_id_idx ON saved_views (owner_id);
+
+COMMENT ON TABLE  saved_views         is 'The named (and optionally shared) queries over the entities.';
+COMMENT ON COLUMN saved_views.filter  is 'The attribute filter, as the JSON serialization of AttributeFilter.';
+COMMENT ON COLUMN saved_views.columns is 'The ids of the attribute definitions whose values are shown as columns.';
@@ -0,0 +1,36 @@
+-- The built-in "Service Instance" entity definition, whose entities are the instances registered in the service registry.
+-- Its items have fixed ids, since they are referred by the application (see `ServiceInstanceDef`).
+
+INSERT INTO attribute_defs (id, name, description, value_type, default_value, required) VALUES
+    ('svcinst-in', 'Instance',       'The service instance, as its service name and address.',          'text',    '',   true),
+    ('svcinst-sn', 'Service',        'The name of the service that the instance belongs to.',          'text',    '',   true),
+    ('svcinst-sv', 'Version',        'The version of the service that the instance runs.',             'text',    '',   false),
+    ('svcinst-ad', 'Address',        'The address (like host:port or URL) the instance is reached at.', 'text',    '',   true),
+    ('svcinst-md', 'Metadata',       'The metadata of the service instance, as a JSON object.',        'text',    '{}', false),
+    ('svcinst-tg', 'Tags',           'The tags of the service instance, separated by comma.',          'text',    '',   false),
+    ('svcinst-tt', 'TTL',            'The seconds a service instance stays registered without a heartbeat.', 'integer', '30', true),
+    ('svcinst-hb', 'Last Heartbeat', 'The time (RFC 3339) of the latest heartbeat of the service instance.', 'text', '', true),
+    ('svcinst-ex', 'Expires At',     'The time (RFC 3339) when the service instance expires, unless renewed.', 'text', '', true);
+
+INSERT INTO entity_defs (id, name, description, listing_attr_def_id) VALUES
+    ('svcinst-ed', 'Service Instance', 'An instance of a service, that is registered in the service registry.', 'svcinst-in');
+
+INSERT INTO entity_defs_attribute_defs_xref (entity_def_id, attribute_def_id, show_index) VALUES
+    ('svcinst-ed', 'svcinst-in', 1),
+    ('svcinst-ed', 'svcinst-sn', 2),
+    ('svcinst-ed', 'svcinst-sv', 3),
+    ('svcinst-ed', 'svcinst-ad', 4),
+    ('svcinst-ed', 'svcinst-md', 5),
+    ('svcinst-ed', 'svcinst-tg', 6),
+    ('svcinst-ed', 'svcinst-tt', 7),
+    ('svcinst-ed', 'svcinst-hb', 8),
+    ('svcinst-ed', 'svcinst-ex', 9);
+
+INSERT INTO search_documents (item_id, item_type, title, content)
+    SELECT id, 'atd', name, COALESCE(description, '') FROM attribute_defs WHERE id LIKE 'svcinst-%';
+
+INSERT INTO search_documents (item_id, item_type, title, content)
+    SELECT id, 'end', name, COALESCE(description, '') FROM entity_defs WHERE id = 'svcinst-ed';
+
+-- The registry looks up the instances by their service name.
+CREATE INDEX text_attributes___svcinst_service_idx ON text_attributes (value) WHERE def_id = 'svcinst-sn';
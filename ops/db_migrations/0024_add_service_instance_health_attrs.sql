@@ -0,0 +1,30 @@
+-- The attributes of the "Service Instance" entity definition that hold the outcome of the active health checks.
+
+INSERT INTO attribute_defs (id, name, description, value_type, default_value, required) VALUES
+    ('svcinst-hc', 'Health Check',  'How the service instance is probed: an http:// URL or a tcp://host:port address.', 'text', '', false),
+    ('svcinst-hs', 'Health Status', 'The health of the service instance: unknown, up or down.',                 'text',    'unknown', false),
+    ('svcinst-lt', 'Latency',       'The milliseconds the latest health check of the service instance took.',  'integer', '0', false),
+    ('svcinst-fc', 'Failed Checks', 'The number of consecutive failed health checks of the service instance.', 'integer', '0', false),
+    ('svcinst-lc', 'Last Checked',  'The time (RFC 3339) of the latest health check of the service instance.', 'text',    '', false);
+
+INSERT INTO entity_defs_attribute_defs_xref (entity_def_id, attribute_def_id, show_index) VALUES
+    ('svcinst-ed', 'svcinst-hc', 10),
+    ('svcinst-ed', 'svcinst-hs', 11),
+    ('svcinst-ed', 'svcinst-lt', 12),
+    ('svcinst-ed', 'svcinst-fc', 13),
+    ('svcinst-ed', 'svcinst-lc', 14);
+
+INSERT INTO search_documents (item_id, item_type, title, content)
+    SELECT id, 'atd', name, COALESCE(description, '') FROM attribute_defs WHERE id IN ('svcinst-hc', 'svcinst-hs', 'svcinst-lt', 'svcinst-fc', 'svcinst-lc');
+
+-- The already registered instances get these attributes as well, with their default values.
+
+INSERT INTO text_attributes (id, owner_id, def_id, value)
+    SELECT substr(md5(random()::text), 1, 10), e.id, ad.id, ad.default_value
+    FROM entities e CROSS JOIN attribute_defs ad
+    WHERE e.def_id = 'svcinst-ed' AND ad.id IN ('svcinst-hc', 'svcinst-hs', 'svcinst-lc');
+
+INSERT INTO integer_attributes (id, owner_id, def_id, value)
+    SELECT substr(md5(random()::text), 1, 10), e.id, ad.id, 0
+    FROM entities e CROSS JOIN attribute_defs ad
+    WHERE e.def_id = 'svcinst-ed' AND ad.id IN ('svcinst-lt', 'svcinst-fc');
@@ -0,0 +1,33 @@
+CREATE TABLE config_sets
+(
+    id              CHAR(10)       PRIMARY KEY,
+    service         VARCHAR(64)    NOT NULL,
+    version         VARCHAR(64)    NOT NULL,
+    env             VARCHAR(64)    NOT NULL,
+    CONSTRAINT config_sets___key_unique UNIQUE (service, version, env)
+);
+
+COMMENT ON TABLE config_sets is 'The configuration sets, each one being the configuration of a service version in an environment.';
+
+CREATE TABLE config_revisions
+(
+    config_set_id   CHAR(10)       NOT NULL    REFERENCES config_sets(id),
+    revision        INT            NOT NULL    CHECK(revision > 0),
+    author          VARCHAR(64)    NOT NULL,
+    comment         VARCHAR(256)   NOT NULL    DEFAULT '',
+    created_at      TIMESTAMPTZ    NOT NULL    DEFAULT now(),
+    items           JSONB          NOT NULL    DEFAULT '[]',
+    PRIMARY KEY (config_set_id, revision)
+);
+
+COMMENT ON TABLE  config_revisions       is 'The revisions of the configuration sets. Once added, a revision cannot be changed.';
+COMMENT ON COLUMN config_revisions.items is 'The configuration items, as the JSON serialization of a ConfigItem array.';
+
+CREATE FUNCTION config_revisions___reject_update() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'The configuration revisions are immutable.';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER config_revisions___immutable BEFORE UPDATE ON config_revisions
+    FOR EACH ROW EXECUTE FUNCTION config_revisions___reject_update();
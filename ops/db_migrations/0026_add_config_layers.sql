@@ -0,0 +1,14 @@
+-- The configuration sets are layered: global, service, environment (of a service), version (of a service in an environment),
+-- and instance (of a service version in an environment). The components that a layer does not use are empty.
+ALTER TABLE config_sets ADD COLUMN instance VARCHAR(64) NOT NULL DEFAULT '';
+
+ALTER TABLE config_sets DROP CONSTRAINT config_sets___key_unique;
+ALTER TABLE config_sets ADD CONSTRAINT config_sets___key_unique UNIQUE (service, version, env, instance);
+
+ALTER TABLE config_sets ADD CONSTRAINT config_sets___layer_check CHECK (
+       (service =  '' AND version =  '' AND env =  '' AND instance = '')
+    OR (service <> '' AND version =  '' AND instance = '')
+    OR (service <> '' AND version <> '' AND env <> '')
+);
+
+COMMENT ON COLUMN config_sets.instance is 'The service instance, for the configuration sets of the instance layer. Otherwise, it is empty.';
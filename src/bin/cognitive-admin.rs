@@ -0,0 +1,431 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::Arc,
+};
+
+use clap::{Parser, Subcommand};
+use futures::StreamExt;
+
+use cognitive::{
+    domain::model::{CsvImportMode, ExportFormat, Id, ImportActionKind, ImportPlan, MetamodelBundle},
+    server::{apply_db_migrations, connect_to_pgdb, db_migrations_status, AppError, AppResult, ServerState},
+};
+
+/// The administration of a Cognitive instance, done directly against its database (set through `DATABASE_URL`).
+#[derive(Parser)]
+#[command(name = "cognitive-admin", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Manage the user accounts.
+    #[command(subcommand)]
+    Users(UsersCommand),
+    /// Apply the pending database migrations.
+    Migrate {
+        /// Only report the state of the migrations, without applying them.
+        #[arg(long)]
+        check: bool,
+    },
+    /// Export or import the metamodel as a (JSON) bundle.
+    #[command(subcommand)]
+    Metamodel(MetamodelCommand),
+    /// Manage the metamodel described as code (a YAML document).
+    #[command(subcommand)]
+    Defs(DefsCommand),
+    /// Export or import the entities of a definition.
+    #[command(subcommand)]
+    Entities(EntitiesCommand),
+    /// Check the consistency of the stored data.
+    Check,
+    /// Write a backup of all the data, to the provided file or else to the standard output.
+    Backup {
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Restore a backup, replacing all the existing data.
+    Restore {
+        file: PathBuf,
+        /// Confirm that all the existing data gets replaced.
+        #[arg(long, required = true)]
+        replace: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum UsersCommand {
+    /// Create a user account, with the provided permissions (like `Admin::Read` and `Admin::Write`).<br/>
+    /// Its password is taken from the `COGNITIVE_ADMIN_PASSWORD` environment variable, or else read from the standard input.
+    Create {
+        #[arg(long)]
+        email: String,
+        #[arg(long)]
+        username: String,
+        #[arg(long = "permission")]
+        permissions: Vec<String>,
+    },
+    /// Grant permissions to an existing user account.
+    Grant {
+        #[arg(long)]
+        email: String,
+        #[arg(long = "permission", required = true)]
+        permissions: Vec<String>,
+    },
+    /// Set a new password of a user account.<br/>
+    /// It is taken from the `COGNITIVE_ADMIN_PASSWORD` environment variable, or else read from the standard input.
+    ResetPassword {
+        #[arg(long)]
+        email: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum MetamodelCommand {
+    /// Write the metamodel bundle, to the provided file or else to the standard output.
+    Export {
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Import a metamodel bundle.
+    Import {
+        file: PathBuf,
+        /// Only show what would be changed.
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum DefsCommand {
+    /// Show the changes that the provided file would make to the metamodel.
+    Plan { file: PathBuf },
+    /// Apply the changes of the provided file to the metamodel.
+    Apply { file: PathBuf },
+    /// Write the current metamodel as YAML, to the provided file or else to the standard output.
+    Export {
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum EntitiesCommand {
+    /// Write the entities of a definition, to the provided file or else to the standard output.
+    Export {
+        /// The id of the entity definition.
+        #[arg(long)]
+        def: String,
+        /// Either `csv` or `jsonl`.
+        #[arg(long, default_value = "csv")]
+        format: String,
+        /// Include the targets of the entities' outgoing links.
+        #[arg(long)]
+        links: bool,
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Import entities of a definition from a CSV file, whose columns are mapped to the attributes with the same name.
+    Import {
+        /// The id of the entity definition.
+        #[arg(long)]
+        def: String,
+        file: PathBuf,
+        /// Import the valid rows, even if some rows have errors.
+        #[arg(long)]
+        skip_bad_rows: bool,
+    },
+}
+
+fn main() -> ExitCode {
+    //
+    let _ = dotenvy::dotenv();
+    simple_logger::SimpleLogger::new()
+        .with_level(log::LevelFilter::Warn)
+        .init()
+        .unwrap();
+    let cli = Cli::parse();
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    match runtime.block_on(run(cli.command)) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Run the command. It returns whether it succeeded.
+async fn run(command: Command) -> AppResult<bool> {
+    //
+    let pool = Arc::new(connect_to_pgdb().await?);
+    let state = ServerState::new(pool.clone());
+    match command {
+        Command::Migrate { check: true } => {
+            let status = db_migrations_status(&pool).await?;
+            println!("Applied: {} migrations.", status.applied.len());
+            for (version, description) in status.pending.iter() {
+                println!("Pending: {} ({})", version, description);
+            }
+            if let Some(problems) = status.problems() {
+                println!("! {}", problems);
+            }
+            Ok(status.is_up_to_date())
+        }
+        Command::Migrate { check: false } => {
+            let status = apply_db_migrations(&pool).await?;
+            for (version, description) in status.pending.iter() {
+                println!("Applied: {} ({})", version, description);
+            }
+            println!("The database is up to date.");
+            Ok(true)
+        }
+        Command::Users(cmd) => run_users(&state, cmd).await,
+        Command::Metamodel(cmd) => run_metamodel(&state, cmd).await,
+        Command::Defs(cmd) => run_defs(&state, cmd).await,
+        Command::Entities(cmd) => run_entities(&state, cmd).await,
+        Command::Check => run_check(&state).await,
+        Command::Backup { output } => {
+            let manifest = match output {
+                Some(path) => {
+                    let file = std::fs::File::create(&path)
+                        .map_err(|e| AppError::Err(format!("Failed to create '{}'. Reason: {}", path.display(), e)))?;
+                    state.backup_mgmt.backup(std::io::BufWriter::new(file)).await?
+                }
+                None => state.backup_mgmt.backup(std::io::stdout().lock()).await?,
+            };
+            let rows: u64 = manifest.tables.iter().map(|t| t.rows).sum();
+            eprintln!("Backed up {} rows of schema version {}.", rows, manifest.schema_version);
+            Ok(true)
+        }
+        Command::Restore { file, .. } => {
+            let reader =
+                std::fs::File::open(&file).map_err(|e| AppError::Err(format!("Failed to open '{}'. Reason: {}", file.display(), e)))?;
+            let manifest = state.backup_mgmt.restore(std::io::BufReader::new(reader)).await?;
+            println!("Restored the backup created at {}.", manifest.created_at);
+            println!("Note that the restored users whose email is new to this instance need their password to be reset.");
+            Ok(true)
+        }
+    }
+}
+
+async fn run_users(state: &ServerState, command: UsersCommand) -> AppResult<bool> {
+    //
+    match command {
+        UsersCommand::Create {
+            email,
+            username,
+            permissions,
+        } => {
+            let password = read_password()?;
+            let id = state
+                .user_mgmt
+                .register_user_with_permissions(&email, &username, password, permissions)
+                .await?;
+            println!("Created the user account with id '{}'.", id);
+        }
+        UsersCommand::Grant { email, permissions } => {
+            state.user_mgmt.grant_permissions(&email, permissions).await?;
+            println!("The permissions were granted.");
+        }
+        UsersCommand::ResetPassword { email } => {
+            let password = read_password()?;
+            state.user_mgmt.reset_password(&email, password).await?;
+            println!("The password was reset.");
+        }
+    }
+    Ok(true)
+}
+
+async fn run_metamodel(state: &ServerState, command: MetamodelCommand) -> AppResult<bool> {
+    //
+    match command {
+        MetamodelCommand::Export { output } => {
+            let bundle = state.bundle_mgmt.export().await?;
+            let json = serde_json::to_string_pretty(&bundle).map_err(|e| AppError::Err(e.to_string()))?;
+            write_output(output.as_deref(), json.as_bytes())?;
+            Ok(true)
+        }
+        MetamodelCommand::Import { file, dry_run } => {
+            let bundle: MetamodelBundle =
+                serde_json::from_str(&read_file(&file)?).map_err(|e| AppError::Err(format!("Invalid metamodel bundle. Reason: {}", e)))?;
+            let plan = state.bundle_mgmt.import(bundle, dry_run).await?;
+            print_plan(&plan);
+            Ok(!plan.has_conflicts())
+        }
+    }
+}
+
+async fn run_defs(state: &ServerState, command: DefsCommand) -> AppResult<bool> {
+    //
+    match command {
+        DefsCommand::Plan { file } => {
+            let plan = state.bundle_mgmt.import_defs(&read_file(&file)?, true).await?;
+            print_plan(&plan);
+            Ok(!plan.has_conflicts())
+        }
+        DefsCommand::Apply { file } => {
+            let plan = state.bundle_mgmt.import_defs(&read_file(&file)?, false).await?;
+            print_plan(&plan);
+            Ok(plan.applied)
+        }
+        DefsCommand::Export { output } => {
+            let yaml = state.bundle_mgmt.export_defs().await?;
+            write_output(output.as_deref(), yaml.as_bytes())?;
+            Ok(true)
+        }
+    }
+}
+
+async fn run_entities(state: &ServerState, command: EntitiesCommand) -> AppResult<bool> {
+    //
+    match command {
+        EntitiesCommand::Export {
+            def,
+            format,
+            links,
+            output,
+        } => {
+            let format = match format.as_str() {
+                "csv" => ExportFormat::Csv,
+                "jsonl" => ExportFormat::Jsonl,
+                other => return Err(AppError::Err(format!("Unknown export format '{}'.", other))),
+            };
+            let def_id = Id::new_from(def);
+            let ent_def = state
+                .ent_def_mgmt
+                .get(&def_id)
+                .await
+                .ok_or(AppError::Err(format!("Unknown entity definition id '{}'.", def_id)))?;
+            let mut chunks = state.ent_export_mgmt.export_entities(ent_def, format, links).await?;
+            // Each chunk is written as soon as it arrives, thus the whole export is never held in memory.
+            let mut out = open_output(output.as_deref())?;
+            while let Some(chunk) = chunks.next().await {
+                out.write_all(&chunk?)
+                    .map_err(|e| format!("Failed to write the output. Reason: {}", e))?;
+            }
+            out.flush().map_err(|e| format!("Failed to write the output. Reason: {}", e))?;
+            Ok(true)
+        }
+        EntitiesCommand::Import { def, file, skip_bad_rows } => {
+            let mode = match skip_bad_rows {
+                true => CsvImportMode::SkipBadRows,
+                false => CsvImportMode::AllOrNothing,
+            };
+            let report = state
+                .ent_import_mgmt
+                .import(&Id::new_from(def), &read_file(&file)?, vec![], mode)
+                .await?;
+            for error in report.errors.iter() {
+                let column = error.column.as_ref().map(|c| format!(" (column '{}')", c)).unwrap_or_default();
+                println!("! line {}{}: {}", error.line, column, error.message);
+            }
+            println!(
+                "Imported {} of {} rows, {} rows have errors.",
+                report.imported,
+                report.total_rows,
+                report.total_rows - report.valid_rows
+            );
+            Ok(report.imported == report.total_rows)
+        }
+    }
+}
+
+async fn run_check(state: &ServerState) -> AppResult<bool> {
+    //
+    let checks = state.consistency_mgmt.check().await?;
+    for check in checks.iter() {
+        match check.count {
+            0 => println!("ok    {}", check.name),
+            count => {
+                println!("FAIL  {}: {} ({} found)", check.name, check.description, count);
+                let ids: Vec<&str> = check.sample_ids.iter().map(|id| id.as_str()).collect();
+                println!("      e.g. {}", ids.join(", "));
+            }
+        }
+    }
+    Ok(checks.iter().all(|check| check.count == 0))
+}
+
+fn print_plan(plan: &ImportPlan) {
+    //
+    for action in plan.actions.iter().filter(|a| a.kind != ImportActionKind::Unchanged) {
+        match action.kind {
+            ImportActionKind::Create => println!("+ {:?} '{}'", action.item_type, action.name),
+            ImportActionKind::Absent => println!("- {:?} '{}' (absent, it is kept)", action.item_type, action.name),
+            _ => println!("~ {:?} '{}'", action.item_type, action.name),
+        }
+    }
+    for conflict in plan.conflicts.iter() {
+        println!("! {:?} '{}': {}", conflict.item_type, conflict.name, conflict.reason);
+    }
+    println!(
+        "Plan: {} to create, {} to update, {} unchanged, {} absent (kept), {} conflicts.",
+        plan.count(ImportActionKind::Create),
+        plan.count(ImportActionKind::Update),
+        plan.count(ImportActionKind::Unchanged),
+        plan.count(ImportActionKind::Absent),
+        plan.conflicts.len()
+    );
+    match (plan.applied, plan.has_conflicts()) {
+        (true, _) => println!("The changes were applied."),
+        (false, true) => println!("Nothing was changed, due to the conflicts."),
+        (false, false) => {}
+    }
+}
+
+/// The name of the environment variable that can provide the password of a user account.
+const PASSWORD_ENV_VAR: &str = "COGNITIVE_ADMIN_PASSWORD";
+
+/// Read the password of a user account, from the `COGNITIVE_ADMIN_PASSWORD` environment variable, or else from the standard input.<br/>
+/// It is never accepted as an argument, as these are visible to the other users of the host (through `ps`, for example).
+fn read_password() -> AppResult<String> {
+    //
+    if let Ok(password) = std::env::var(PASSWORD_ENV_VAR) {
+        return match password.is_empty() {
+            true => Err(format!("The password in '{}' cannot be empty.", PASSWORD_ENV_VAR).into()),
+            false => Ok(password),
+        };
+    }
+    eprint!("Password: ");
+    let _ = std::io::stderr().flush();
+    let mut line = String::new();
+    std::io::stdin()
+        .read_line(&mut line)
+        .map_err(|e| AppError::Err(format!("Failed to read the password. Reason: {}", e)))?;
+    let password = line.trim_end_matches(['\r', '\n']).to_string();
+    match password.is_empty() {
+        true => Err("The password cannot be empty.".into()),
+        false => Ok(password),
+    }
+}
+
+fn read_file(path: &Path) -> AppResult<String> {
+    std::fs::read_to_string(path).map_err(|e| format!("Failed to read '{}'. Reason: {}", path.display(), e).into())
+}
+
+/// Open the output to write into: the file at the provided path (that is created or truncated), or else the standard output.
+fn open_output(path: Option<&Path>) -> AppResult<Box<dyn Write + Send>> {
+    match path {
+        Some(path) => std::fs::File::create(path)
+            .map(|file| Box::new(std::io::BufWriter::new(file)) as Box<dyn Write + Send>)
+            .map_err(|e| format!("Failed to create '{}'. Reason: {}", path.display(), e).into()),
+        None => Ok(Box::new(std::io::stdout())),
+    }
+}
+
+/// Write the content to the provided file, or else to the standard output.
+fn write_output(path: Option<&Path>, content: &[u8]) -> AppResult<()> {
+    match path {
+        Some(path) => std::fs::write(path, content).map_err(|e| format!("Failed to write '{}'. Reason: {}", path.display(), e).into()),
+        None => std::io::stdout()
+            .write_all(content)
+            .map_err(|e| format!("Failed to write the output. Reason: {}", e).into()),
+    }
+}
@@ -0,0 +1,425 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use strum::Display;
+
+use crate::domain::model::{AttributeValueType, Id};
+
+/// The layers of the configuration, from the most generic to the most specific one.<br/>
+/// A configuration value is resolved through these layers, the value of a more specific layer overriding the others.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Display, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigLayer {
+    /// The defaults of all the services.
+    #[default]
+    #[strum(to_string = "global")]
+    Global,
+    #[strum(to_string = "service")]
+    Service,
+    /// An environment of a service, for all its versions.
+    #[strum(to_string = "environment")]
+    Environment,
+    /// A service version in an environment.
+    #[strum(to_string = "version")]
+    Version,
+    /// A service instance, of a service version in an environment.
+    #[strum(to_string = "instance")]
+    Instance,
+}
+
+/// The key of a configuration set, that is the configuration of a layer.<br/>
+/// The components that the layer does not use are empty: all of them for the global layer, the version and the environment
+/// for the service layer, the version for the environment layer. The instance is set for the instance layer only.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct ConfigKey {
+    #[serde(default)]
+    pub service: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub env: String,
+    #[serde(default)]
+    pub instance: String,
+}
+
+impl ConfigKey {
+    //
+    /// The key of the configuration of a service version in an environment.
+    pub fn new(service: String, version: String, env: String) -> Self {
+        Self {
+            service,
+            version,
+            env,
+            instance: String::new(),
+        }
+    }
+
+    /// Get its layer, as per the components that are set. It is `None` if these do not match any layer.
+    pub fn layer(&self) -> Option<ConfigLayer> {
+        let set = |c: &String| !c.is_empty();
+        match (set(&self.service), set(&self.env), set(&self.version), set(&self.instance)) {
+            (false, false, false, false) => Some(ConfigLayer::Global),
+            (true, false, false, false) => Some(ConfigLayer::Service),
+            (true, true, false, false) => Some(ConfigLayer::Environment),
+            (true, true, true, false) => Some(ConfigLayer::Version),
+            (true, true, true, true) => Some(ConfigLayer::Instance),
+            _ => None,
+        }
+    }
+
+    /// Tell whether the provided set is in this one's layer or under it, that is each component of this set is empty or the same.
+    pub fn covers(&self, other: &ConfigKey) -> bool {
+        let covers = |this: &String, other: &String| this.is_empty() || this == other;
+        covers(&self.service, &other.service)
+            && covers(&self.version, &other.version)
+            && covers(&self.env, &other.env)
+            && covers(&self.instance, &other.instance)
+    }
+
+    /// Get the name that the layer of this configuration set is known by (such as the environment name, for the environment layer).
+    pub fn layer_name(&self) -> &str {
+        match self.layer() {
+            Some(ConfigLayer::Service) => &self.service,
+            Some(ConfigLayer::Environment) => &self.env,
+            Some(ConfigLayer::Version) => &self.version,
+            Some(ConfigLayer::Instance) => &self.instance,
+            _ => "global",
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let or_any = |c: &str| if c.is_empty() { "*".to_string() } else { c.to_string() };
+        write!(f, "{}/{}/{}", or_any(&self.service), or_any(&self.version), or_any(&self.env))?;
+        if !self.instance.is_empty() {
+            write!(f, "/{}", self.instance)?;
+        }
+        Ok(())
+    }
+}
+
+/// A configuration item, whose value is typed (and validated) as per its attribute definition.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct ConfigItem {
+    pub key: String,
+    /// The id of the attribute definition that gives its type and default value.
+    pub attr_def_id: Id,
+    /// The value, as text. If empty, the default value of the attribute definition is used.
+    #[serde(default)]
+    pub value: String,
+    /// The value type of the attribute definition at the time the revision was added.
+    /// It is kept with the item, so that a revision stays the same even if its attribute definitions change.
+    #[serde(default)]
+    pub value_type: AttributeValueType,
+}
+
+impl ConfigItem {
+    //
+    /// Get its typed value. A value that is no longer valid (if any) is provided as text.
+    pub fn json_value(&self) -> Value {
+        self.value_type.parse_value(&self.value).unwrap_or(Value::from(self.value.as_str()))
+    }
+}
+
+/// An (immutable) revision of a configuration set.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct ConfigRevision {
+    #[serde(flatten)]
+    pub key: ConfigKey,
+    /// Its number, starting with 1 for the first revision of the set.
+    pub revision: i32,
+    /// The username of the (authenticated) user that added it.
+    pub author: String,
+    pub comment: String,
+    /// The time (RFC 3339) it was added at.
+    pub created_at: String,
+    pub items: Vec<ConfigItem>,
+}
+
+/// A new revision of a configuration set, that replaces all the items of the previous one.<br/>
+/// Its author is the user that adds it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct NewConfigRevision {
+    #[serde(default)]
+    pub comment: String,
+    pub items: Vec<ConfigItem>,
+}
+
+/// The effective configuration of a service version in an environment (or of one of its instances), as typed values by key.<br/>
+/// The values are resolved through all the configuration layers.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct EffectiveConfig {
+    #[serde(flatten)]
+    pub key: ConfigKey,
+    #[cfg_attr(feature = "server", schema(value_type = Object))]
+    pub values: BTreeMap<String, Value>,
+}
+
+impl From<ResolvedConfig> for EffectiveConfig {
+    fn from(resolved: ResolvedConfig) -> Self {
+        Self {
+            key: resolved.key,
+            values: resolved.values.into_iter().map(|(key, value)| (key, value.value)).collect(),
+        }
+    }
+}
+
+/// A resolved configuration value, with the layer it came from.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct ResolvedConfigValue {
+    #[cfg_attr(feature = "server", schema(value_type = Object))]
+    pub value: Value,
+    pub layer: ConfigLayer,
+    /// The name of the layer it came from, such as `staging` for the `staging` environment layer.
+    pub layer_name: String,
+    /// The revision of the layer's configuration set that it came from.
+    pub revision: i32,
+}
+
+/// An override (that is a value of the service, environment or version layer) that is never used: all the configuration sets
+/// under its layer override it as well, or get the key from a set that does.<br/>
+/// Note that it would still be used by a service version or instance that is not configured (that has no configuration set).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct UnusedConfigOverride {
+    /// The configuration set that has the override.
+    pub key: ConfigKey,
+    pub item_key: String,
+    #[cfg_attr(feature = "server", schema(value_type = Object))]
+    pub value: Value,
+    pub layer: ConfigLayer,
+    pub layer_name: String,
+    pub revision: i32,
+    /// The configuration sets (of the more specific layers) whose values are used instead.
+    pub overridden_by: Vec<ConfigKey>,
+}
+
+/// The resolution of the configuration of a service version in an environment (or of one of its instances):
+/// the values with the layer each one came from, and the overrides of its layers that are never used.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct ResolvedConfig {
+    #[serde(flatten)]
+    pub key: ConfigKey,
+    pub values: BTreeMap<String, ResolvedConfigValue>,
+    pub unused_overrides: Vec<UnusedConfigOverride>,
+}
+
+impl ResolvedConfig {
+    //
+    /// Resolve the configuration through the (latest) revisions of its layers, in any order.
+    /// The unused overrides are found among the (latest) revisions of all the configuration sets of the service.
+    /// The values that are no longer valid (if any) are provided as text.
+    pub fn resolve(key: ConfigKey, revisions: Vec<ConfigRevision>, service_revisions: &[ConfigRevision]) -> Self {
+        //
+        let unused_overrides = find_unused_config_overrides(service_revisions)
+            .into_iter()
+            .filter(|unused| revisions.iter().any(|r| r.key == unused.key))
+            .collect();
+        Self {
+            key,
+            values: resolve_config_values(revisions),
+            unused_overrides,
+        }
+    }
+}
+
+impl EffectiveConfig {
+    //
+    /// Resolve the configuration through the (latest) revisions of its layers, in any order, as values only.
+    pub fn resolve(key: ConfigKey, revisions: Vec<ConfigRevision>) -> Self {
+        Self {
+            key,
+            values: resolve_config_values(revisions)
+                .into_iter()
+                .map(|(key, value)| (key, value.value))
+                .collect(),
+        }
+    }
+}
+
+/// Resolve the values through the (latest) revisions of the layers, in any order: the more specific layer wins.
+fn resolve_config_values(mut revisions: Vec<ConfigRevision>) -> BTreeMap<String, ResolvedConfigValue> {
+    //
+    revisions.sort_by_key(|revision| revision.key.layer());
+    let mut values = BTreeMap::new();
+    for revision in revisions.into_iter() {
+        for item in revision.items.iter() {
+            let value = ResolvedConfigValue {
+                value: item.json_value(),
+                layer: revision.key.layer().unwrap_or_default(),
+                layer_name: revision.key.layer_name().to_string(),
+                revision: revision.revision,
+            };
+            values.insert(item.key.clone(), value);
+        }
+    }
+    values
+}
+
+/// Find the overrides that are never used, among the (latest) revisions of the configuration sets of a service.<br/>
+/// An override of a (service, environment or version) layer is never used if there is at least one configuration set under it,
+/// and each of these sets either overrides the key or gets it from a set (between the two) that does.
+pub fn find_unused_config_overrides(revisions: &[ConfigRevision]) -> Vec<UnusedConfigOverride> {
+    //
+    let mut unused = vec![];
+    for revision in revisions.iter().filter(|r| {
+        matches!(
+            r.key.layer(),
+            Some(ConfigLayer::Service | ConfigLayer::Environment | ConfigLayer::Version)
+        )
+    }) {
+        let under: Vec<&ConfigRevision> = revisions
+            .iter()
+            .filter(|r| r.key != revision.key && revision.key.covers(&r.key))
+            .collect();
+        if under.is_empty() {
+            continue;
+        }
+        for item in revision.items.iter() {
+            // For each set under this one, the most generic set (under this one) that overrides the key for it, if any.
+            let overriding: Vec<Option<&ConfigKey>> = under
+                .iter()
+                .map(|target| {
+                    revisions
+                        .iter()
+                        .filter(|r| r.key != revision.key && revision.key.covers(&r.key) && r.key.covers(&target.key))
+                        .filter(|r| r.items.iter().any(|i| i.key == item.key))
+                        .min_by_key(|r| r.key.layer())
+                        .map(|r| &r.key)
+                })
+                .collect();
+            if overriding.iter().all(Option::is_some) {
+                let mut overridden_by: Vec<ConfigKey> = vec![];
+                for key in overriding.into_iter().flatten() {
+                    if !overridden_by.contains(key) {
+                        overridden_by.push(key.clone());
+                    }
+                }
+                unused.push(UnusedConfigOverride {
+                    key: revision.key.clone(),
+                    item_key: item.key.clone(),
+                    value: item.json_value(),
+                    layer: revision.key.layer().unwrap_or_default(),
+                    layer_name: revision.key.layer_name().to_string(),
+                    revision: revision.revision,
+                    overridden_by,
+                });
+            }
+        }
+    }
+    unused
+}
+
+#[cfg(test)]
+mod tests {
+    //
+    use super::*;
+
+    fn revision(service: &str, version: &str, env: &str, keys: &[&str]) -> ConfigRevision {
+        ConfigRevision {
+            key: ConfigKey {
+                service: service.into(),
+                version: version.into(),
+                env: env.into(),
+                instance: String::new(),
+            },
+            revision: 1,
+            items: keys
+                .iter()
+                .map(|key| ConfigItem {
+                    key: key.to_string(),
+                    value: "1".into(),
+                    value_type: AttributeValueType::Integer,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn of_instance(mut revision: ConfigRevision, instance: &str) -> ConfigRevision {
+        revision.key.instance = instance.into();
+        revision
+    }
+
+    #[test]
+    fn resolve_records_the_layer_of_each_value() {
+        let layers = vec![
+            revision("api", "v1", "staging", &["b"]),
+            revision("", "", "", &["a", "b", "c"]),
+            revision("api", "", "staging", &["a"]),
+        ];
+        let resolved = ResolvedConfig::resolve(ConfigKey::new("api".into(), "v1".into(), "staging".into()), layers, &[]);
+        let sources: Vec<(&str, ConfigLayer, &str)> = resolved
+            .values
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.layer, value.layer_name.as_str()))
+            .collect();
+        assert_eq!(
+            sources,
+            vec![
+                ("a", ConfigLayer::Environment, "staging"),
+                ("b", ConfigLayer::Version, "v1"),
+                ("c", ConfigLayer::Global, "global"),
+            ]
+        );
+    }
+
+    #[test]
+    fn an_override_used_by_another_version_is_not_unused() {
+        let revisions = vec![
+            revision("api", "", "staging", &["a"]),
+            revision("api", "v1", "staging", &["a"]),
+            revision("api", "v2", "staging", &[]),
+        ];
+        assert!(find_unused_config_overrides(&revisions).is_empty());
+    }
+
+    #[test]
+    fn an_override_that_all_versions_override_is_unused() {
+        let revisions = vec![
+            revision("api", "", "", &["a"]),
+            revision("api", "", "staging", &["a"]),
+            revision("api", "v1", "staging", &["a"]),
+            revision("api", "v2", "staging", &["a"]),
+            revision("api", "", "prod", &[]),
+        ];
+        let unused = find_unused_config_overrides(&revisions);
+        let unused: Vec<(ConfigLayer, &str, usize)> = unused
+            .iter()
+            .map(|u| (u.layer, u.layer_name.as_str(), u.overridden_by.len()))
+            .collect();
+        // The service's override is still used by the prod environment (and thus by its versions).
+        assert_eq!(unused, vec![(ConfigLayer::Environment, "staging", 2)]);
+    }
+
+    #[test]
+    fn resolve_lists_the_unused_overrides_of_its_own_layers_only() {
+        let service_revisions = vec![
+            revision("api", "v1", "staging", &["a"]),
+            revision("api", "v1", "prod", &["a"]),
+            of_instance(revision("api", "v1", "prod", &["a"]), "i1"),
+            of_instance(revision("api", "v1", "staging", &[]), "i1"),
+        ];
+        let staging = ConfigKey::new("api".into(), "v1".into(), "staging".into());
+        let layers = vec![revision("api", "v1", "staging", &["a"])];
+        let resolved = ResolvedConfig::resolve(staging, layers, &service_revisions);
+        assert!(resolved.unused_overrides.is_empty());
+
+        let prod = ConfigKey::new("api".into(), "v1".into(), "prod".into());
+        let layers = vec![revision("api", "v1", "prod", &["a"])];
+        let resolved = ResolvedConfig::resolve(prod.clone(), layers, &service_revisions);
+        let unused: Vec<&ConfigKey> = resolved.unused_overrides.iter().map(|u| &u.key).collect();
+        assert_eq!(unused, vec![&prod]);
+    }
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::model::{Entity, EntityLink, Id};
+
+/// A (sub)graph of entities (as nodes) and entity links (as edges).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EntityGraph {
+    pub entities: Vec<Entity>,
+    pub links: Vec<EntityLink>,
+}
+
+impl EntityGraph {
+    pub fn new(entities: Vec<Entity>, links: Vec<EntityLink>) -> Self {
+        Self { entities, links }
+    }
+
+    /// Merge another graph into this one, skipping the entities and links that are already included.
+    pub fn merge(&mut self, other: EntityGraph) {
+        for ent in other.entities {
+            if !self.entities.iter().any(|e| e.id == ent.id) {
+                self.entities.push(ent);
+            }
+        }
+        for link in other.links {
+            if !self.links.iter().any(|l| l.id == link.id) {
+                self.links.push(link);
+            }
+        }
+    }
+}
+
+/// The criteria used for getting an `EntityGraph`.<br/>
+/// An empty list means no filtering on that criteria.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EntityGraphFilter {
+    /// Include only the entities with these definitions.
+    pub ent_def_ids: Vec<Id>,
+
+    /// Include only the links with these definitions.
+    pub ent_link_def_ids: Vec<Id>,
+
+    /// Include only the entities whose definitions (or their attribute definitions) are tagged with these tags.
+    pub tag_ids: Vec<Id>,
+}
@@ -0,0 +1,245 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::domain::model::{AttributeValueType, Id};
+
+/// The format of an exported (instance) graph.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphExportFormat {
+    /// The XML based GraphML format.
+    #[default]
+    GraphMl,
+    /// The Graphviz DOT language.
+    Dot,
+    /// A JSON object with `nodes` and `edges` arrays.
+    Json,
+}
+
+impl GraphExportFormat {
+    //
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::GraphMl => "application/graphml+xml; charset=utf-8",
+            Self::Dot => "text/vnd.graphviz; charset=utf-8",
+            Self::Json => "application/json",
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            Self::GraphMl => "graphml",
+            Self::Dot => "dot",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// An attribute (of a node or an edge) of an exported graph. Its value is kept in its text representation.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GraphExportAttribute {
+    /// The id of its attribute definition, as the names of these are not unique.
+    pub def_id: Id,
+    pub name: String,
+    pub value_type: AttributeValueType,
+    pub value: String,
+}
+
+/// An entity, as a node of an exported graph.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GraphExportNode {
+    pub id: Id,
+    pub kind: String,
+    pub label: String,
+    pub attributes: Vec<GraphExportAttribute>,
+}
+
+/// An entity link, as an edge of an exported graph.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GraphExportEdge {
+    pub id: Id,
+    pub kind: String,
+    pub source: Id,
+    pub target: Id,
+    pub attributes: Vec<GraphExportAttribute>,
+}
+
+/// The instance graph (entities as nodes, and entity links as edges) to be exported.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphExport {
+    pub nodes: Vec<GraphExportNode>,
+    pub edges: Vec<GraphExportEdge>,
+}
+
+impl GraphExport {
+    //
+    pub fn render(&self, format: GraphExportFormat) -> String {
+        match format {
+            GraphExportFormat::GraphMl => self.to_graphml(),
+            GraphExportFormat::Dot => self.to_dot(),
+            GraphExportFormat::Json => self.to_json().to_string(),
+        }
+    }
+
+    /// Render the graph as GraphML.<br/>
+    /// Each distinct attribute (definition) of the nodes and of the edges gets its own typed `key` declaration.
+    pub fn to_graphml(&self) -> String {
+        //
+        let node_keys = graphml_keys(self.nodes.iter().flat_map(|n| n.attributes.iter()));
+        let edge_keys = graphml_keys(self.edges.iter().flat_map(|e| e.attributes.iter()));
+
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        out.push_str("  <key id=\"kind\" for=\"all\" attr.name=\"kind\" attr.type=\"string\"/>\n");
+        out.push_str("  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n");
+        for (idx, (_, name, value_type)) in node_keys.iter().enumerate() {
+            out.push_str(&format!(
+                "  <key id=\"n{}\" for=\"node\" attr.name=\"{}\" attr.type=\"{}\"/>\n",
+                idx,
+                xml_escape(name),
+                graphml_type(value_type)
+            ));
+        }
+        for (idx, (_, name, value_type)) in edge_keys.iter().enumerate() {
+            out.push_str(&format!(
+                "  <key id=\"e{}\" for=\"edge\" attr.name=\"{}\" attr.type=\"{}\"/>\n",
+                idx,
+                xml_escape(name),
+                graphml_type(value_type)
+            ));
+        }
+        out.push_str("  <graph id=\"entities\" edgedefault=\"directed\">\n");
+        for node in self.nodes.iter() {
+            out.push_str(&format!("    <node id=\"{}\">\n", xml_escape(node.id.as_str())));
+            out.push_str(&format!("      <data key=\"kind\">{}</data>\n", xml_escape(&node.kind)));
+            out.push_str(&format!("      <data key=\"label\">{}</data>\n", xml_escape(&node.label)));
+            for attr in node.attributes.iter() {
+                if let Some(idx) = node_keys.iter().position(|(def_id, _, _)| def_id == &attr.def_id) {
+                    out.push_str(&format!("      <data key=\"n{}\">{}</data>\n", idx, xml_escape(&attr.value)));
+                }
+            }
+            out.push_str("    </node>\n");
+        }
+        for edge in self.edges.iter() {
+            out.push_str(&format!(
+                "    <edge id=\"{}\" source=\"{}\" target=\"{}\">\n",
+                xml_escape(edge.id.as_str()),
+                xml_escape(edge.source.as_str()),
+                xml_escape(edge.target.as_str())
+            ));
+            out.push_str(&format!("      <data key=\"kind\">{}</data>\n", xml_escape(&edge.kind)));
+            for attr in edge.attributes.iter() {
+                if let Some(idx) = edge_keys.iter().position(|(def_id, _, _)| def_id == &attr.def_id) {
+                    out.push_str(&format!("      <data key=\"e{}\">{}</data>\n", idx, xml_escape(&attr.value)));
+                }
+            }
+            out.push_str("    </edge>\n");
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+
+    /// Render the graph in Graphviz DOT language, as a directed graph.<br/>
+    /// The attributes are included as (quoted) node and edge attributes, with an `attr_` prefix so that they never override
+    /// the DOT ones (such as `label`, `color` or `shape`).
+    pub fn to_dot(&self) -> String {
+        //
+        let mut out = String::from("digraph entities {\n");
+        for node in self.nodes.iter() {
+            let mut attrs = vec![("label".to_string(), node.label.as_str()), ("kind".to_string(), node.kind.as_str())];
+            attrs.extend(node.attributes.iter().map(dot_user_attr));
+            out.push_str(&format!("  {} [{}];\n", dot_quote(node.id.as_str()), dot_attrs(&attrs)));
+        }
+        for edge in self.edges.iter() {
+            let mut attrs = vec![("label".to_string(), edge.kind.as_str()), ("id".to_string(), edge.id.as_str())];
+            attrs.extend(edge.attributes.iter().map(dot_user_attr));
+            out.push_str(&format!(
+                "  {} -> {} [{}];\n",
+                dot_quote(edge.source.as_str()),
+                dot_quote(edge.target.as_str()),
+                dot_attrs(&attrs)
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Render the graph as a JSON object with `nodes` and `edges`. The attribute values are typed.
+    pub fn to_json(&self) -> Value {
+        //
+        let nodes: Vec<Value> = self
+            .nodes
+            .iter()
+            .map(|n| json!({ "id": n.id, "kind": n.kind, "label": n.label, "attributes": json_attrs(&n.attributes) }))
+            .collect();
+        let edges: Vec<Value> = self
+            .edges
+            .iter()
+            .map(|e| json!({ "id": e.id, "kind": e.kind, "source": e.source, "target": e.target, "attributes": json_attrs(&e.attributes) }))
+            .collect();
+        json!({ "nodes": nodes, "edges": edges })
+    }
+}
+
+/// The distinct (by definition) attributes, in the order of their first occurrence.
+fn graphml_keys<'a>(attrs: impl Iterator<Item = &'a GraphExportAttribute>) -> Vec<(Id, String, AttributeValueType)> {
+    let mut keys: Vec<(Id, String, AttributeValueType)> = Vec::new();
+    for attr in attrs {
+        if !keys.iter().any(|(def_id, _, _)| def_id == &attr.def_id) {
+            keys.push((attr.def_id.clone(), attr.name.clone(), attr.value_type.clone()));
+        }
+    }
+    keys
+}
+
+fn graphml_type(value_type: &AttributeValueType) -> &'static str {
+    match value_type {
+        AttributeValueType::SmallInteger | AttributeValueType::Integer => "int",
+        AttributeValueType::BigInteger => "long",
+        AttributeValueType::Decimal => "float",
+        AttributeValueType::Boolean => "boolean",
+        _ => "string",
+    }
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn dot_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+fn dot_user_attr(attr: &GraphExportAttribute) -> (String, &str) {
+    (format!("attr_{}", attr.name), attr.value.as_str())
+}
+
+fn dot_attrs(attrs: &[(String, &str)]) -> String {
+    attrs
+        .iter()
+        .map(|(name, value)| format!("{}={}", dot_quote(name), dot_quote(value)))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn json_attrs(attrs: &[GraphExportAttribute]) -> Map<String, Value> {
+    attrs
+        .iter()
+        .map(|a| {
+            let value = match a.value_type {
+                AttributeValueType::SmallInteger | AttributeValueType::Integer | AttributeValueType::BigInteger => {
+                    a.value.parse::<i64>().map(Value::from).unwrap_or(Value::String(a.value.clone()))
+                }
+                AttributeValueType::Decimal => a.value.parse::<f64>().map(Value::from).unwrap_or(Value::String(a.value.clone())),
+                AttributeValueType::Boolean => a.value.parse::<bool>().map(Value::from).unwrap_or(Value::String(a.value.clone())),
+                _ => Value::String(a.value.clone()),
+            };
+            (a.name.clone(), value)
+        })
+        .collect()
+}
@@ -3,11 +3,14 @@ use super::{Item, ItemType};
 use crate::domain::model::Id;
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use strum::Display;
 
 #[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 /// An attribute definition.
 pub struct AttributeDef {
+    #[serde(default)]
     pub id: Id,
     pub name: String,
     pub description: Option<String>,
@@ -59,6 +62,7 @@ impl Item for AttributeDef {
 }
 
 #[derive(Debug, Default, PartialEq, Clone, Display, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 /// The type of a value of an attribute.
 pub enum AttributeValueType {
     //
@@ -109,6 +113,38 @@ impl AttributeValueType {
             Self::DateTime => "DateTime",
         }
     }
+
+    /// Parse a (text) value of this type into its JSON representation. It returns none if the value is not valid.<br/>
+    /// The dates are expected as `YYYY-MM-DD`, and the timestamps as `YYYY-MM-DDTHH:MM:SS` (optionally, with fractional seconds).
+    pub fn parse_value(&self, value: &str) -> Option<Value> {
+        //
+        let value = value.trim();
+        match self {
+            Self::Text => Some(Value::from(value)),
+            Self::SmallInteger => value.parse::<i16>().ok().map(Value::from),
+            Self::Integer => value.parse::<i32>().ok().map(Value::from),
+            Self::BigInteger => value.parse::<i64>().ok().map(Value::from),
+            // The range is the one of `f32` (the stored type), but the number is taken as `f64` to keep it as written
+            // (`0.1f32` as `f64` would be `0.10000000149011612`).
+            Self::Decimal => value
+                .parse::<f32>()
+                .ok()
+                .filter(|v| v.is_finite())
+                .and_then(|_| value.parse::<f64>().ok())
+                .map(Value::from),
+            Self::Boolean => match value.to_ascii_lowercase().as_str() {
+                "true" => Some(Value::Bool(true)),
+                "false" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            Self::Date => chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .map(|_| Value::from(value)),
+            Self::DateTime => chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+                .ok()
+                .map(|_| Value::from(value)),
+        }
+    }
 }
 
 impl From<&str> for AttributeValueType {
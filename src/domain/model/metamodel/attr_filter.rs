@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::model::Id;
+
+/// A filter expression over the attributes values of entities.<br/>
+/// Conditions can be combined using `And` and `Or`. An empty `And` matches everything,
+/// while an empty `Or` matches nothing.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AttributeFilter {
+    And(Vec<AttributeFilter>),
+    Or(Vec<AttributeFilter>),
+    Condition { attr: AttributeRef, op: FilterOp },
+}
+
+impl AttributeFilter {
+    //
+    pub fn and(filters: Vec<AttributeFilter>) -> Self {
+        Self::And(filters)
+    }
+
+    pub fn or(filters: Vec<AttributeFilter>) -> Self {
+        Self::Or(filters)
+    }
+
+    pub fn condition(attr: AttributeRef, op: FilterOp) -> Self {
+        Self::Condition { attr, op }
+    }
+
+    /// Get all the attributes referred by the conditions of this filter.
+    pub fn attr_refs(&self) -> Vec<&AttributeRef> {
+        match self {
+            Self::And(filters) | Self::Or(filters) => filters.iter().flat_map(|f| f.attr_refs()).collect(),
+            Self::Condition { attr, .. } => vec![attr],
+        }
+    }
+}
+
+/// A reference to an attribute definition, either by its id or by its name.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AttributeRef {
+    Id(Id),
+    Name(String),
+}
+
+impl std::fmt::Display for AttributeRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Id(id) => write!(f, "id:{}", id),
+            Self::Name(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// The operation (and its operand) that an attribute value is checked against.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum FilterOp {
+    Eq(FilterValue),
+    Neq(FilterValue),
+    Lt(FilterValue),
+    Gt(FilterValue),
+    /// Case insensitive substring match. Applicable to text attributes only.
+    Contains(String),
+    In(Vec<FilterValue>),
+    /// The entity does not have a value for the attribute.
+    IsNull,
+}
+
+/// A value used in a filter condition.<br/>
+/// Dates and timestamps are provided as text, using the ISO 8601 format.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum FilterValue {
+    Text(String),
+    Integer(i64),
+    Real(f64),
+    Boolean(bool),
+}
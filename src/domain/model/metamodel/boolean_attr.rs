@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 /// An instance of an attribute whose value type is boolean.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 pub struct BooleanAttribute {
     //
     /// Its identifier.
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+use super::{AttributeDef, Cardinality, ItemType, Tag};
+use crate::domain::model::Id;
+
+/// A portable (JSON) document that contains the whole metamodel,
+/// used for moving the definitions between instances.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MetamodelBundle {
+    /// The format version of the bundle.
+    pub version: u32,
+    pub tags: Vec<Tag>,
+    pub attribute_defs: Vec<AttributeDef>,
+    pub entity_defs: Vec<BundleEntityDef>,
+    pub entity_link_defs: Vec<BundleEntityLinkDef>,
+}
+
+impl MetamodelBundle {
+    /// The current format version. Bundles with a newer version cannot be imported.
+    pub const VERSION: u32 = 1;
+}
+
+impl Default for MetamodelBundle {
+    fn default() -> Self {
+        Self {
+            version: Self::VERSION,
+            tags: vec![],
+            attribute_defs: vec![],
+            entity_defs: vec![],
+            entity_link_defs: vec![],
+        }
+    }
+}
+
+/// An entity definition, as included in a bundle.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BundleEntityDef {
+    pub id: Id,
+    pub name: String,
+    pub description: Option<String>,
+    pub listing_attr_def_id: Id,
+    /// The ids of its attributes definitions, in their show order.
+    pub attribute_def_ids: Vec<Id>,
+}
+
+/// An entity link definition, as included in a bundle.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BundleEntityLinkDef {
+    pub id: Id,
+    pub name: String,
+    pub description: Option<String>,
+    pub cardinality: Cardinality,
+    pub source_entity_def_id: Id,
+    pub target_entity_def_id: Id,
+    pub attribute_def_ids: Vec<Id>,
+}
+
+/// The plan of importing a bundle, that is what would be (or was) changed.<br/>
+/// A bundle is imported only if there are no conflicts.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ImportPlan {
+    pub actions: Vec<ImportAction>,
+    pub conflicts: Vec<ImportConflict>,
+    /// Whether the plan was applied, that is it was not a dry run and there were no conflicts.
+    pub applied: bool,
+}
+
+impl ImportPlan {
+    //
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+
+    /// Get the number of actions of the provided kind.
+    pub fn count(&self, kind: ImportActionKind) -> usize {
+        self.actions.iter().filter(|a| a.kind == kind).count()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ImportActionKind {
+    Create,
+    Update,
+    Unchanged,
+    /// The item exists, but it is not included in the bundle. It is reported as such, but it is not removed.
+    Absent,
+}
+
+/// The action that is planned for an item of the bundle (or for an existing item that is absent from it).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ImportAction {
+    pub kind: ImportActionKind,
+    pub item_type: ItemType,
+    pub name: String,
+    /// The id of the item in the bundle. It is empty for an absent item.
+    pub bundle_id: Id,
+    /// The id of the item in this instance. It is different than `bundle_id` when
+    /// the item was matched (to an existing one) by its name.
+    pub target_id: Id,
+}
+
+/// An item of the bundle that cannot be imported.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ImportConflict {
+    pub item_type: ItemType,
+    pub name: String,
+    pub reason: String,
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::model::Id;
+
+/// How a CSV import handles the rows that cannot be parsed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum CsvImportMode {
+    /// Nothing is imported if any row has errors.
+    #[default]
+    AllOrNothing,
+    /// The rows with errors are skipped, and the rest are imported.
+    SkipBadRows,
+}
+
+/// The mapping of a CSV column to an attribute definition.<br/>
+/// A column without an attribute definition is ignored.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CsvColumnMapping {
+    pub column: String,
+    pub attr_def_id: Option<Id>,
+}
+
+/// An error of a CSV row.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CsvRowError {
+    /// The line number (in the CSV content) of the row.
+    pub line: u64,
+    /// The column whose value is invalid, if the error is specific to one.
+    pub column: Option<String>,
+    pub message: String,
+}
+
+/// The outcome of a CSV import, or of its preview.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CsvImportReport {
+    /// The columns of the CSV content, as per its header.
+    pub headers: Vec<String>,
+    /// The used mapping. If none was provided, the columns are mapped to the attributes with the same name.
+    pub mapping: Vec<CsvColumnMapping>,
+    /// The number of the (data) rows.
+    pub total_rows: usize,
+    /// The number of rows that have no errors.
+    pub valid_rows: usize,
+    pub errors: Vec<CsvRowError>,
+    /// The first valid rows, as (attribute name, value) pairs, for previewing them.
+    pub preview: Vec<Vec<(String, String)>>,
+    /// The number of the imported entities. It is always zero on preview.
+    pub imported: usize,
+}
+
+impl CsvImportReport {
+    /// The maximum number of rows included in the preview.
+    pub const PREVIEW_ROWS: usize = 20;
+}
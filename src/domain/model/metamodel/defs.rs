@@ -0,0 +1,348 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::{AttributeDef, AttributeValueType, BundleEntityDef, BundleEntityLinkDef, Cardinality, MetamodelBundle, Tag};
+use crate::domain::model::Id;
+
+/// The metamodel described as code (a YAML document that is kept under version control).<br/>
+/// Unlike a bundle, its items have no ids and refer to each other by their names, thus the names
+/// of the items of the same kind must be unique within the document.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetamodelDefs {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<TagDef>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<AttributeDefDef>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entities: Vec<EntityDefDef>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<EntityLinkDefDef>,
+}
+
+/// A tag, as described in the metamodel's code.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TagDef {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// An attribute definition, as described in the metamodel's code.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AttributeDefDef {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The value type, as one of `text`, `smallint`, `integer`, `bigint`, `real`, `boolean`, `date` or `timestamp`.
+    #[serde(rename = "type")]
+    pub value_type: String,
+    #[serde(default)]
+    pub default: String,
+    #[serde(default)]
+    pub required: bool,
+    /// The name of its tag.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+}
+
+/// An entity definition, as described in the metamodel's code.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EntityDefDef {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The names of its attributes definitions, in their show order.
+    pub attributes: Vec<String>,
+    /// The name of its listing attribute, that must be one of its attributes.
+    pub listing_attribute: String,
+}
+
+/// An entity link definition, as described in the metamodel's code.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EntityLinkDefDef {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The cardinality, as one of `1:1`, `1:M` or `M:M`.
+    pub cardinality: String,
+    /// The name of the source entity definition.
+    pub source: String,
+    /// The name of the target entity definition.
+    pub target: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<String>,
+}
+
+impl MetamodelDefs {
+    //
+    /// Describe the provided metamodel, by replacing the ids with names.
+    pub fn from_bundle(bundle: &MetamodelBundle) -> Self {
+        //
+        let tag_names: HashMap<&Id, &String> = bundle.tags.iter().map(|t| (&t.id, &t.name)).collect();
+        let attr_names: HashMap<&Id, &String> = bundle.attribute_defs.iter().map(|a| (&a.id, &a.name)).collect();
+        let ent_def_names: HashMap<&Id, &String> = bundle.entity_defs.iter().map(|d| (&d.id, &d.name)).collect();
+        let names = |names: &HashMap<&Id, &String>, ids: &[Id]| -> Vec<String> {
+            ids.iter().filter_map(|id| names.get(id).map(|n| n.to_string())).collect()
+        };
+
+        Self {
+            tags: bundle
+                .tags
+                .iter()
+                .map(|t| TagDef {
+                    name: t.name.clone(),
+                    description: t.description.clone(),
+                })
+                .collect(),
+            attributes: bundle
+                .attribute_defs
+                .iter()
+                .map(|a| AttributeDefDef {
+                    name: a.name.clone(),
+                    description: a.description.clone(),
+                    value_type: a.value_type.to_string(),
+                    default: a.default_value.clone(),
+                    required: a.is_required,
+                    tag: a.tag_id.as_ref().and_then(|id| tag_names.get(id)).map(|n| n.to_string()),
+                })
+                .collect(),
+            entities: bundle
+                .entity_defs
+                .iter()
+                .map(|d| EntityDefDef {
+                    name: d.name.clone(),
+                    description: d.description.clone(),
+                    attributes: names(&attr_names, &d.attribute_def_ids),
+                    listing_attribute: attr_names.get(&d.listing_attr_def_id).map(|n| n.to_string()).unwrap_or_default(),
+                })
+                .collect(),
+            links: bundle
+                .entity_link_defs
+                .iter()
+                .map(|d| EntityLinkDefDef {
+                    name: d.name.clone(),
+                    description: d.description.clone(),
+                    cardinality: d.cardinality.as_string(),
+                    source: ent_def_names
+                        .get(&d.source_entity_def_id)
+                        .map(|n| n.to_string())
+                        .unwrap_or_default(),
+                    target: ent_def_names
+                        .get(&d.target_entity_def_id)
+                        .map(|n| n.to_string())
+                        .unwrap_or_default(),
+                    attributes: names(&attr_names, &d.attribute_def_ids),
+                })
+                .collect(),
+        }
+    }
+
+    /// Convert the description into a bundle that can be imported into the provided (current) metamodel.<br/>
+    /// Each item gets the id of the existing item with the same name (and, for the attributes definitions, the same description,
+    /// or else the only one with that name), or a new id. The references by name are replaced with these ids.<br/>
+    /// The references can also be to the existing items that are not described, as long as their names are not ambiguous.
+    /// It returns all the problems found (like unknown names or values), if any.
+    pub fn to_bundle(&self, current: &MetamodelBundle) -> Result<MetamodelBundle, Vec<String>> {
+        //
+        let mut errors = vec![];
+
+        let tag_ids = assign_ids(&mut errors, "tag", self.tags.iter().map(|t| &t.name), |name| {
+            current.tags.iter().find(|t| &t.name == name).map(|t| t.id.clone())
+        });
+        let attr_ids = assign_ids(&mut errors, "attribute", self.attributes.iter().map(|a| &a.name), |name| {
+            let attr = self.attributes.iter().find(|a| &a.name == name)?;
+            let same_name: Vec<&AttributeDef> = current.attribute_defs.iter().filter(|a| &a.name == name).collect();
+            match same_name.iter().find(|a| a.description == attr.description) {
+                Some(existing) => Some(existing.id.clone()),
+                None if same_name.len() == 1 => Some(same_name[0].id.clone()),
+                None => None,
+            }
+        });
+        let ent_def_ids = assign_ids(&mut errors, "entity", self.entities.iter().map(|d| &d.name), |name| {
+            current.entity_defs.iter().find(|d| &d.name == name).map(|d| d.id.clone())
+        });
+        let link_def_ids = assign_ids(&mut errors, "link", self.links.iter().map(|d| &d.name), |name| {
+            current.entity_link_defs.iter().find(|d| &d.name == name).map(|d| d.id.clone())
+        });
+        let tag_refs = with_existing(&tag_ids, current.tags.iter().map(|t| (&t.name, &t.id)));
+        let attr_refs = with_existing(&attr_ids, current.attribute_defs.iter().map(|a| (&a.name, &a.id)));
+        let ent_def_refs = with_existing(&ent_def_ids, current.entity_defs.iter().map(|d| (&d.name, &d.id)));
+
+        let mut bundle = MetamodelBundle::default();
+        for tag in self.tags.iter() {
+            bundle
+                .tags
+                .push(Tag::new(tag_ids[&tag.name].clone(), tag.name.clone(), tag.description.clone()));
+        }
+        for attr in self.attributes.iter() {
+            let context = format!("Attribute '{}'", attr.name);
+            let value_type = AttributeValueType::from(attr.value_type.as_str());
+            if value_type.to_string() != attr.value_type {
+                errors.push(format!("{}: unknown type '{}'.", context, attr.value_type));
+            }
+            bundle.attribute_defs.push(AttributeDef::new(
+                attr_ids[&attr.name].clone(),
+                attr.name.clone(),
+                attr.description.clone(),
+                value_type,
+                attr.default.clone(),
+                attr.required,
+                attr.tag.as_ref().map(|tag| lookup(&mut errors, &context, "tag", &tag_refs, tag)),
+            ));
+        }
+        for ent_def in self.entities.iter() {
+            let context = format!("Entity '{}'", ent_def.name);
+            bundle.entity_defs.push(BundleEntityDef {
+                id: ent_def_ids[&ent_def.name].clone(),
+                name: ent_def.name.clone(),
+                description: ent_def.description.clone(),
+                listing_attr_def_id: lookup(&mut errors, &context, "attribute", &attr_refs, &ent_def.listing_attribute),
+                attribute_def_ids: ent_def
+                    .attributes
+                    .iter()
+                    .map(|name| lookup(&mut errors, &context, "attribute", &attr_refs, name))
+                    .collect(),
+            });
+        }
+        for link_def in self.links.iter() {
+            let context = format!("Link '{}'", link_def.name);
+            let cardinality = match link_def.cardinality.as_str() {
+                "1:1" => Cardinality::OneToOne,
+                "1:M" => Cardinality::OneToMany,
+                "M:M" => Cardinality::ManyToMany,
+                other => {
+                    errors.push(format!("{}: unknown cardinality '{}'.", context, other));
+                    Cardinality::ManyToMany
+                }
+            };
+            bundle.entity_link_defs.push(BundleEntityLinkDef {
+                id: link_def_ids[&link_def.name].clone(),
+                name: link_def.name.clone(),
+                description: link_def.description.clone(),
+                cardinality,
+                source_entity_def_id: lookup(&mut errors, &context, "entity", &ent_def_refs, &link_def.source),
+                target_entity_def_id: lookup(&mut errors, &context, "entity", &ent_def_refs, &link_def.target),
+                attribute_def_ids: link_def
+                    .attributes
+                    .iter()
+                    .map(|name| lookup(&mut errors, &context, "attribute", &attr_refs, name))
+                    .collect(),
+            });
+        }
+
+        match errors.is_empty() {
+            true => Ok(bundle),
+            false => Err(errors),
+        }
+    }
+}
+
+/// Assign an id to each (uniquely) named item: the one of the matching existing item, if any, or else a new one.
+fn assign_ids<'a>(
+    errors: &mut Vec<String>,
+    kind: &str,
+    names: impl Iterator<Item = &'a String>,
+    existing_id: impl Fn(&String) -> Option<Id>,
+) -> HashMap<String, Id> {
+    //
+    let mut ids = HashMap::new();
+    for name in names {
+        if ids.contains_key(name) {
+            errors.push(format!("The {} '{}' is described more than once.", kind, name));
+            continue;
+        }
+        // FYI: A new id is a random one, thus not the default (empty) one.
+        let id = match existing_id(name) {
+            Some(id) => id,
+            None => Id::new(),
+        };
+        ids.insert(name.clone(), id);
+    }
+    ids
+}
+
+/// Add the ids of the existing items that are not described, so that they can be referred to by their names as well.<br/>
+/// The names that more than one of these items have are ambiguous, thus these items cannot be referred to.
+fn with_existing<'a>(ids: &HashMap<String, Id>, existing: impl Iterator<Item = (&'a String, &'a Id)>) -> HashMap<String, Id> {
+    //
+    let mut refs = ids.clone();
+    let mut ambiguous = HashSet::new();
+    for (name, id) in existing.filter(|(name, _)| !ids.contains_key(*name)) {
+        if refs.insert(name.clone(), id.clone()).is_some_and(|other_id| &other_id != id) {
+            ambiguous.insert(name.clone());
+        }
+    }
+    refs.retain(|name, _| !ambiguous.contains(name));
+    refs
+}
+
+/// Get the id of the item of the provided kind that is referred by its name.
+fn lookup(errors: &mut Vec<String>, context: &str, kind: &str, ids: &HashMap<String, Id>, name: &str) -> Id {
+    match ids.get(name) {
+        Some(id) => id.clone(),
+        None => {
+            errors.push(format!("{}: unknown {} '{}'.", context, kind, name));
+            Id::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //
+    use super::*;
+
+    fn existing() -> MetamodelBundle {
+        let attr = |id: &str, name: &str| AttributeDef::new(Id::from(id), name.into(), None, AttributeValueType::Text, "".into(), false, None);
+        MetamodelBundle {
+            attribute_defs: vec![attr("a1", "Name"), attr("a2", "Code"), attr("a3", "Code")],
+            entity_defs: vec![BundleEntityDef {
+                id: Id::from("e1"),
+                name: "Service".into(),
+                description: None,
+                listing_attr_def_id: Id::from("a1"),
+                attribute_def_ids: vec![Id::from("a1")],
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn to_bundle_refers_to_the_existing_items_that_are_not_described() {
+        //
+        let defs = MetamodelDefs {
+            links: vec![EntityLinkDefDef {
+                name: "depends on".into(),
+                description: None,
+                cardinality: "M:M".into(),
+                source: "Service".into(),
+                target: "Service".into(),
+                attributes: vec!["Name".into()],
+            }],
+            ..Default::default()
+        };
+        let bundle = defs.to_bundle(&existing()).expect("the definitions are valid");
+        let link_def = &bundle.entity_link_defs[0];
+        assert_eq!(link_def.source_entity_def_id, Id::from("e1"));
+        assert_eq!(link_def.target_entity_def_id, Id::from("e1"));
+        assert_eq!(link_def.attribute_def_ids, vec![Id::from("a1")]);
+        assert!(bundle.entity_defs.is_empty());
+    }
+
+    #[test]
+    fn to_bundle_does_not_refer_to_the_existing_items_with_ambiguous_names() {
+        //
+        let defs = MetamodelDefs {
+            entities: vec![EntityDefDef {
+                name: "Service".into(),
+                description: None,
+                attributes: vec!["Name".into(), "Code".into()],
+                listing_attribute: "Name".into(),
+            }],
+            ..Default::default()
+        };
+        let errors = defs.to_bundle(&existing()).expect_err("the 'Code' attribute is ambiguous");
+        assert_eq!(errors, vec!["Entity 'Service': unknown attribute 'Code'.".to_string()]);
+    }
+}
@@ -0,0 +1,112 @@
+use super::{Cardinality, EntityDef, EntityLinkDef};
+use crate::domain::model::Id;
+
+/// A diagram of the (user level) metamodel: the entities definitions with their attributes definitions,
+/// and the entity links definitions between them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MetamodelDiagram {
+    pub ent_defs: Vec<EntityDef>,
+    pub ent_link_defs: Vec<EntityLinkDef>,
+}
+
+impl MetamodelDiagram {
+    //
+    pub fn new(ent_defs: Vec<EntityDef>, ent_link_defs: Vec<EntityLinkDef>) -> Self {
+        Self { ent_defs, ent_link_defs }
+    }
+
+    /// Export the diagram as a Mermaid `erDiagram`.
+    pub fn to_mermaid(&self) -> String {
+        //
+        let mut out = String::from("erDiagram\n");
+        for ent_def in self.ent_defs.iter() {
+            out.push_str(&format!("    {} {{\n", identifier(&ent_def.name)));
+            for attr_def in ent_def.attributes.iter() {
+                let required = if attr_def.is_required { " \"required\"" } else { "" };
+                out.push_str(&format!(
+                    "        {} {}{}\n",
+                    attr_def.value_type,
+                    identifier(&attr_def.name),
+                    required
+                ));
+            }
+            out.push_str("    }\n");
+        }
+        for eld in self.ent_link_defs.iter() {
+            if let (Some(source), Some(target)) = (
+                self.ent_def_name(&eld.source_entity_def_id),
+                self.ent_def_name(&eld.target_entity_def_id),
+            ) {
+                out.push_str(&format!(
+                    "    {} {} {} : \"{}\"\n",
+                    identifier(source),
+                    crows_foot(&eld.cardinality),
+                    identifier(target),
+                    eld.name.replace('"', "'")
+                ));
+            }
+        }
+        out
+    }
+
+    /// Export the diagram as a PlantUML (Information Engineering) entity relationship diagram.
+    pub fn to_plantuml(&self) -> String {
+        //
+        let mut out = String::from("@startuml\nhide circle\nskinparam linetype ortho\n\n");
+        for ent_def in self.ent_defs.iter() {
+            out.push_str(&format!("entity \"{}\" as {} {{\n", ent_def.name.replace('"', "'"), alias(ent_def)));
+            for attr_def in ent_def.attributes.iter() {
+                let required = if attr_def.is_required { "* " } else { "" };
+                out.push_str(&format!("  {}{} : {}\n", required, attr_def.name, attr_def.value_type));
+            }
+            out.push_str("}\n\n");
+        }
+        for eld in self.ent_link_defs.iter() {
+            let source = self.ent_defs.iter().find(|ed| ed.id == eld.source_entity_def_id);
+            let target = self.ent_defs.iter().find(|ed| ed.id == eld.target_entity_def_id);
+            if let (Some(source), Some(target)) = (source, target) {
+                out.push_str(&format!(
+                    "{} {} {} : {}\n",
+                    alias(source),
+                    crows_foot(&eld.cardinality),
+                    alias(target),
+                    eld.name
+                ));
+            }
+        }
+        out.push_str("@enduml\n");
+        out
+    }
+
+    fn ent_def_name(&self, id: &Id) -> Option<&String> {
+        self.ent_defs.iter().find(|ed| ed.id == *id).map(|ed| &ed.name)
+    }
+}
+
+/// The crow's foot notation of a cardinality, as used by both Mermaid and PlantUML.
+fn crows_foot(cardinality: &Cardinality) -> &'static str {
+    match cardinality {
+        Cardinality::OneToOne => "||--||",
+        Cardinality::OneToMany => "||--o{",
+        Cardinality::ManyToMany => "}o--o{",
+    }
+}
+
+/// A name usable as an identifier in the diagram languages (only alphanumerics, `_` and `-` are kept).
+fn identifier(name: &str) -> String {
+    let id: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '_' || c == '-' { c } else { '_' })
+        .collect();
+    if id.is_empty() {
+        "_".into()
+    } else {
+        id
+    }
+}
+
+/// The (PlantUML) alias of an entity definition, based on its id, so that it stays unique.
+fn alias(ent_def: &EntityDef) -> String {
+    format!("ed_{}", identifier(ent_def.id.as_str()))
+}
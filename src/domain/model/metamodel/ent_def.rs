@@ -5,7 +5,9 @@ use serde::{Deserialize, Serialize};
 
 /// The definition of an `Entity`.
 #[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 pub struct EntityDef {
+    #[serde(default)]
     pub id: Id,
     pub name: String,
     pub description: Option<String>,
@@ -0,0 +1,125 @@
+use serde_json::{json, Map, Value};
+
+use super::{AttributeDef, AttributeValueType, EntityDef};
+use crate::domain::model::RdfVocabulary;
+
+impl EntityDef {
+    //
+    /// Get the JSON Schema (draft 2020-12) of the payload that creates (or updates) an instance of this definition.<br/>
+    /// As per `Entity`, the attributes are provided in an array per value type, and each one refers to its definition by `def_id`.
+    /// Only the value types that an entity can carry (text, small integer, integer and boolean) are described.
+    /// The other fields of `Entity` are optional, as they are derived from the definition.
+    pub fn to_json_schema(&self) -> Value {
+        //
+        let mut properties = Map::new();
+        properties.insert("def_id".into(), json!({ "const": self.id.to_string() }));
+        properties.insert(
+            "listing_attr_def_id".into(),
+            json!({
+                "type": "string",
+                "description": "The definition id of the listing attribute. If empty, the one of the entity definition is used.",
+            }),
+        );
+        let mut required = vec![Value::from("def_id")];
+        for (field, value_type) in [
+            ("text_attributes", AttributeValueType::Text),
+            ("smallint_attributes", AttributeValueType::SmallInteger),
+            ("int_attributes", AttributeValueType::Integer),
+            ("boolean_attributes", AttributeValueType::Boolean),
+        ] {
+            let attr_defs: Vec<&AttributeDef> = self
+                .attributes
+                .iter()
+                .filter(|attr_def| attr_def.value_type == value_type)
+                .collect();
+            if attr_defs.iter().any(|attr_def| attr_def.is_required) {
+                required.push(Value::from(field));
+            }
+            properties.insert(field.into(), attributes_json_schema(&attr_defs));
+        }
+        let mut schema = json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "$id": format!("{}entity-defs:{}:schema", RdfVocabulary::DEFAULT_BASE_IRI, self.id),
+            "title": self.name,
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        });
+        if let Some(description) = &self.description {
+            schema["description"] = Value::String(description.clone());
+        }
+        schema
+    }
+}
+
+/// Get the JSON Schema of an array of attributes (of the same value type), as per the provided definitions.<br/>
+/// Each item must refer to one of the definitions, and the required ones must be included.
+fn attributes_json_schema(attr_defs: &[&AttributeDef]) -> Value {
+    //
+    if attr_defs.is_empty() {
+        return json!({ "type": "array", "maxItems": 0 });
+    }
+    let items: Vec<Value> = attr_defs.iter().filter_map(|attr_def| attr_def.to_json_schema()).collect();
+    let contains: Vec<Value> = attr_defs
+        .iter()
+        .filter(|attr_def| attr_def.is_required)
+        .map(|attr_def| json!({ "contains": { "properties": { "def_id": { "const": attr_def.id.to_string() } }, "required": ["def_id"] } }))
+        .collect();
+    let mut schema = json!({ "type": "array", "items": { "oneOf": items } });
+    if !contains.is_empty() {
+        schema["allOf"] = Value::Array(contains);
+    }
+    schema
+}
+
+impl AttributeDef {
+    //
+    /// Get the JSON Schema of an attribute of this definition, as an entity carries it.
+    /// It is `None` if the value type cannot be carried by an entity.<br/>
+    /// The default value is included only if it is valid for the value type.
+    pub fn to_json_schema(&self) -> Option<Value> {
+        //
+        let mut value = match self.value_type {
+            AttributeValueType::Text => json!({ "type": "string" }),
+            AttributeValueType::SmallInteger => json!({ "type": "integer", "minimum": i16::MIN, "maximum": i16::MAX }),
+            AttributeValueType::Integer => json!({ "type": "integer", "minimum": i32::MIN, "maximum": i32::MAX }),
+            AttributeValueType::Boolean => json!({ "type": "boolean" }),
+            _ => return None,
+        };
+        if let Some(default) = self.default_json_value() {
+            value["default"] = default;
+        }
+        let mut schema = json!({
+            "title": self.name,
+            "type": "object",
+            "properties": {
+                "def_id": { "const": self.id.to_string() },
+                "value": value,
+                "id": { "type": "string", "description": "Not used, it is assigned on creation." },
+                "name": { "type": "string", "description": "Not used, it is the definition's name." },
+                "owner_id": { "type": "string", "description": "Not used, it is the entity's id." },
+            },
+            "required": ["id", "name", "value", "def_id", "owner_id"],
+        });
+        if let Some(description) = &self.description {
+            schema["description"] = Value::String(description.clone());
+        }
+        Some(schema)
+    }
+
+    /// Get the default value as a JSON value of the proper type, if it is provided and valid.
+    fn default_json_value(&self) -> Option<Value> {
+        //
+        let value = self.default_value.trim();
+        if value.is_empty() && self.value_type != AttributeValueType::Text {
+            return None;
+        }
+        match self.value_type {
+            AttributeValueType::Text => Some(Value::String(self.default_value.clone())),
+            AttributeValueType::SmallInteger => value.parse::<i16>().ok().map(Value::from),
+            AttributeValueType::Integer => value.parse::<i32>().ok().map(Value::from),
+            AttributeValueType::Boolean => value.parse::<bool>().ok().map(Value::from),
+            _ => None,
+        }
+    }
+}
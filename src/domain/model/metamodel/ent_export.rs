@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// The format of an export of entities (or of their links).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// Comma separated values, having a header row.
+    #[default]
+    Csv,
+    /// JSON Lines, that is one JSON object per line.
+    Jsonl,
+}
+
+impl ExportFormat {
+    //
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Jsonl => "application/jsonl; charset=utf-8",
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Jsonl => "jsonl",
+        }
+    }
+}
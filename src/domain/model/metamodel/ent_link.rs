@@ -3,10 +3,13 @@ use crate::domain::model::Id;
 use super::{BooleanAttribute, IntegerAttribute, SmallintAttribute, TextAttribute};
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 pub struct EntityLink {
+    #[serde(default)]
     pub id: Id,
 
     /// Its kind, that is its definition name.
+    #[serde(default)]
     pub kind: String,
 
     /// Its definition id.
@@ -7,7 +7,9 @@ use super::AttributeDef;
 
 /// The definition of an entity link.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 pub struct EntityLinkDef {
+    #[serde(default)]
     pub id: Id,
     pub name: String,
     pub description: Option<String>,
@@ -60,6 +62,7 @@ impl EntityLinkDef {
 
 /// The cardinality of an entity link definition.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 pub enum Cardinality {
     OneToOne,
     OneToMany,
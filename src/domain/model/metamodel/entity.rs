@@ -3,11 +3,14 @@ use crate::domain::model::Id;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 pub struct Entity {
     //
+    #[serde(default)]
     pub id: Id,
 
     /// Its kind, that is its definition name.
+    #[serde(default)]
     pub kind: String,
 
     /// Its definition id.
@@ -29,8 +32,11 @@ pub struct Entity {
     #[serde(default)]
     pub boolean_attributes: Vec<BooleanAttribute>,
 
+    #[serde(default)]
     pub listing_attr_def_id: Id,
+    #[serde(default)]
     pub listing_attr_name: String,
+    #[serde(default)]
     pub listing_attr_value: String,
 }
 
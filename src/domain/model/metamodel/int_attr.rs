@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 /// Its value ranges from -2147483648 to +2147483647.\
 /// Stored in PostgreSQL in an `int4` data type.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 pub struct IntegerAttribute {
     //
     /// Its identifier.
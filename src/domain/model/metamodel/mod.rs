@@ -4,12 +4,32 @@ pub use attributes::*;
 mod attr_def;
 pub use attr_def::*;
 
+mod attr_filter;
+pub use attr_filter::*;
+
 mod boolean_attr;
 pub use boolean_attr::*;
 
+mod bundle;
+pub use bundle::*;
+
+mod csv_import;
+pub use csv_import::*;
+
+mod defs;
+pub use defs::*;
+
+mod diagram;
+pub use diagram::*;
+
 mod ent_def;
 pub use ent_def::*;
 
+mod ent_def_schema;
+
+mod ent_export;
+pub use ent_export::*;
+
 mod ent_link_def;
 pub use ent_link_def::*;
 
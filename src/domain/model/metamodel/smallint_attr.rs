@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 /// Its value ranges from -128 to 127.
 /// /// Stored in PostgreSQL in an `int2` data type.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 pub struct SmallintAttribute {
     //
     /// Its identifier.
@@ -2,7 +2,9 @@ use crate::domain::model::Id;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 pub struct Tag {
+    #[serde(default)]
     pub id: Id,
     pub name: String,
     pub description: Option<String>,
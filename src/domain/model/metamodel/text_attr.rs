@@ -3,6 +3,7 @@ use crate::domain::model::Id;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 pub struct TextAttribute {
     //
     /// Its identifier.
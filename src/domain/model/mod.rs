@@ -1,8 +1,32 @@
 mod metamodel;
 pub use metamodel::*;
 
+mod config;
+pub use config::*;
+
+mod graph;
+pub use graph::*;
+
+mod graph_export;
+pub use graph_export::*;
+
 mod id;
 pub use id::*;
 
+mod pagination;
+pub use pagination::*;
+
+mod rdf;
+pub use rdf::*;
+
+mod registry;
+pub use registry::*;
+
+mod saved_view;
+pub use saved_view::*;
+
+mod search;
+pub use search::*;
+
 mod user;
 pub use user::*;
@@ -0,0 +1,228 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::model::Id;
+
+/// The options for getting a page of a (sorted) list of items.<br/>
+/// If no values are provided, the default values are page=1 and limit=10.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Pagination {
+    pub page: Option<i32>,
+    pub limit: Option<i32>,
+
+    /// The name of the field to sort by. Each list has its own set of sortable fields,
+    /// and the entities can also be sorted by the value of an attribute, using its definition id.
+    pub sort_by: Option<String>,
+
+    /// Whether to sort in descending order. By default, the order is ascending.
+    pub sort_desc: Option<bool>,
+}
+
+impl Pagination {
+    //
+    pub const DEFAULT_LIMIT: i32 = 10;
+    pub const MAX_LIMIT: i32 = 500;
+
+    pub fn new(page: i32, limit: i32) -> Self {
+        Self {
+            page: Some(page),
+            limit: Some(limit),
+            sort_by: None,
+            sort_desc: None,
+        }
+    }
+
+    pub fn sorted_by(mut self, sort_by: &str, sort_desc: bool) -> Self {
+        self.sort_by = Some(sort_by.to_string());
+        self.sort_desc = Some(sort_desc);
+        self
+    }
+
+    pub fn get_page(&self) -> i32 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn get_limit(&self) -> i32 {
+        self.limit.unwrap_or(Self::DEFAULT_LIMIT).clamp(1, Self::MAX_LIMIT)
+    }
+
+    /// Get the pagination's `offset` and `limit` options.
+    pub fn get_offset_limit(&self) -> (i64, i64) {
+        let limit = self.get_limit() as i64;
+        let offset = (self.get_page() as i64 - 1) * limit;
+        (offset, limit)
+    }
+
+    /// Get the `offset` and `limit` options from an optional pagination.<br/>
+    /// If no pagination is provided, there is no limit (so that all the items are included).
+    pub fn get_offset_limit_opt(pagination_opt: Option<&Pagination>) -> (i64, Option<i64>) {
+        match pagination_opt {
+            Some(p) => {
+                let (offset, limit) = p.get_offset_limit();
+                (offset, Some(limit))
+            }
+            None => (0, None),
+        }
+    }
+
+    /// Get the column to sort by, if `sort_by` is one of the `sortable` (field name, column name) pairs.
+    pub fn get_sort_column<'a>(&self, sortable: &[(&str, &'a str)]) -> Option<&'a str> {
+        let sort_by = self.sort_by.as_ref()?;
+        sortable.iter().find(|(field, _)| field == sort_by).map(|(_, column)| *column)
+    }
+
+    pub fn get_sort_direction(&self) -> &'static str {
+        if self.sort_desc.unwrap_or_default() {
+            "DESC"
+        } else {
+            "ASC"
+        }
+    }
+
+    /// Get the `ORDER BY` expression of an SQL query, using the `sortable` (field name, column name) pairs
+    /// and the `default_column` in case `sort_by` is not provided or is unknown.
+    pub fn get_order_by(pagination_opt: Option<&Pagination>, sortable: &[(&str, &str)], default_column: &str) -> String {
+        match pagination_opt {
+            Some(p) => format!(
+                "{} {}",
+                p.get_sort_column(sortable).unwrap_or(default_column),
+                p.get_sort_direction()
+            ),
+            None => default_column.to_string(),
+        }
+    }
+}
+
+impl Default for Pagination {
+    fn default() -> Self {
+        Self::new(1, Self::DEFAULT_LIMIT)
+    }
+}
+
+/// A page of a list of items, including the total number of items in the list.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub page: i32,
+    pub limit: i32,
+    pub total: i64,
+}
+
+impl<T> Page<T> {
+    //
+    /// Create the page of `items`, as per the provided pagination.<br/>
+    /// If no pagination is provided, the page contains all the items.
+    pub fn new(items: Vec<T>, pagination_opt: Option<&Pagination>, total: i64) -> Self {
+        match pagination_opt {
+            Some(p) => Self {
+                items,
+                page: p.get_page(),
+                limit: p.get_limit(),
+                total,
+            },
+            None => Self::all(items),
+        }
+    }
+
+    /// Create a page that contains all the items.
+    pub fn all(items: Vec<T>) -> Self {
+        let total = items.len() as i64;
+        Self {
+            items,
+            page: 1,
+            limit: total.max(1) as i32,
+            total,
+        }
+    }
+
+    /// The number of pages, considering the total number of items.
+    pub fn page_count(&self) -> i32 {
+        let limit = self.limit.max(1) as i64;
+        ((self.total + limit - 1) / limit).max(1) as i32
+    }
+
+    pub fn has_prev(&self) -> bool {
+        self.page > 1
+    }
+
+    pub fn has_next(&self) -> bool {
+        self.page < self.page_count()
+    }
+}
+
+impl<T> Default for Page<T> {
+    fn default() -> Self {
+        Self::all(vec![])
+    }
+}
+
+/// A position within a list that is sorted by a (sort key, id) pair, used for keyset pagination.<br/>
+/// It is exchanged with the clients in its encoded form, which is opaque to them.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Cursor {
+    pub sort_key: String,
+    pub id: Id,
+}
+
+impl Cursor {
+    //
+    pub fn new(sort_key: String, id: Id) -> Self {
+        Self { sort_key, id }
+    }
+
+    /// Encode the cursor as a hex string of its `id:sort_key` form.
+    pub fn encode(&self) -> String {
+        format!("{}:{}", self.id, self.sort_key)
+            .bytes()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Decode a cursor that was previously encoded. It returns `None` if the input is not a valid cursor.
+    pub fn decode(encoded: &str) -> Option<Self> {
+        if !encoded.len().is_multiple_of(2) {
+            return None;
+        }
+        let bytes = (0..encoded.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(encoded.get(i..i + 2)?, 16).ok())
+            .collect::<Option<Vec<u8>>>()?;
+        let decoded = String::from_utf8(bytes).ok()?;
+        let (id, sort_key) = decoded.split_once(':')?;
+        if id.is_empty() {
+            return None;
+        }
+        Some(Self::new(sort_key.to_string(), Id::from(id)))
+    }
+}
+
+/// A page of a list that uses keyset pagination.<br/>
+/// The `next_cursor` is provided for getting the next page, if there is one.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CursorPage<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+impl<T> CursorPage<T> {
+    //
+    /// Create a page out of the `items` fetched with a limit of `limit + 1`, so that the extra item
+    /// (if any) signals that there is a next page. The `cursor_of` function provides the cursor of an item.
+    pub fn new(mut items: Vec<T>, limit: i64, cursor_of: impl Fn(&T) -> Cursor) -> Self {
+        let mut next_cursor = None;
+        if items.len() as i64 > limit {
+            items.truncate(limit as usize);
+            next_cursor = items.last().map(|item| cursor_of(item).encode());
+        }
+        Self { items, next_cursor }
+    }
+}
+
+impl<T> Default for CursorPage<T> {
+    fn default() -> Self {
+        Self {
+            items: vec![],
+            next_cursor: None,
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::domain::model::{AttributeValueType, Id};
+
+/// The RDF serialization format of an export of the knowledge base.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RdfFormat {
+    #[default]
+    Turtle,
+    JsonLd,
+}
+
+impl RdfFormat {
+    //
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Turtle => "text/turtle; charset=utf-8",
+            Self::JsonLd => "application/ld+json",
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            Self::Turtle => "ttl",
+            Self::JsonLd => "jsonld",
+        }
+    }
+}
+
+/// The mapping of the metamodel and its instances to RDF terms.<br/>
+/// Entity definitions are mapped to classes, attribute definitions to datatype properties,
+/// link definitions to object properties, and entities to individuals. All the IRIs are made of
+/// the base IRI, the kind of the item and its `Id`, so they are stable across renames.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RdfVocabulary {
+    pub base_iri: String,
+}
+
+impl RdfVocabulary {
+    //
+    pub const RDF: &'static str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+    pub const RDFS: &'static str = "http://www.w3.org/2000/01/rdf-schema#";
+    pub const OWL: &'static str = "http://www.w3.org/2002/07/owl#";
+    pub const XSD: &'static str = "http://www.w3.org/2001/XMLSchema#";
+
+    /// The base IRI used when none is configured.
+    pub const DEFAULT_BASE_IRI: &'static str = "urn:cognitive:";
+
+    /// Create the vocabulary, if the provided base IRI is valid (non empty, and without characters that are not allowed in an IRI).
+    pub fn new(base_iri: &str) -> Option<Self> {
+        let invalid = |c: char| c.is_whitespace() || c.is_control() || "<>\"{}|\\^`".contains(c);
+        if base_iri.is_empty() || !base_iri.contains(':') || base_iri.chars().any(invalid) {
+            return None;
+        }
+        Some(Self {
+            base_iri: base_iri.to_string(),
+        })
+    }
+
+    pub fn class_iri(&self, ent_def_id: &Id) -> String {
+        format!("{}class/{}", self.base_iri, ent_def_id)
+    }
+
+    pub fn datatype_property_iri(&self, attr_def_id: &Id) -> String {
+        format!("{}attribute/{}", self.base_iri, attr_def_id)
+    }
+
+    pub fn object_property_iri(&self, ent_link_def_id: &Id) -> String {
+        format!("{}link/{}", self.base_iri, ent_link_def_id)
+    }
+
+    pub fn individual_iri(&self, ent_id: &Id) -> String {
+        format!("{}entity/{}", self.base_iri, ent_id)
+    }
+
+    /// The XML Schema datatype (local name) of the values of an attribute type.
+    pub fn xsd_type(value_type: &AttributeValueType) -> &'static str {
+        match value_type {
+            AttributeValueType::Text => "string",
+            AttributeValueType::SmallInteger => "short",
+            AttributeValueType::Integer => "int",
+            AttributeValueType::BigInteger => "long",
+            AttributeValueType::Decimal => "float",
+            AttributeValueType::Boolean => "boolean",
+            AttributeValueType::Date => "date",
+            AttributeValueType::DateTime => "dateTime",
+        }
+    }
+
+    /// The Turtle prefixes declaration of the used (standard) vocabularies.
+    pub fn turtle_prefixes() -> String {
+        format!(
+            "@prefix rdf: <{}> .\n@prefix rdfs: <{}> .\n@prefix owl: <{}> .\n@prefix xsd: <{}> .\n\n",
+            Self::RDF,
+            Self::RDFS,
+            Self::OWL,
+            Self::XSD
+        )
+    }
+
+    /// The JSON-LD context of the used (standard) vocabularies.
+    pub fn json_ld_context() -> Value {
+        json!({ "rdf": Self::RDF, "rdfs": Self::RDFS, "owl": Self::OWL, "xsd": Self::XSD })
+    }
+}
+
+/// An RDF resource (subject) with its statements, that can be written as Turtle or as a JSON-LD node.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RdfResource {
+    pub iri: String,
+    /// The (prefixed or full) IRIs of its types.
+    pub types: Vec<String>,
+    pub statements: Vec<(String, RdfObject)>,
+}
+
+/// The object of an RDF statement.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RdfObject {
+    /// An IRI, either prefixed (like `xsd:string`) or full.
+    Iri(String),
+    /// A literal, with its datatype's local name in XML Schema.
+    Literal(String, &'static str),
+}
+
+impl RdfResource {
+    //
+    pub fn new(iri: String, rdf_type: &str) -> Self {
+        Self {
+            iri,
+            types: vec![rdf_type.to_string()],
+            statements: vec![],
+        }
+    }
+
+    pub fn add(&mut self, predicate: &str, object: RdfObject) {
+        self.statements.push((predicate.to_string(), object));
+    }
+
+    pub fn to_turtle(&self) -> String {
+        //
+        let mut out = format!(
+            "{}\n    a {}",
+            turtle_iri(&self.iri),
+            self.types.iter().map(|t| turtle_iri(t)).collect::<Vec<_>>().join(", ")
+        );
+        for (predicate, object) in self.statements.iter() {
+            let object = match object {
+                RdfObject::Iri(iri) => turtle_iri(iri),
+                RdfObject::Literal(value, xsd_type) => match *xsd_type {
+                    "string" => turtle_string(value),
+                    _ => format!("{}^^xsd:{}", turtle_string(value), xsd_type),
+                },
+            };
+            out.push_str(&format!(" ;\n    {} {}", turtle_iri(predicate), object));
+        }
+        out.push_str(" .\n\n");
+        out
+    }
+
+    pub fn to_json_ld(&self) -> Value {
+        //
+        let mut node = serde_json::Map::new();
+        node.insert("@id".into(), Value::String(self.iri.clone()));
+        node.insert("@type".into(), json!(self.types));
+        for (predicate, object) in self.statements.iter() {
+            let object = match object {
+                RdfObject::Iri(iri) => json!({ "@id": iri }),
+                RdfObject::Literal(value, "string") => Value::String(value.clone()),
+                RdfObject::Literal(value, xsd_type) => json!({ "@value": value, "@type": format!("xsd:{}", xsd_type) }),
+            };
+            match node.get_mut(predicate) {
+                Some(Value::Array(objects)) => objects.push(object),
+                Some(existing) => *existing = Value::Array(vec![existing.take(), object]),
+                None => {
+                    node.insert(predicate.clone(), object);
+                }
+            }
+        }
+        Value::Object(node)
+    }
+}
+
+/// A prefixed name (of the standard vocabularies) is written as it is, while a full IRI is enclosed in angle brackets.
+fn turtle_iri(iri: &str) -> String {
+    match ["rdf:", "rdfs:", "owl:", "xsd:"].iter().any(|prefix| iri.starts_with(prefix)) {
+        true => iri.to_string(),
+        false => format!("<{}>", iri),
+    }
+}
+
+fn turtle_string(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
@@ -0,0 +1,150 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use crate::domain::model::Id;
+
+/// The built-in "Service Instance" entity definition and its attributes definitions.<br/>
+/// These are created by a database migration with fixed ids, thus they can be referred to by the application.
+pub struct ServiceInstanceDef;
+
+impl ServiceInstanceDef {
+    pub const ID: &'static str = "svcinst-ed";
+    /// The listing attribute, whose value is the service name and the address.
+    pub const INSTANCE_ATTR_ID: &'static str = "svcinst-in";
+    pub const SERVICE_ATTR_ID: &'static str = "svcinst-sn";
+    pub const VERSION_ATTR_ID: &'static str = "svcinst-sv";
+    pub const ADDRESS_ATTR_ID: &'static str = "svcinst-ad";
+    pub const METADATA_ATTR_ID: &'static str = "svcinst-md";
+    pub const TAGS_ATTR_ID: &'static str = "svcinst-tg";
+    pub const TTL_ATTR_ID: &'static str = "svcinst-tt";
+    pub const HEARTBEAT_ATTR_ID: &'static str = "svcinst-hb";
+    pub const EXPIRES_AT_ATTR_ID: &'static str = "svcinst-ex";
+    pub const HEALTH_CHECK_ATTR_ID: &'static str = "svcinst-hc";
+    pub const HEALTH_STATUS_ATTR_ID: &'static str = "svcinst-hs";
+    pub const LATENCY_ATTR_ID: &'static str = "svcinst-lt";
+    pub const FAILED_CHECKS_ATTR_ID: &'static str = "svcinst-fc";
+    pub const LAST_CHECKED_ATTR_ID: &'static str = "svcinst-lc";
+}
+
+/// An instance of a service, as registered in the service registry.<br/>
+/// It is stored as an entity of the built-in "Service Instance" definition.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct ServiceInstance {
+    /// Its id, that is the id of its entity.
+    pub id: Id,
+    pub service: String,
+    pub version: String,
+    /// The address (like `host:port` or a URL) it is reached at.
+    pub address: String,
+    pub metadata: BTreeMap<String, String>,
+    pub tags: Vec<String>,
+    /// The seconds it stays registered without a heartbeat.
+    pub ttl_secs: i32,
+    /// The time (RFC 3339) of its latest heartbeat (or registration).
+    pub last_heartbeat: String,
+    /// The time (RFC 3339) when it expires, unless it sends a heartbeat.
+    pub expires_at: String,
+    /// How it is actively probed: an `http://` or `https://` URL, or a `tcp://host:port` address. If empty, it is not probed.
+    pub health_check: String,
+    pub health_status: HealthStatus,
+    /// The milliseconds its latest health check took.
+    pub latency_ms: i32,
+    /// The number of its consecutive failed health checks.
+    pub failed_checks: i32,
+    /// The time (RFC 3339) of its latest health check.
+    pub last_checked: String,
+}
+
+/// The health of a service instance, as per its active health checks.<br/>
+/// An instance that is not (yet) probed has an unknown health, and it is considered healthy while it sends heartbeats.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Display, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    #[default]
+    #[strum(to_string = "unknown")]
+    Unknown,
+    #[strum(to_string = "up")]
+    Up,
+    /// It failed (at least) the configured number of consecutive health checks.
+    #[strum(to_string = "down")]
+    Down,
+}
+
+impl From<&str> for HealthStatus {
+    fn from(value: &str) -> Self {
+        match value {
+            "up" => Self::Up,
+            "down" => Self::Down,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// The registration of a service instance, as sent by the instance itself.<br/>
+/// An instance is identified by its service and address, thus registering it again updates the existing registration.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct ServiceRegistration {
+    pub service: String,
+    #[serde(default)]
+    pub version: String,
+    pub address: String,
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// The seconds it stays registered without a heartbeat. If not provided, the registry's default is used.
+    #[serde(default)]
+    pub ttl_secs: Option<i32>,
+    /// How it is actively probed: an `http://` or `https://` URL (that must respond with a 2xx or 3xx status) or
+    /// a `tcp://host:port` address (that must accept connections). Its host must be the one of the instance's address.
+    /// If not provided, it is not probed.
+    #[serde(default)]
+    pub health_check: String,
+}
+
+/// The order of the discovered instances, that is a hint for the clients on how to balance their load.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DiscoveryOrder {
+    /// Sorted by address, thus always the same.
+    #[default]
+    Sorted,
+    /// Shuffled on each lookup.
+    Random,
+    /// Rotated by one on each lookup of the same service, thus each instance takes turns in being the first one.
+    RoundRobin,
+}
+
+/// The lookup of the instances of a service, optionally only the ones with the provided version and tag.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DiscoveryQuery {
+    pub service: String,
+    pub version: Option<String>,
+    pub tag: Option<String>,
+    pub order: DiscoveryOrder,
+}
+
+impl DiscoveryQuery {
+    //
+    /// Whether the provided instance (that is assumed to be of the same service) is looked up.
+    pub fn matches(&self, instance: &ServiceInstance) -> bool {
+        self.version.iter().all(|version| &instance.version == version) && self.tag.iter().all(|tag| instance.tags.contains(tag))
+    }
+}
+
+/// The outcome of looking up the healthy instances of a service.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct ServiceDiscovery {
+    pub service: String,
+    /// The digest of the set of instances. It changes only when the set (or any of its instances) changes,
+    /// thus it can be provided back to wait for such a change.
+    pub index: String,
+    pub instances: Vec<ServiceInstance>,
+}
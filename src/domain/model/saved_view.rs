@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::model::{AttributeFilter, Id, Pagination};
+
+/// A named query over the entities, that can be re-opened later.<br/>
+/// It is owned by the user who created it and, if shared, it is visible (read-only) to the other users as well.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SavedView {
+    pub id: Id,
+    pub name: String,
+
+    /// The id of the user who owns (created) it.
+    pub owner_id: Id,
+
+    /// If true, the other users can see and open it as well.
+    pub shared: bool,
+
+    /// The definition of the entities to list. If not provided, all the entities are listed.
+    pub def_id: Option<Id>,
+
+    /// The filter on the entities' attributes. It is applicable only if `def_id` is provided.
+    pub filter: Option<AttributeFilter>,
+
+    /// The field (or attribute definition id) to sort the entities by.
+    pub sort_by: Option<String>,
+    pub sort_desc: bool,
+
+    /// The ids of the attribute definitions whose values are shown as columns.
+    pub columns: Vec<Id>,
+}
+
+impl SavedView {
+    //
+    /// Get the pagination options for the first page, as per this view's sorting.
+    pub fn pagination(&self) -> Pagination {
+        Pagination {
+            sort_by: self.sort_by.clone(),
+            sort_desc: Some(self.sort_desc),
+            ..Pagination::default()
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::model::{Id, ItemType};
+
+/// A result of a full-text search.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SearchHit {
+    /// The id of the found item.
+    pub item_id: Id,
+
+    /// The type of the found item (entity, entity link, or one of the definitions).
+    pub item_type: ItemType,
+
+    /// The definition id of the found item, in case of entities and entity links.
+    pub def_id: Option<Id>,
+
+    /// The title of the found item, that is its listing value or name.
+    pub title: String,
+
+    /// The relevant fragment of the item's content, with the matching words
+    /// enclosed between `SearchHit::HIGHLIGHT_START` and `SearchHit::HIGHLIGHT_STOP`.
+    pub snippet: String,
+
+    /// The relevance of this hit. The higher, the more relevant.
+    pub rank: f32,
+}
+
+impl SearchHit {
+    pub const HIGHLIGHT_START: &'static str = "«";
+    pub const HIGHLIGHT_STOP: &'static str = "»";
+
+    /// Get the snippet as a sequence of fragments, each one flagged as highlighted (matching) or not.
+    pub fn snippet_fragments(&self) -> Vec<(String, bool)> {
+        let mut fragments = Vec::new();
+        for (i, part) in self.snippet.split(Self::HIGHLIGHT_START).enumerate() {
+            if i == 0 {
+                fragments.push((part.to_string(), false));
+                continue;
+            }
+            match part.split_once(Self::HIGHLIGHT_STOP) {
+                Some((highlighted, rest)) => {
+                    fragments.push((highlighted.to_string(), true));
+                    fragments.push((rest.to_string(), false));
+                }
+                None => fragments.push((part.to_string(), false)),
+            }
+        }
+        fragments.retain(|(text, _)| !text.is_empty());
+        fragments
+    }
+}
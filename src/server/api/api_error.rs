@@ -0,0 +1,72 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::server::AppError;
+
+/// The error of a REST API call.<br/>
+/// It is returned to the clients with the relevant status code and an `ApiErrorBody` as JSON.
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub code: &'static str,
+    pub message: String,
+}
+
+/// The (stable) body of any REST API error response.<br/>
+/// The `code` is meant to be used by clients, while the `message` is meant for humans.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ApiErrorBody {
+    /// One of: `bad_request`, `not_found`, `already_exists`, `dependencies_exist`, `not_unique`, `unauthorized`, `internal_error`.
+    pub code: String,
+    pub message: String,
+}
+
+pub type ApiResult<T> = Result<T, ApiError>;
+
+impl ApiError {
+    //
+    pub fn new(status: StatusCode, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn not_found(what: &str) -> Self {
+        Self::new(StatusCode::NOT_FOUND, "not_found", format!("{what} not found"))
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "bad_request", message)
+    }
+}
+
+impl From<AppError> for ApiError {
+    fn from(err: AppError) -> Self {
+        let message = err.to_string();
+        match err {
+            AppError::AlreadyExists(_) => Self::new(StatusCode::CONFLICT, "already_exists", message),
+            AppError::DependenciesExist => Self::new(StatusCode::CONFLICT, "dependencies_exist", message),
+            AppError::NameDescriptionNotUnique => Self::new(StatusCode::CONFLICT, "not_unique", message),
+            AppError::Unauthorized(_) => Self::new(StatusCode::FORBIDDEN, "unauthorized", message),
+            AppError::Err(_) => Self::bad_request(message),
+            AppError::Ignorable | AppError::InternalErr => Self::new(StatusCode::INTERNAL_SERVER_ERROR, "internal_error", "internal error"),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = ApiErrorBody {
+            code: self.code.to_string(),
+            message: self.message,
+        };
+        (self.status, Json(body)).into_response()
+    }
+}
@@ -0,0 +1,263 @@
+//! The REST API handlers of the centralized configuration.
+
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::{
+    domain::model::{ConfigKey, ConfigRevision, EffectiveConfig, NewConfigRevision, ResolvedConfig},
+    server::{ServerState, Session},
+};
+
+use super::{ApiError, ApiErrorBody, ApiResult};
+
+/// The query parameters of getting the configuration of a service version in an environment.
+#[derive(Clone, Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ConfigParams {
+    /// The service instance whose configuration to get. If not provided, the one of the service version is provided.
+    pub instance: Option<String>,
+}
+
+/// The query parameters that select a configuration layer.<br/>
+/// None is provided for the global layer, the service for a service layer, the service and environment for an environment layer,
+/// plus the version for a version layer, plus the instance for an instance layer.
+#[derive(Clone, Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ConfigLayerParams {
+    pub service: Option<String>,
+    pub version: Option<String>,
+    pub env: Option<String>,
+    pub instance: Option<String>,
+}
+
+impl From<ConfigLayerParams> for ConfigKey {
+    fn from(params: ConfigLayerParams) -> Self {
+        Self {
+            service: params.service.unwrap_or_default(),
+            version: params.version.unwrap_or_default(),
+            env: params.env.unwrap_or_default(),
+            instance: params.instance.unwrap_or_default(),
+        }
+    }
+}
+
+/// Get the effective configuration of a service version in an environment (or of one of its instances),
+/// as resolved through the global, service, environment, version and instance layers.
+#[utoipa::path(
+    get,
+    path = "/config/{service}/{version}/{env}",
+    tag = "Configuration",
+    params(
+        ("service" = String, Path, description = "The name of the service"),
+        ("version" = String, Path, description = "The version of the service"),
+        ("env" = String, Path, description = "The environment"),
+        ConfigParams,
+    ),
+    responses(
+        (status = 200, description = "The effective configuration", body = EffectiveConfig),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn get_config(
+    Extension(state): Extension<ServerState>,
+    Path((service, version, env)): Path<(String, String, String)>,
+    Query(params): Query<ConfigParams>,
+) -> ApiResult<Json<EffectiveConfig>> {
+    let key = config_key(service, version, env, params);
+    let config = state.config_mgmt.get_effective(&key).await?;
+    config.map(Json).ok_or(ApiError::not_found("configuration"))
+}
+
+/// Get the resolution of the configuration of a service version in an environment (or of one of its instances):
+/// the layer that each value came from, and the overrides of its layers that none of the service's known configurations use.
+#[utoipa::path(
+    get,
+    path = "/config/{service}/{version}/{env}/resolution",
+    tag = "Configuration",
+    params(
+        ("service" = String, Path, description = "The name of the service"),
+        ("version" = String, Path, description = "The version of the service"),
+        ("env" = String, Path, description = "The environment"),
+        ConfigParams,
+    ),
+    responses(
+        (status = 200, description = "The resolved configuration", body = ResolvedConfig),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn resolve_config(
+    Extension(state): Extension<ServerState>,
+    Path((service, version, env)): Path<(String, String, String)>,
+    Query(params): Query<ConfigParams>,
+) -> ApiResult<Json<ResolvedConfig>> {
+    let key = config_key(service, version, env, params);
+    let config = state.config_mgmt.resolve(&key).await?;
+    config.map(Json).ok_or(ApiError::not_found("configuration"))
+}
+
+#[utoipa::path(
+    get,
+    path = "/config/{service}/{version}/{env}/revisions",
+    tag = "Configuration",
+    params(
+        ("service" = String, Path, description = "The name of the service"),
+        ("version" = String, Path, description = "The version of the service"),
+        ("env" = String, Path, description = "The environment"),
+    ),
+    responses(
+        (status = 200, description = "The revisions, the latest first", body = Vec<ConfigRevision>),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn list_config_revisions(
+    Extension(state): Extension<ServerState>,
+    Path((service, version, env)): Path<(String, String, String)>,
+) -> ApiResult<Json<Vec<ConfigRevision>>> {
+    let revisions = state.config_mgmt.list_revisions(&ConfigKey::new(service, version, env)).await?;
+    Ok(Json(revisions))
+}
+
+#[utoipa::path(
+    get,
+    path = "/config/{service}/{version}/{env}/revisions/{revision}",
+    tag = "Configuration",
+    params(
+        ("service" = String, Path, description = "The name of the service"),
+        ("version" = String, Path, description = "The version of the service"),
+        ("env" = String, Path, description = "The environment"),
+        ("revision" = i32, Path, description = "The number of the revision"),
+    ),
+    responses(
+        (status = 200, description = "The revision", body = ConfigRevision),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn get_config_revision(
+    Extension(state): Extension<ServerState>,
+    Path((service, version, env, revision)): Path<(String, String, String, i32)>,
+) -> ApiResult<Json<ConfigRevision>> {
+    let revision = state
+        .config_mgmt
+        .get_revision(&ConfigKey::new(service, version, env), Some(revision))
+        .await?;
+    revision.map(Json).ok_or(ApiError::not_found("configuration revision"))
+}
+
+/// Add a new revision of the configuration of a service version in an environment.<br/>
+/// Its items replace the ones of the previous revision, and each value must be valid as per the item's attribute definition.
+/// Its author is the current user, thus a login is required.
+#[utoipa::path(
+    post,
+    path = "/config/{service}/{version}/{env}/revisions",
+    tag = "Configuration",
+    params(
+        ("service" = String, Path, description = "The name of the service"),
+        ("version" = String, Path, description = "The version of the service"),
+        ("env" = String, Path, description = "The environment"),
+    ),
+    request_body = NewConfigRevision,
+    responses(
+        (status = 201, description = "The revision was added", body = ConfigRevision),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 403, description = "Login is required", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn add_config_revision(
+    Extension(state): Extension<ServerState>,
+    session: Session,
+    Path((service, version, env)): Path<(String, String, String)>,
+    Json(new): Json<NewConfigRevision>,
+) -> ApiResult<(StatusCode, Json<ConfigRevision>)> {
+    let author = session.current_username()?;
+    let revision = state.config_mgmt.add_revision(ConfigKey::new(service, version, env), &author, new).await?;
+    Ok((StatusCode::CREATED, Json(revision)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/config-layers/revisions",
+    tag = "Configuration",
+    params(ConfigLayerParams),
+    responses(
+        (status = 200, description = "The revisions of the layer, the latest first", body = Vec<ConfigRevision>),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn list_config_layer_revisions(
+    Extension(state): Extension<ServerState>,
+    Query(params): Query<ConfigLayerParams>,
+) -> ApiResult<Json<Vec<ConfigRevision>>> {
+    let revisions = state.config_mgmt.list_revisions(&params.into()).await?;
+    Ok(Json(revisions))
+}
+
+#[utoipa::path(
+    get,
+    path = "/config-layers/revisions/{revision}",
+    tag = "Configuration",
+    params(
+        ("revision" = i32, Path, description = "The number of the revision"),
+        ConfigLayerParams,
+    ),
+    responses(
+        (status = 200, description = "The revision of the layer", body = ConfigRevision),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn get_config_layer_revision(
+    Extension(state): Extension<ServerState>,
+    Path(revision): Path<i32>,
+    Query(params): Query<ConfigLayerParams>,
+) -> ApiResult<Json<ConfigRevision>> {
+    let revision = state.config_mgmt.get_revision(&params.into(), Some(revision)).await?;
+    revision.map(Json).ok_or(ApiError::not_found("configuration revision"))
+}
+
+/// Add a new revision of the configuration of a layer (such as the global defaults, or the overrides of an environment).<br/>
+/// Its items replace the ones of the layer's previous revision. Its author is the current user, thus a login is required.
+#[utoipa::path(
+    post,
+    path = "/config-layers/revisions",
+    tag = "Configuration",
+    params(ConfigLayerParams),
+    request_body = NewConfigRevision,
+    responses(
+        (status = 201, description = "The revision was added", body = ConfigRevision),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 403, description = "Login is required", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn add_config_layer_revision(
+    Extension(state): Extension<ServerState>,
+    session: Session,
+    Query(params): Query<ConfigLayerParams>,
+    Json(new): Json<NewConfigRevision>,
+) -> ApiResult<(StatusCode, Json<ConfigRevision>)> {
+    let author = session.current_username()?;
+    let revision = state.config_mgmt.add_revision(params.into(), &author, new).await?;
+    Ok((StatusCode::CREATED, Json(revision)))
+}
+
+fn config_key(service: String, version: String, env: String, params: ConfigParams) -> ConfigKey {
+    ConfigKey {
+        instance: params.instance.unwrap_or_default(),
+        ..ConfigKey::new(service, version, env)
+    }
+}
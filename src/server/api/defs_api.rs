@@ -0,0 +1,445 @@
+//! The REST API handlers of the definitions (and tags).
+
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde_json::Value;
+
+use crate::{
+    domain::model::{AttributeDef, EntityDef, EntityLinkDef, Id, Page, Tag},
+    server::ServerState,
+};
+
+use super::{ApiError, ApiErrorBody, ApiResult, Created, ListParams};
+
+// ----
+// Tags
+// ----
+
+#[utoipa::path(
+    get,
+    path = "/tags",
+    tag = "Tags",
+    params(ListParams),
+    responses(
+        (status = 200, description = "A page of the items", body = Page<Tag>),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn list_tags(Extension(state): Extension<ServerState>, Query(params): Query<ListParams>) -> ApiResult<Json<Page<Tag>>> {
+    let page = state.tag_mgmt.list(Some(&params.pagination())).await?;
+    Ok(Json(page))
+}
+
+#[utoipa::path(
+    get,
+    path = "/tags/{id}",
+    tag = "Tags",
+    params(("id" = String, Path, description = "The id of the item")),
+    responses(
+        (status = 200, description = "The item", body = Tag),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn get_tag(Extension(state): Extension<ServerState>, Path(id): Path<Id>) -> ApiResult<Json<Tag>> {
+    let tag = state.tag_mgmt.get(id.to_string()).await?;
+    tag.map(Json).ok_or(ApiError::not_found("tag"))
+}
+
+#[utoipa::path(
+    post,
+    path = "/tags",
+    tag = "Tags",
+    request_body = Tag,
+    responses(
+        (status = 201, description = "The item was created", body = Created),
+        (status = 409, description = "Conflict", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn create_tag(Extension(state): Extension<ServerState>, Json(tag): Json<Tag>) -> ApiResult<(StatusCode, Json<Created>)> {
+    let id = state.tag_mgmt.add(tag.name, tag.description).await?;
+    Ok((StatusCode::CREATED, Json(Created { id })))
+}
+
+#[utoipa::path(
+    put,
+    path = "/tags/{id}",
+    tag = "Tags",
+    params(("id" = String, Path, description = "The id of the item")),
+    request_body = Tag,
+    responses(
+        (status = 204, description = "The item was updated"),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 409, description = "Conflict", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn update_tag(Extension(state): Extension<ServerState>, Path(id): Path<Id>, Json(mut tag): Json<Tag>) -> ApiResult<StatusCode> {
+    state.tag_mgmt.get(id.to_string()).await?.ok_or(ApiError::not_found("tag"))?;
+    tag.id = id;
+    state.tag_mgmt.update(tag).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/tags/{id}",
+    tag = "Tags",
+    params(("id" = String, Path, description = "The id of the item")),
+    responses(
+        (status = 204, description = "The item was removed"),
+        (status = 409, description = "The item is still referred", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn remove_tag(Extension(state): Extension<ServerState>, Path(id): Path<Id>) -> ApiResult<StatusCode> {
+    state.tag_mgmt.remove(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ----------------------
+// Attributes Definitions
+// ----------------------
+
+#[utoipa::path(
+    get,
+    path = "/attribute-defs",
+    tag = "Attributes Definitions",
+    params(ListParams),
+    responses(
+        (status = 200, description = "A page of the items", body = Page<AttributeDef>),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn list_attr_defs(
+    Extension(state): Extension<ServerState>,
+    Query(params): Query<ListParams>,
+) -> ApiResult<Json<Page<AttributeDef>>> {
+    let page = state.attr_def_mgmt.list(Some(&params.pagination())).await?;
+    Ok(Json(page))
+}
+
+#[utoipa::path(
+    get,
+    path = "/attribute-defs/{id}",
+    tag = "Attributes Definitions",
+    params(("id" = String, Path, description = "The id of the item")),
+    responses(
+        (status = 200, description = "The item", body = AttributeDef),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn get_attr_def(Extension(state): Extension<ServerState>, Path(id): Path<Id>) -> ApiResult<Json<AttributeDef>> {
+    let attr_def = state.attr_def_mgmt.get(&id).await?;
+    attr_def.map(Json).ok_or(ApiError::not_found("attribute definition"))
+}
+
+#[utoipa::path(
+    post,
+    path = "/attribute-defs",
+    tag = "Attributes Definitions",
+    request_body = AttributeDef,
+    responses(
+        (status = 201, description = "The item was created", body = Created),
+        (status = 409, description = "Conflict", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn create_attr_def(
+    Extension(state): Extension<ServerState>,
+    Json(attr_def): Json<AttributeDef>,
+) -> ApiResult<(StatusCode, Json<Created>)> {
+    let id = state.attr_def_mgmt.add(attr_def).await?;
+    Ok((StatusCode::CREATED, Json(Created { id })))
+}
+
+#[utoipa::path(
+    put,
+    path = "/attribute-defs/{id}",
+    tag = "Attributes Definitions",
+    params(("id" = String, Path, description = "The id of the item")),
+    request_body = AttributeDef,
+    responses(
+        (status = 204, description = "The item was updated"),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 409, description = "Conflict", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn update_attr_def(
+    Extension(state): Extension<ServerState>,
+    Path(id): Path<Id>,
+    Json(mut attr_def): Json<AttributeDef>,
+) -> ApiResult<StatusCode> {
+    state
+        .attr_def_mgmt
+        .get(&id)
+        .await?
+        .ok_or(ApiError::not_found("attribute definition"))?;
+    attr_def.id = id;
+    state.attr_def_mgmt.update(&attr_def).await?;
+    // The entities keep a copy of their listing attribute's name.
+    state
+        .ent_mgmt
+        .update_listing_attr_name_by_attr_def_id(&attr_def.id, &attr_def.name)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/attribute-defs/{id}",
+    tag = "Attributes Definitions",
+    params(("id" = String, Path, description = "The id of the item")),
+    responses(
+        (status = 204, description = "The item was removed"),
+        (status = 409, description = "The item is still referred", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn remove_attr_def(Extension(state): Extension<ServerState>, Path(id): Path<Id>) -> ApiResult<StatusCode> {
+    state.attr_def_mgmt.remove(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// --------------------
+// Entities Definitions
+// --------------------
+
+#[utoipa::path(
+    get,
+    path = "/entity-defs",
+    tag = "Entities Definitions",
+    params(ListParams),
+    responses(
+        (status = 200, description = "A page of the items", body = Page<EntityDef>),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn list_ent_defs(Extension(state): Extension<ServerState>, Query(params): Query<ListParams>) -> ApiResult<Json<Page<EntityDef>>> {
+    let page = state.ent_def_mgmt.list(Some(&params.pagination())).await?;
+    Ok(Json(page))
+}
+
+#[utoipa::path(
+    get,
+    path = "/entity-defs/{id}",
+    tag = "Entities Definitions",
+    params(("id" = String, Path, description = "The id of the item")),
+    responses(
+        (status = 200, description = "The item", body = EntityDef),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn get_ent_def(Extension(state): Extension<ServerState>, Path(id): Path<Id>) -> ApiResult<Json<EntityDef>> {
+    let ent_def = state.ent_def_mgmt.get(&id).await;
+    ent_def.map(Json).ok_or(ApiError::not_found("entity definition"))
+}
+
+/// Get the JSON Schema of the payload that creates (or updates) an instance of an entity definition.
+#[utoipa::path(
+    get,
+    path = "/entity-defs/{id}/schema",
+    tag = "Entities Definitions",
+    params(("id" = String, Path, description = "The id of the entity definition")),
+    responses(
+        (status = 200, description = "The JSON Schema of the definition's instances payload", body = Object),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn get_ent_def_schema(Extension(state): Extension<ServerState>, Path(id): Path<Id>) -> ApiResult<Json<Value>> {
+    let ent_def = state.ent_def_mgmt.get(&id).await.ok_or(ApiError::not_found("entity definition"))?;
+    Ok(Json(ent_def.to_json_schema()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/entity-defs",
+    tag = "Entities Definitions",
+    request_body = EntityDef,
+    responses(
+        (status = 201, description = "The item was created", body = Created),
+        (status = 409, description = "Conflict", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn create_ent_def(
+    Extension(state): Extension<ServerState>,
+    Json(ent_def): Json<EntityDef>,
+) -> ApiResult<(StatusCode, Json<Created>)> {
+    let id = state.ent_def_mgmt.add(ent_def).await?;
+    Ok((StatusCode::CREATED, Json(Created { id })))
+}
+
+#[utoipa::path(
+    put,
+    path = "/entity-defs/{id}",
+    tag = "Entities Definitions",
+    params(("id" = String, Path, description = "The id of the item")),
+    request_body = EntityDef,
+    responses(
+        (status = 204, description = "The item was updated"),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 409, description = "Conflict", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn update_ent_def(
+    Extension(state): Extension<ServerState>,
+    Path(id): Path<Id>,
+    Json(mut ent_def): Json<EntityDef>,
+) -> ApiResult<StatusCode> {
+    state.ent_def_mgmt.get(&id).await.ok_or(ApiError::not_found("entity definition"))?;
+    ent_def.id = id;
+    state.ent_def_mgmt.update(&ent_def).await?;
+    // The entities keep a copy of their listing attribute's name and value.
+    state
+        .ent_mgmt
+        .update_listing_addr_name(&ent_def.id, &ent_def.listing_attr_def_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/entity-defs/{id}",
+    tag = "Entities Definitions",
+    params(("id" = String, Path, description = "The id of the item")),
+    responses(
+        (status = 204, description = "The item was removed"),
+        (status = 409, description = "The item is still referred", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn remove_ent_def(Extension(state): Extension<ServerState>, Path(id): Path<Id>) -> ApiResult<StatusCode> {
+    state.ent_def_mgmt.remove(&id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ------------------------
+// Entity Links Definitions
+// ------------------------
+
+#[utoipa::path(
+    get,
+    path = "/entity-link-defs",
+    tag = "Entity Links Definitions",
+    params(ListParams),
+    responses(
+        (status = 200, description = "A page of the items", body = Page<EntityLinkDef>),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn list_ent_link_defs(
+    Extension(state): Extension<ServerState>,
+    Query(params): Query<ListParams>,
+) -> ApiResult<Json<Page<EntityLinkDef>>> {
+    let page = state.ent_link_def_mgmt.list(Some(&params.pagination())).await?;
+    Ok(Json(page))
+}
+
+#[utoipa::path(
+    get,
+    path = "/entity-link-defs/{id}",
+    tag = "Entity Links Definitions",
+    params(("id" = String, Path, description = "The id of the item")),
+    responses(
+        (status = 200, description = "The item", body = EntityLinkDef),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn get_ent_link_def(Extension(state): Extension<ServerState>, Path(id): Path<Id>) -> ApiResult<Json<EntityLinkDef>> {
+    let ent_link_def = state.ent_link_def_mgmt.get(&id).await?;
+    ent_link_def.map(Json).ok_or(ApiError::not_found("entity link definition"))
+}
+
+#[utoipa::path(
+    post,
+    path = "/entity-link-defs",
+    tag = "Entity Links Definitions",
+    request_body = EntityLinkDef,
+    responses(
+        (status = 201, description = "The item was created", body = Created),
+        (status = 409, description = "Conflict", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn create_ent_link_def(
+    Extension(state): Extension<ServerState>,
+    Json(ent_link_def): Json<EntityLinkDef>,
+) -> ApiResult<(StatusCode, Json<Created>)> {
+    let id = state.ent_link_def_mgmt.add(ent_link_def).await?;
+    Ok((StatusCode::CREATED, Json(Created { id })))
+}
+
+#[utoipa::path(
+    put,
+    path = "/entity-link-defs/{id}",
+    tag = "Entity Links Definitions",
+    params(("id" = String, Path, description = "The id of the item")),
+    request_body = EntityLinkDef,
+    responses(
+        (status = 204, description = "The item was updated"),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 409, description = "Conflict", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn update_ent_link_def(
+    Extension(state): Extension<ServerState>,
+    Path(id): Path<Id>,
+    Json(mut ent_link_def): Json<EntityLinkDef>,
+) -> ApiResult<StatusCode> {
+    state
+        .ent_link_def_mgmt
+        .get(&id)
+        .await?
+        .ok_or(ApiError::not_found("entity link definition"))?;
+    ent_link_def.id = id;
+    state.ent_link_def_mgmt.update(&ent_link_def).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/entity-link-defs/{id}",
+    tag = "Entity Links Definitions",
+    params(("id" = String, Path, description = "The id of the item")),
+    responses(
+        (status = 204, description = "The item was removed"),
+        (status = 409, description = "The item is still referred", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn remove_ent_link_def(Extension(state): Extension<ServerState>, Path(id): Path<Id>) -> ApiResult<StatusCode> {
+    state.ent_link_def_mgmt.remove(&id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,205 @@
+//! The REST API handlers of exporting the entities (of a definition), the instance graph, and the whole knowledge base as RDF.
+
+use axum::{
+    body::Body,
+    extract::{Path, Query},
+    http::header,
+    response::{IntoResponse, Response},
+    Extension,
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::{
+    domain::model::{EntityDef, EntityGraphFilter, ExportFormat, GraphExportFormat, Id, RdfFormat},
+    server::{ExportChunks, ServerState},
+};
+
+use super::{ApiError, ApiErrorBody, ApiResult};
+
+/// The query parameters of the export endpoints.
+#[derive(Clone, Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportParams {
+    /// The format of the content: `csv` (the default) or `jsonl`.
+    #[param(value_type = Option<String>)]
+    pub format: Option<ExportFormat>,
+    /// Whether to include the targets of the entities' outgoing links, as extra columns (or a `links` object).
+    pub links: Option<bool>,
+}
+
+/// Export (download) all the entities of a definition.<br/>
+/// The content is streamed, as it is read from the database.
+#[utoipa::path(
+    get,
+    path = "/entity-defs/{id}/export",
+    tag = "Entities",
+    params(("id" = String, Path, description = "The id of the entity definition"), ExportParams),
+    responses(
+        (status = 200, description = "The entities, as CSV (or JSON Lines)", body = String, content_type = "text/csv"),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn export_ents(
+    Extension(state): Extension<ServerState>,
+    Path(id): Path<Id>,
+    Query(params): Query<ExportParams>,
+) -> ApiResult<Response> {
+    let ent_def = state.ent_def_mgmt.get(&id).await.ok_or(ApiError::not_found("entity definition"))?;
+    let format = params.format.unwrap_or_default();
+    let file_name = file_name(&ent_def, "", format);
+    let chunks = state
+        .ent_export_mgmt
+        .export_entities(ent_def, format, params.links.unwrap_or_default())
+        .await?;
+    Ok(attachment(chunks, format.content_type(), file_name))
+}
+
+/// Export (download) the outgoing links of a definition's entities, as an edge list.
+#[utoipa::path(
+    get,
+    path = "/entity-defs/{id}/export/links",
+    tag = "Entities",
+    params(("id" = String, Path, description = "The id of the entity definition"), ExportParams),
+    responses(
+        (status = 200, description = "The entity links, as CSV (or JSON Lines)", body = String, content_type = "text/csv"),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn export_ent_links(
+    Extension(state): Extension<ServerState>,
+    Path(id): Path<Id>,
+    Query(params): Query<ExportParams>,
+) -> ApiResult<Response> {
+    let ent_def = state.ent_def_mgmt.get(&id).await.ok_or(ApiError::not_found("entity definition"))?;
+    let format = params.format.unwrap_or_default();
+    let chunks = state.ent_export_mgmt.export_links(&ent_def.id, format);
+    Ok(attachment(chunks, format.content_type(), file_name(&ent_def, "_links", format)))
+}
+
+/// The query parameters of the graph export endpoint.<br/>
+/// The lists of ids are comma separated, and an empty (or missing) list means no filtering on that criteria.
+#[derive(Clone, Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GraphExportParams {
+    /// The format of the content: `graphml` (the default), `dot` or `json`.
+    #[param(value_type = Option<String>)]
+    pub format: Option<GraphExportFormat>,
+    /// Include only the entities with these definitions.
+    pub ent_def_ids: Option<String>,
+    /// Include only the links with these definitions.
+    pub ent_link_def_ids: Option<String>,
+    /// Include only the entities whose definitions (or their attribute definitions) are tagged with these tags.
+    /// It is not applicable when starting from an entity.
+    pub tag_ids: Option<String>,
+    /// The entity to start from. If provided, the graph contains the entities reachable from it.
+    #[param(value_type = Option<String>)]
+    pub start_id: Option<Id>,
+    /// The maximum number of links between the starting entity and the included ones. It defaults to 1.
+    pub depth: Option<i32>,
+}
+
+impl GraphExportParams {
+    //
+    pub fn filter(&self) -> EntityGraphFilter {
+        EntityGraphFilter {
+            ent_def_ids: split_ids(&self.ent_def_ids),
+            ent_link_def_ids: split_ids(&self.ent_link_def_ids),
+            tag_ids: split_ids(&self.tag_ids),
+        }
+    }
+}
+
+/// Export (download) the instance graph, that is the entities as nodes and their links as edges,
+/// both including their attributes.
+#[utoipa::path(
+    get,
+    path = "/graph/export",
+    tag = "Entities",
+    params(GraphExportParams),
+    responses(
+        (status = 200, description = "The graph, as GraphML (or DOT, or JSON)", body = String, content_type = "application/graphml+xml"),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn export_graph(Extension(state): Extension<ServerState>, Query(params): Query<GraphExportParams>) -> ApiResult<Response> {
+    let format = params.format.unwrap_or_default();
+    let graph = state
+        .graph_export_mgmt
+        .export(&params.filter(), params.start_id.as_ref(), params.depth.unwrap_or(1))
+        .await?;
+    let headers = [
+        (header::CONTENT_TYPE, format.content_type().to_string()),
+        (
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"graph.{}\"", format.file_extension()),
+        ),
+    ];
+    Ok((headers, graph.render(format)).into_response())
+}
+
+/// The query parameters of the RDF export endpoint.
+#[derive(Clone, Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RdfExportParams {
+    /// The format of the content: `turtle` (the default) or `jsonld`.
+    #[param(value_type = Option<String>)]
+    pub format: Option<RdfFormat>,
+    /// The base of the IRIs. It defaults to the configured one (see `RDF_BASE_IRI` environment variable).
+    pub base: Option<String>,
+}
+
+/// Export (download) the knowledge base as RDF. The entity, attribute and link definitions are included
+/// as classes, datatype properties and object properties, and the entities as their individuals.
+#[utoipa::path(
+    get,
+    path = "/rdf/export",
+    tag = "Entities",
+    params(RdfExportParams),
+    responses(
+        (status = 200, description = "The knowledge base, as Turtle (or JSON-LD)", body = String, content_type = "text/turtle"),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn export_rdf(Extension(state): Extension<ServerState>, Query(params): Query<RdfExportParams>) -> ApiResult<Response> {
+    let format = params.format.unwrap_or_default();
+    let chunks = state.rdf_export_mgmt.export(format, params.base.as_deref()).await?;
+    Ok(attachment(
+        chunks,
+        format.content_type(),
+        format!("knowledge_base.{}", format.file_extension()),
+    ))
+}
+
+fn split_ids(ids: &Option<String>) -> Vec<Id> {
+    ids.as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(|id| id.trim())
+        .filter(|id| !id.is_empty())
+        .map(|id| Id::new_from(id.to_string()))
+        .collect()
+}
+
+fn file_name(ent_def: &EntityDef, suffix: &str, format: ExportFormat) -> String {
+    let name: String = ent_def
+        .name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect();
+    format!("{}{}.{}", name, suffix, format.file_extension())
+}
+
+fn attachment(chunks: ExportChunks, content_type: &str, file_name: String) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", file_name)),
+        ],
+        Body::from_stream(chunks),
+    )
+        .into_response()
+}
@@ -0,0 +1,237 @@
+//! The REST API handlers of the instances, that is the entities and entity links.
+
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    Extension, Json,
+};
+
+use crate::{
+    domain::model::{Entity, EntityLink, Id, Page},
+    server::ServerState,
+};
+
+use super::{ApiError, ApiErrorBody, ApiResult, Created, ListParams};
+
+// --------
+// Entities
+// --------
+
+#[utoipa::path(
+    get,
+    path = "/entities",
+    tag = "Entities",
+    params(ListParams),
+    responses(
+        (status = 200, description = "A page of the items", body = Page<Entity>),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn list_ents(Extension(state): Extension<ServerState>, Query(params): Query<ListParams>) -> ApiResult<Json<Page<Entity>>> {
+    let pagination = params.pagination();
+    let page = match &params.def_id {
+        Some(def_id) => state.ent_mgmt.list_by_def_id(def_id, Some(&pagination)).await?,
+        None => state.ent_mgmt.list(Some(&pagination)).await?,
+    };
+    Ok(Json(page))
+}
+
+#[utoipa::path(
+    get,
+    path = "/entities/{id}",
+    tag = "Entities",
+    params(("id" = String, Path, description = "The id of the item")),
+    responses(
+        (status = 200, description = "The item", body = Entity),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn get_ent(Extension(state): Extension<ServerState>, Path(id): Path<Id>) -> ApiResult<Json<Entity>> {
+    let ent = state.ent_mgmt.get(&id).await?;
+    ent.map(Json).ok_or(ApiError::not_found("entity"))
+}
+
+/// Create an entity.<br/>
+/// If its listing attribute is not provided, the one of its definition is used.
+#[utoipa::path(
+    post,
+    path = "/entities",
+    tag = "Entities",
+    request_body = Entity,
+    responses(
+        (status = 201, description = "The item was created", body = Created),
+        (status = 409, description = "Conflict", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn create_ent(Extension(state): Extension<ServerState>, Json(mut ent): Json<Entity>) -> ApiResult<(StatusCode, Json<Created>)> {
+    let ent_def = state
+        .ent_def_mgmt
+        .get(&ent.def_id)
+        .await
+        .ok_or(ApiError::bad_request("unknown entity definition"))?;
+    if ent.listing_attr_def_id.is_empty() {
+        ent.listing_attr_def_id = ent_def.listing_attr_def_id.clone();
+    }
+    if let Some(attr_def) = ent_def.attributes.iter().find(|a| a.id == ent.listing_attr_def_id) {
+        ent.listing_attr_name = attr_def.name.clone();
+    }
+    let id = state.ent_mgmt.add(ent).await?;
+    Ok((StatusCode::CREATED, Json(Created { id })))
+}
+
+#[utoipa::path(
+    put,
+    path = "/entities/{id}",
+    tag = "Entities",
+    params(("id" = String, Path, description = "The id of the item")),
+    request_body = Entity,
+    responses(
+        (status = 204, description = "The item was updated"),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 409, description = "Conflict", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn update_ent(
+    Extension(state): Extension<ServerState>,
+    Path(id): Path<Id>,
+    Json(mut ent): Json<Entity>,
+) -> ApiResult<StatusCode> {
+    let curr = state.ent_mgmt.get(&id).await?.ok_or(ApiError::not_found("entity"))?;
+    ent.id = id;
+    if ent.listing_attr_def_id.is_empty() {
+        ent.listing_attr_def_id = curr.listing_attr_def_id;
+        ent.listing_attr_name = curr.listing_attr_name;
+    }
+    state.ent_mgmt.update(&ent).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/entities/{id}",
+    tag = "Entities",
+    params(("id" = String, Path, description = "The id of the item")),
+    responses(
+        (status = 204, description = "The item was removed"),
+        (status = 409, description = "The item is still referred", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn remove_ent(Extension(state): Extension<ServerState>, Path(id): Path<Id>) -> ApiResult<StatusCode> {
+    state.ent_mgmt.remove(&id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ------------
+// Entity Links
+// ------------
+
+#[utoipa::path(
+    get,
+    path = "/entity-links",
+    tag = "Entity Links",
+    params(ListParams),
+    responses(
+        (status = 200, description = "A page of the items", body = Page<EntityLink>),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn list_ent_links(
+    Extension(state): Extension<ServerState>,
+    Query(params): Query<ListParams>,
+) -> ApiResult<Json<Page<EntityLink>>> {
+    let pagination = params.pagination();
+    let page = match &params.def_id {
+        Some(def_id) => state.ent_link_mgmt.list_by_def_id(def_id, Some(&pagination)).await?,
+        None => state.ent_link_mgmt.list(Some(&pagination)).await?,
+    };
+    Ok(Json(page))
+}
+
+#[utoipa::path(
+    get,
+    path = "/entity-links/{id}",
+    tag = "Entity Links",
+    params(("id" = String, Path, description = "The id of the item")),
+    responses(
+        (status = 200, description = "The item", body = EntityLink),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn get_ent_link(Extension(state): Extension<ServerState>, Path(id): Path<Id>) -> ApiResult<Json<EntityLink>> {
+    let ent_link = state.ent_link_mgmt.get(&id).await?;
+    ent_link.map(Json).ok_or(ApiError::not_found("entity link"))
+}
+
+#[utoipa::path(
+    post,
+    path = "/entity-links",
+    tag = "Entity Links",
+    request_body = EntityLink,
+    responses(
+        (status = 201, description = "The item was created", body = Created),
+        (status = 409, description = "Conflict", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn create_ent_link(
+    Extension(state): Extension<ServerState>,
+    Json(ent_link): Json<EntityLink>,
+) -> ApiResult<(StatusCode, Json<Created>)> {
+    let id = state.ent_link_mgmt.add(ent_link).await?;
+    Ok((StatusCode::CREATED, Json(Created { id })))
+}
+
+#[utoipa::path(
+    put,
+    path = "/entity-links/{id}",
+    tag = "Entity Links",
+    params(("id" = String, Path, description = "The id of the item")),
+    request_body = EntityLink,
+    responses(
+        (status = 204, description = "The item was updated"),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 409, description = "Conflict", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn update_ent_link(
+    Extension(state): Extension<ServerState>,
+    Path(id): Path<Id>,
+    Json(mut ent_link): Json<EntityLink>,
+) -> ApiResult<StatusCode> {
+    state.ent_link_mgmt.get(&id).await?.ok_or(ApiError::not_found("entity link"))?;
+    ent_link.id = id;
+    state.ent_link_mgmt.update(&ent_link).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/entity-links/{id}",
+    tag = "Entity Links",
+    params(("id" = String, Path, description = "The id of the item")),
+    responses(
+        (status = 204, description = "The item was removed"),
+        (status = 409, description = "The item is still referred", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn remove_ent_link(Extension(state): Extension<ServerState>, Path(id): Path<Id>) -> ApiResult<StatusCode> {
+    state.ent_link_mgmt.remove(&id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
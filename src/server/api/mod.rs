@@ -0,0 +1,146 @@
+//! The public (resource oriented) REST API, that is meant for other services.<br/>
+//! It is versioned, and the current version is served under `/api/v1`.
+
+mod api_error;
+pub use api_error::*;
+
+mod config_api;
+pub use config_api::*;
+
+mod defs_api;
+pub use defs_api::*;
+
+mod export_api;
+pub use export_api::*;
+
+mod instances_api;
+pub use instances_api::*;
+
+mod openapi;
+pub use openapi::*;
+
+mod registry_api;
+pub use registry_api::*;
+
+use axum::{
+    handler::Handler,
+    http::Method,
+    routing::{on, MethodFilter, MethodRouter},
+    Router,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::domain::model::{Id, Pagination};
+
+/// A route of the REST API, as its method, path and handler.
+pub type ApiRoute = (Method, &'static str, MethodRouter);
+
+/// The router of the REST API's version 1.<br/>
+/// It expects the `ServerState` to be provided as an `Extension` layer.
+pub fn api_v1_router() -> Router {
+    api_v1_routes()
+        .into_iter()
+        .fold(Router::new(), |router, (_, path, handler)| router.route(path, handler))
+}
+
+/// The routes of the REST API's version 1.<br/>
+/// These are kept as data, so that they can also be checked against the OpenAPI document.
+pub fn api_v1_routes() -> Vec<ApiRoute> {
+    vec![
+        api_route(Method::GET, "/tags", list_tags),
+        api_route(Method::POST, "/tags", create_tag),
+        api_route(Method::GET, "/tags/:id", get_tag),
+        api_route(Method::PUT, "/tags/:id", update_tag),
+        api_route(Method::DELETE, "/tags/:id", remove_tag),
+        api_route(Method::GET, "/attribute-defs", list_attr_defs),
+        api_route(Method::POST, "/attribute-defs", create_attr_def),
+        api_route(Method::GET, "/attribute-defs/:id", get_attr_def),
+        api_route(Method::PUT, "/attribute-defs/:id", update_attr_def),
+        api_route(Method::DELETE, "/attribute-defs/:id", remove_attr_def),
+        api_route(Method::GET, "/entity-defs", list_ent_defs),
+        api_route(Method::POST, "/entity-defs", create_ent_def),
+        api_route(Method::GET, "/entity-defs/:id", get_ent_def),
+        api_route(Method::PUT, "/entity-defs/:id", update_ent_def),
+        api_route(Method::DELETE, "/entity-defs/:id", remove_ent_def),
+        api_route(Method::GET, "/entity-defs/:id/schema", get_ent_def_schema),
+        api_route(Method::GET, "/entity-defs/:id/export", export_ents),
+        api_route(Method::GET, "/entity-defs/:id/export/links", export_ent_links),
+        api_route(Method::GET, "/entity-link-defs", list_ent_link_defs),
+        api_route(Method::POST, "/entity-link-defs", create_ent_link_def),
+        api_route(Method::GET, "/entity-link-defs/:id", get_ent_link_def),
+        api_route(Method::PUT, "/entity-link-defs/:id", update_ent_link_def),
+        api_route(Method::DELETE, "/entity-link-defs/:id", remove_ent_link_def),
+        api_route(Method::GET, "/entities", list_ents),
+        api_route(Method::POST, "/entities", create_ent),
+        api_route(Method::GET, "/entities/:id", get_ent),
+        api_route(Method::PUT, "/entities/:id", update_ent),
+        api_route(Method::DELETE, "/entities/:id", remove_ent),
+        api_route(Method::GET, "/graph/export", export_graph),
+        api_route(Method::GET, "/rdf/export", export_rdf),
+        api_route(Method::GET, "/entity-links", list_ent_links),
+        api_route(Method::POST, "/entity-links", create_ent_link),
+        api_route(Method::GET, "/entity-links/:id", get_ent_link),
+        api_route(Method::PUT, "/entity-links/:id", update_ent_link),
+        api_route(Method::DELETE, "/entity-links/:id", remove_ent_link),
+        api_route(Method::GET, "/registry/instances", list_instances),
+        api_route(Method::POST, "/registry/instances", register_instance),
+        api_route(Method::GET, "/registry/instances/:id", get_instance),
+        api_route(Method::DELETE, "/registry/instances/:id", deregister_instance),
+        api_route(Method::PUT, "/registry/instances/:id/heartbeat", heartbeat_instance),
+        api_route(Method::GET, "/discovery/:service", discover_instances),
+        api_route(Method::GET, "/config/:service/:version/:env", get_config),
+        api_route(Method::GET, "/config/:service/:version/:env/resolution", resolve_config),
+        api_route(Method::GET, "/config/:service/:version/:env/revisions", list_config_revisions),
+        api_route(Method::POST, "/config/:service/:version/:env/revisions", add_config_revision),
+        api_route(Method::GET, "/config/:service/:version/:env/revisions/:revision", get_config_revision),
+        api_route(Method::GET, "/config-layers/revisions", list_config_layer_revisions),
+        api_route(Method::POST, "/config-layers/revisions", add_config_layer_revision),
+        api_route(Method::GET, "/config-layers/revisions/:revision", get_config_layer_revision),
+    ]
+}
+
+fn api_route<H, T>(method: Method, path: &'static str, handler: H) -> ApiRoute
+where
+    H: Handler<T, ()>,
+    T: 'static,
+{
+    let filter = MethodFilter::try_from(method.clone()).expect("an unsupported method");
+    (method, path, on(filter, handler))
+}
+
+/// The query parameters of the list endpoints.<br/>
+/// The `def_id` is applicable to the entities and entity links only.
+#[derive(Clone, Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListParams {
+    /// The page number, starting from 1.
+    pub page: Option<i32>,
+    /// The maximum number of items in a page.
+    pub limit: Option<i32>,
+    /// The field to sort the items by.
+    pub sort_by: Option<String>,
+    /// Whether to sort in descending order.
+    pub sort_desc: Option<bool>,
+    /// The definition id of the items to list.
+    #[param(value_type = Option<String>)]
+    pub def_id: Option<Id>,
+}
+
+impl ListParams {
+    //
+    pub fn pagination(&self) -> Pagination {
+        Pagination {
+            page: self.page,
+            limit: self.limit,
+            sort_by: self.sort_by.clone(),
+            sort_desc: self.sort_desc,
+        }
+    }
+}
+
+/// The response body of the create endpoints.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Created {
+    pub id: Id,
+}
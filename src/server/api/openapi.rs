@@ -0,0 +1,149 @@
+use axum::Router;
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::domain::model::{
+    AttributeDef, AttributeValueType, BooleanAttribute, Cardinality, ConfigItem, ConfigKey, ConfigLayer, ConfigRevision, DiscoveryOrder,
+    EffectiveConfig, Entity, EntityDef, EntityLink, EntityLinkDef, HealthStatus, Id, IntegerAttribute, NewConfigRevision, Page,
+    ResolvedConfig, ResolvedConfigValue, ServiceDiscovery, ServiceInstance, ServiceRegistration, SmallintAttribute, Tag, TextAttribute,
+    UnusedConfigOverride,
+};
+
+use super::*;
+
+/// The OpenAPI document of the REST API.<br/>
+/// It is generated from the handlers' `utoipa::path` annotations and the types used by them,
+/// thus any new route must be added to `paths` as well (which is checked by the tests below).
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Cognitive REST API", description = "The resource oriented API of the metamodel and its instances, of the service registry, and of the configuration."),
+    servers((url = "/api/v1")),
+    paths(
+        list_tags, get_tag, create_tag, update_tag, remove_tag,
+        list_attr_defs, get_attr_def, create_attr_def, update_attr_def, remove_attr_def,
+        list_ent_defs, get_ent_def, get_ent_def_schema, create_ent_def, update_ent_def, remove_ent_def,
+        list_ent_link_defs, get_ent_link_def, create_ent_link_def, update_ent_link_def, remove_ent_link_def,
+        list_ents, get_ent, create_ent, update_ent, remove_ent, export_ents, export_ent_links, export_graph, export_rdf,
+        list_ent_links, get_ent_link, create_ent_link, update_ent_link, remove_ent_link,
+        register_instance, list_instances, get_instance, heartbeat_instance, deregister_instance, discover_instances,
+        get_config, resolve_config, list_config_revisions, get_config_revision, add_config_revision,
+        list_config_layer_revisions, get_config_layer_revision, add_config_layer_revision,
+    ),
+    components(schemas(
+        Id, Tag, AttributeDef, AttributeValueType, EntityDef, EntityLinkDef, Cardinality, Entity, EntityLink,
+        TextAttribute, SmallintAttribute, IntegerAttribute, BooleanAttribute,
+        Page<Tag>, Page<AttributeDef>, Page<EntityDef>, Page<EntityLinkDef>, Page<Entity>, Page<EntityLink>,
+        ServiceInstance, ServiceRegistration, ServiceDiscovery, DiscoveryOrder, HealthStatus,
+        ConfigLayer, ConfigKey, ConfigItem, ConfigRevision, NewConfigRevision, EffectiveConfig,
+        ResolvedConfig, ResolvedConfigValue, UnusedConfigOverride,
+        Created, ApiErrorBody,
+    ))
+)]
+pub struct ApiDoc;
+
+/// The router that serves the OpenAPI document at `/api/openapi.json`,
+/// and its (self-hosted) docs page at `/api/docs`.
+pub fn api_docs_router() -> Router {
+    SwaggerUi::new("/api/docs").url("/api/openapi.json", ApiDoc::openapi()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    //
+    use std::collections::BTreeSet;
+
+    use serde_json::Value;
+    use utoipa::OpenApi;
+
+    use super::{api_v1_routes, ApiDoc};
+
+    const METHODS: [&str; 5] = ["get", "post", "put", "delete", "patch"];
+
+    /// Get the routes of `api_v1_router` as (method, path) pairs, with the path parameters as `{name}`.
+    fn router_routes() -> BTreeSet<(String, String)> {
+        //
+        api_v1_routes()
+            .into_iter()
+            .map(|(method, path, _)| {
+                let path = path
+                    .split('/')
+                    .map(|segment| match segment.strip_prefix(':') {
+                        Some(param) => format!("{{{}}}", param),
+                        None => segment.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("/");
+                (method.as_str().to_lowercase(), path)
+            })
+            .collect()
+    }
+
+    fn spec() -> Value {
+        serde_json::to_value(ApiDoc::openapi()).expect("the OpenAPI document cannot be serialized")
+    }
+
+    #[test]
+    fn spec_paths_match_the_router_routes() {
+        //
+        let spec = spec();
+        let mut spec_routes = BTreeSet::new();
+        for (path, item) in spec["paths"].as_object().expect("the OpenAPI document has no paths") {
+            for method in METHODS.into_iter().filter(|method| item.get(*method).is_some()) {
+                spec_routes.insert((method.to_string(), path.clone()));
+            }
+        }
+        let routes = router_routes();
+        assert!(!routes.is_empty(), "No routes were found in api_v1_router.");
+
+        let undocumented: Vec<_> = routes.difference(&spec_routes).collect();
+        assert!(
+            undocumented.is_empty(),
+            "These routes are missing from ApiDoc's paths: {:?}",
+            undocumented
+        );
+        let unrouted: Vec<_> = spec_routes.difference(&routes).collect();
+        assert!(
+            unrouted.is_empty(),
+            "These ApiDoc paths are not routed by api_v1_router: {:?}",
+            unrouted
+        );
+    }
+
+    #[test]
+    fn spec_schema_refs_are_listed_in_components() {
+        //
+        fn collect_refs(value: &Value, refs: &mut BTreeSet<String>) {
+            match value {
+                Value::Object(fields) => {
+                    for (name, field) in fields {
+                        match (name.as_str(), field) {
+                            ("$ref", Value::String(r)) => {
+                                refs.insert(r.clone());
+                            }
+                            _ => collect_refs(field, refs),
+                        }
+                    }
+                }
+                Value::Array(items) => items.iter().for_each(|item| collect_refs(item, refs)),
+                _ => {}
+            }
+        }
+
+        let spec = spec();
+        let mut refs = BTreeSet::new();
+        collect_refs(&spec, &mut refs);
+        let schemas = spec["components"]["schemas"].as_object().cloned().unwrap_or_default();
+        let missing: Vec<_> = refs
+            .iter()
+            .filter(|r| match r.strip_prefix("#/components/schemas/") {
+                Some(name) => !schemas.contains_key(name),
+                None => true,
+            })
+            .collect();
+        assert!(
+            missing.is_empty(),
+            "These schemas are referred but missing from ApiDoc's components: {:?}",
+            missing
+        );
+    }
+}
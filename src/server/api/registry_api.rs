@@ -0,0 +1,171 @@
+//! The REST API handlers of the service registry, that are used by the service instances to register themselves,
+//! and by their clients to discover them.
+
+use std::time::Duration;
+
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::{
+    domain::model::{DiscoveryOrder, DiscoveryQuery, Id, ServiceDiscovery, ServiceInstance, ServiceRegistration},
+    server::{ServerState, ServiceRegistryMgmt},
+};
+
+use super::{ApiError, ApiErrorBody, ApiResult};
+
+/// The query parameters of listing the registered instances.
+#[derive(Clone, Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RegistryListParams {
+    /// The name of the service whose instances to list.
+    pub service: Option<String>,
+}
+
+/// The query parameters of discovering the instances of a service.
+#[derive(Clone, Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DiscoveryParams {
+    /// The version of the instances.
+    pub version: Option<String>,
+    /// A tag that the instances must have.
+    pub tag: Option<String>,
+    /// The order of the instances: `sorted` (the default), `random` or `round_robin`.
+    #[param(value_type = Option<String>)]
+    pub order: Option<DiscoveryOrder>,
+    /// The index of a previous discovery. If provided, the response is sent as soon as the instances change,
+    /// or when the waiting times out.
+    pub index: Option<String>,
+    /// The maximum seconds to wait for the instances to change. It defaults to 30 and it is at most 300.
+    pub wait: Option<u64>,
+}
+
+/// Register a service instance.<br/>
+/// If the instance (that is, its service and address) is already registered, its registration is updated and renewed.
+/// The instance must then send heartbeats, before its TTL passes.
+#[utoipa::path(
+    post,
+    path = "/registry/instances",
+    tag = "Service Registry",
+    request_body = ServiceRegistration,
+    responses(
+        (status = 201, description = "The instance was registered", body = ServiceInstance),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn register_instance(
+    Extension(state): Extension<ServerState>,
+    Json(reg): Json<ServiceRegistration>,
+) -> ApiResult<(StatusCode, Json<ServiceInstance>)> {
+    let instance = state.registry_mgmt.register(reg).await?;
+    Ok((StatusCode::CREATED, Json(instance)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/registry/instances",
+    tag = "Service Registry",
+    params(RegistryListParams),
+    responses(
+        (status = 200, description = "The registered instances", body = Vec<ServiceInstance>),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn list_instances(
+    Extension(state): Extension<ServerState>,
+    Query(params): Query<RegistryListParams>,
+) -> ApiResult<Json<Vec<ServiceInstance>>> {
+    let instances = state.registry_mgmt.list(params.service.as_deref()).await?;
+    Ok(Json(instances))
+}
+
+#[utoipa::path(
+    get,
+    path = "/registry/instances/{id}",
+    tag = "Service Registry",
+    params(("id" = String, Path, description = "The id of the instance")),
+    responses(
+        (status = 200, description = "The instance", body = ServiceInstance),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn get_instance(Extension(state): Extension<ServerState>, Path(id): Path<Id>) -> ApiResult<Json<ServiceInstance>> {
+    let instance = state.registry_mgmt.get(&id).await?;
+    instance.map(Json).ok_or(ApiError::not_found("service instance"))
+}
+
+/// Renew the registration of an instance.<br/>
+/// If the instance is not found, it expired (or was deregistered) and it needs to register again.
+#[utoipa::path(
+    put,
+    path = "/registry/instances/{id}/heartbeat",
+    tag = "Service Registry",
+    params(("id" = String, Path, description = "The id of the instance")),
+    responses(
+        (status = 200, description = "The renewed instance", body = ServiceInstance),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn heartbeat_instance(Extension(state): Extension<ServerState>, Path(id): Path<Id>) -> ApiResult<Json<ServiceInstance>> {
+    let instance = state.registry_mgmt.heartbeat(&id).await?;
+    instance.map(Json).ok_or(ApiError::not_found("service instance"))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/registry/instances/{id}",
+    tag = "Service Registry",
+    params(("id" = String, Path, description = "The id of the instance")),
+    responses(
+        (status = 204, description = "The instance was deregistered"),
+        (status = 404, description = "Not found", body = ApiErrorBody),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn deregister_instance(Extension(state): Extension<ServerState>, Path(id): Path<Id>) -> ApiResult<StatusCode> {
+    match state.registry_mgmt.deregister(&id).await? {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Err(ApiError::not_found("service instance")),
+    }
+}
+
+/// Discover the healthy instances of a service.<br/>
+/// To watch for changes, provide the `index` of the previous response: the request is answered as soon as
+/// the instances are different from the ones of that response, or (with the same instances) after the `wait` seconds.
+#[utoipa::path(
+    get,
+    path = "/discovery/{service}",
+    tag = "Service Registry",
+    params(("service" = String, Path, description = "The name of the service"), DiscoveryParams),
+    responses(
+        (status = 200, description = "The healthy instances", body = ServiceDiscovery),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+    )
+)]
+pub async fn discover_instances(
+    Extension(state): Extension<ServerState>,
+    Path(service): Path<String>,
+    Query(params): Query<DiscoveryParams>,
+) -> ApiResult<Json<ServiceDiscovery>> {
+    let query = DiscoveryQuery {
+        service,
+        version: params.version,
+        tag: params.tag,
+        order: params.order.unwrap_or_default(),
+    };
+    let wait = Duration::from_secs(params.wait.unwrap_or(ServiceRegistryMgmt::DEFAULT_WAIT_SECS));
+    let discovery = state.registry_mgmt.discover(&query, params.index.as_deref(), wait).await?;
+    Ok(Json(discovery))
+}
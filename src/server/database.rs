@@ -1,5 +1,5 @@
 use crate::server::AppError;
-use sqlx::{postgres::PgPoolOptions, PgPool};
+use sqlx::{migrate::Migrator, postgres::PgPoolOptions, PgPool, Row};
 
 #[cfg(feature = "server")]
 pub async fn connect_to_pgdb() -> Result<PgPool, AppError> {
@@ -15,3 +15,123 @@ pub async fn connect_to_pgdb() -> Result<PgPool, AppError> {
         .map_err(|_| AppError::Err("Failed to connect to database".into()))?;
     Ok(pool)
 }
+
+/// The database migrations, embedded from `ops/db_migrations`.
+#[cfg(feature = "server")]
+static MIGRATOR: Migrator = sqlx::migrate!("./ops/db_migrations");
+
+/// The state of the database migrations, as the ones embedded into this binary compared to the ones applied to the database.
+#[derive(Debug, Default)]
+pub struct DbMigrationsStatus {
+    /// The versions of the (successfully) applied migrations.
+    pub applied: Vec<i64>,
+    /// The (version, description) of the migrations that are not applied yet.
+    pub pending: Vec<(i64, String)>,
+    /// The versions of the applied migrations that are unknown to this binary, that is the database is ahead of it.
+    pub unknown: Vec<i64>,
+    /// The versions of the applied migrations whose content was changed since they were applied.
+    pub modified: Vec<i64>,
+    /// The versions of the migrations that failed (partially applied).
+    pub failed: Vec<i64>,
+}
+
+impl DbMigrationsStatus {
+    //
+    pub fn is_up_to_date(&self) -> bool {
+        self.pending.is_empty() && self.unknown.is_empty() && self.modified.is_empty() && self.failed.is_empty()
+    }
+
+    /// Describe the problems that prevent the migrations from being applied, if any.
+    pub fn problems(&self) -> Option<String> {
+        //
+        let versions = |versions: &[i64]| versions.iter().map(|v| v.to_string()).collect::<Vec<String>>().join(", ");
+        let mut problems = vec![];
+        if !self.unknown.is_empty() {
+            problems.push(format!(
+                "The database is ahead of this binary: the applied migrations {} are unknown to it. Use a newer version of the binary.",
+                versions(&self.unknown)
+            ));
+        }
+        if !self.modified.is_empty() {
+            problems.push(format!(
+                "The applied migrations {} were changed afterwards.",
+                versions(&self.modified)
+            ));
+        }
+        if !self.failed.is_empty() {
+            problems.push(format!(
+                "The migrations {} previously failed, and must be fixed by hand.",
+                versions(&self.failed)
+            ));
+        }
+        match problems.is_empty() {
+            true => None,
+            false => Some(problems.join(" ")),
+        }
+    }
+}
+
+/// Get the state of the database migrations, without changing anything.
+#[cfg(feature = "server")]
+pub async fn db_migrations_status(pool: &PgPool) -> Result<DbMigrationsStatus, AppError> {
+    //
+    let db_err = |err: sqlx::Error| {
+        log::error!("Failed to read the applied database migrations. Reason: '{}'.", err);
+        AppError::Err(format!("Failed to read the applied database migrations: {}", err))
+    };
+    // The migrations table is created on the first run, thus its absence means that none was applied.
+    let has_table: bool = sqlx::query("SELECT to_regclass('_sqlx_migrations') IS NOT NULL AS has_table")
+        .fetch_one(pool)
+        .await
+        .map_err(db_err)?
+        .get("has_table");
+    let applied: Vec<(i64, bool, Vec<u8>)> = match has_table {
+        true => sqlx::query("SELECT version, success, checksum FROM _sqlx_migrations ORDER BY version")
+            .fetch_all(pool)
+            .await
+            .map_err(db_err)?
+            .iter()
+            .map(|row| (row.get("version"), row.get("success"), row.get("checksum")))
+            .collect(),
+        false => vec![],
+    };
+
+    let mut status = DbMigrationsStatus::default();
+    for (version, success, checksum) in applied.iter() {
+        match MIGRATOR.iter().find(|m| &m.version == version) {
+            None => status.unknown.push(*version),
+            Some(_) if !success => status.failed.push(*version),
+            Some(m) if m.checksum.as_ref() != checksum.as_slice() => status.modified.push(*version),
+            Some(_) => status.applied.push(*version),
+        }
+    }
+    for migration in MIGRATOR.iter().filter(|m| m.migration_type.is_up_migration()) {
+        if !applied.iter().any(|(version, _, _)| version == &migration.version) {
+            status.pending.push((migration.version, migration.description.to_string()));
+        }
+    }
+    Ok(status)
+}
+
+/// Apply the pending database migrations, that are embedded from `ops/db_migrations`.<br/>
+/// Nothing is applied if the database is ahead of this binary, or if the applied migrations were changed or failed.
+/// It returns the status from before applying them, thus the pending ones are the ones that got applied.
+#[cfg(feature = "server")]
+pub async fn apply_db_migrations(pool: &PgPool) -> Result<DbMigrationsStatus, AppError> {
+    //
+    let status = db_migrations_status(pool).await?;
+    if let Some(problems) = status.problems() {
+        return Err(AppError::Err(problems));
+    }
+    if status.pending.is_empty() {
+        return Ok(status);
+    }
+    MIGRATOR.run(pool).await.map_err(|err| {
+        log::error!("Failed to apply the database migrations. Reason: '{}'.", err);
+        AppError::Err(format!("Failed to apply the database migrations: {}", err))
+    })?;
+    for (version, description) in status.pending.iter() {
+        log::info!("Applied the database migration {} ({}).", version, description);
+    }
+    Ok(status)
+}
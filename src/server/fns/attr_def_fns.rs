@@ -1,16 +1,17 @@
 use dioxus_fullstack::prelude::*;
 use server_fn::codec::GetUrl;
 
-use crate::domain::model::{AttributeDef, Id};
+use crate::domain::model::{AttributeDef, Id, Page, Pagination};
 
 #[cfg(feature = "server")]
 use crate::server::Session;
 
-/// List the attribute definitions.
+/// List the attribute definitions, as per the provided pagination options.<br/>
+/// If no pagination is provided, all the attribute definitions are returned.
 #[server(endpoint = "admin/list_attr_defs", input = GetUrl)]
-pub async fn list_attribute_defs() -> Result<Vec<AttributeDef>, ServerFnError> {
+pub async fn list_attribute_defs(pagination: Option<Pagination>) -> Result<Page<AttributeDef>, ServerFnError> {
     let session: Session = extract().await?;
-    let attr_defs = session.attr_def_mgmt().list().await;
+    let attr_defs = session.attr_def_mgmt().list(pagination.as_ref()).await?;
     Ok(attr_defs)
 }
 
@@ -18,7 +19,7 @@ pub async fn list_attribute_defs() -> Result<Vec<AttributeDef>, ServerFnError> {
 #[server(endpoint = "admin/get_attr_def", input = GetUrl)]
 pub async fn get_attribute_def(id: Id) -> Result<Option<AttributeDef>, ServerFnError> {
     let session: Session = extract().await?;
-    let attr_def = session.attr_def_mgmt().get(&id).await;
+    let attr_def = session.attr_def_mgmt().get(&id).await?;
     Ok(attr_def)
 }
 
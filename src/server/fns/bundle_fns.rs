@@ -0,0 +1,48 @@
+use dioxus_fullstack::prelude::*;
+
+use crate::domain::model::{ImportPlan, MetamodelBundle};
+
+#[cfg(feature = "server")]
+use crate::server::Session;
+
+/// Export the metamodel (tags and all the definitions) as a bundle.
+#[server(endpoint = "admin/export_metamodel")]
+pub async fn export_metamodel() -> Result<MetamodelBundle, ServerFnError> {
+    let session: Session = extract().await?;
+    let result = session.bundle_mgmt().export().await;
+    result.map_err(|e| e.into())
+}
+
+/// Import a metamodel bundle. If `dry_run` is true, nothing is changed and just the plan is returned.<br/>
+/// Note that the plan is not applied if it has conflicts.
+#[server(endpoint = "admin/import_metamodel")]
+pub async fn import_metamodel(bundle: MetamodelBundle, dry_run: bool) -> Result<ImportPlan, ServerFnError> {
+    let session: Session = extract().await?;
+    let result = session.bundle_mgmt().import(bundle, dry_run).await;
+    result.map_err(|e| e.into())
+}
+
+/// Export the metamodel as code, that is a YAML document that describes its items by their names.
+#[server(endpoint = "admin/export_metamodel_defs")]
+pub async fn export_metamodel_defs() -> Result<String, ServerFnError> {
+    let session: Session = extract().await?;
+    let result = session.bundle_mgmt().export_defs().await;
+    result.map_err(|e| e.into())
+}
+
+/// Plan the changes of the metamodel described as code (a YAML document), without applying them.
+#[server(endpoint = "admin/plan_metamodel_defs")]
+pub async fn plan_metamodel_defs(yaml: String) -> Result<ImportPlan, ServerFnError> {
+    let session: Session = extract().await?;
+    let result = session.bundle_mgmt().import_defs(&yaml, true).await;
+    result.map_err(|e| e.into())
+}
+
+/// Apply the changes of the metamodel described as code (a YAML document).<br/>
+/// Note that the plan is not applied if it has conflicts.
+#[server(endpoint = "admin/apply_metamodel_defs")]
+pub async fn apply_metamodel_defs(yaml: String) -> Result<ImportPlan, ServerFnError> {
+    let session: Session = extract().await?;
+    let result = session.bundle_mgmt().import_defs(&yaml, false).await;
+    result.map_err(|e| e.into())
+}
@@ -1,4 +1,4 @@
-use crate::domain::model::{EntityDef, Id};
+use crate::domain::model::{EntityDef, Id, Page, Pagination};
 
 #[cfg(feature = "server")]
 use crate::server::Session;
@@ -23,12 +23,13 @@ pub async fn list_entity_defs_refs_by_attr_def_id(attr_def_id: Id) -> Result<Vec
     Ok(result)
 }
 
-/// List the entities definitions.
+/// List the entities definitions, as per the provided pagination options.<br/>
+/// If no pagination is provided, all the entities definitions are returned.
 #[server(endpoint = "admin/list_ent_defs", input = GetUrl)]
-pub async fn list_entities_defs() -> Result<IndexMap<Id, EntityDef>, ServerFnError> {
+pub async fn list_entities_defs(pagination: Option<Pagination>) -> Result<Page<EntityDef>, ServerFnError> {
     let session: Session = extract().await?;
-    let items = session.ent_def_mgmt().list().await?;
-    Ok(IndexMap::from_iter(items.into_iter().map(|item| (item.id.clone(), item))))
+    let items = session.ent_def_mgmt().list(pagination.as_ref()).await?;
+    Ok(items)
 }
 
 /// Create an entity definition.
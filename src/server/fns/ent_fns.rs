@@ -1,5 +1,5 @@
 use crate::{
-    domain::model::{Entity, Id},
+    domain::model::{AttributeFilter, CursorPage, Entity, Id, Page, Pagination},
     ui::pages::Name,
 };
 
@@ -9,11 +9,12 @@ use crate::server::Session;
 use dioxus_fullstack::prelude::*;
 use server_fn::codec::GetUrl;
 
-/// List the entities instances.
+/// List the entities instances, as per the provided pagination options.<br/>
+/// If no pagination is provided, all the entities are returned.
 #[server(endpoint = "admin/list_ents", input = GetUrl)]
-pub async fn list_entities() -> Result<Vec<Entity>, ServerFnError> {
+pub async fn list_entities(pagination: Option<Pagination>) -> Result<Page<Entity>, ServerFnError> {
     let session: Session = extract().await?;
-    let result = session.ent_mgmt().list().await;
+    let result = session.ent_mgmt().list(pagination.as_ref()).await;
     result.map_err(|e| e.into())
 }
 
@@ -34,11 +35,48 @@ pub async fn get_entity(id: Id) -> Result<Option<Entity>, ServerFnError> {
     result.map_err(|e| e.into())
 }
 
-/// List the entities with the same definition.
+/// List the entities (optionally, only the ones with the provided definition id) using keyset pagination.<br/>
+/// The `cursor` is the `next_cursor` of the previously returned page, or none for getting the first page.
+#[server(endpoint = "admin/list_ents_after")]
+pub async fn list_entities_after(
+    def_id: Option<Id>,
+    cursor: Option<String>,
+    limit: Option<i64>,
+) -> Result<CursorPage<Entity>, ServerFnError> {
+    let session: Session = extract().await?;
+    let result = session.ent_mgmt().list_after(def_id.as_ref(), cursor.as_deref(), limit).await;
+    result.map_err(|e| e.into())
+}
+
+/// List the entities with the same definition, as per the provided pagination options.
 #[server(endpoint = "admin/list_ents_by_def_id/:id", input = GetUrl)]
-pub async fn list_entities_by_def_id(id: Id) -> Result<Vec<Entity>, ServerFnError> {
+pub async fn list_entities_by_def_id(id: Id, pagination: Option<Pagination>) -> Result<Page<Entity>, ServerFnError> {
+    let session: Session = extract().await?;
+    let result = session.ent_mgmt().list_by_def_id(&id, pagination.as_ref()).await;
+    result.map_err(|e| e.into())
+}
+
+/// Get the values (as text) of the provided attributes of the provided entities,
+/// as `(entity id, attribute definition id, value)` entries.
+#[server(endpoint = "admin/list_ents_attrs_values")]
+pub async fn list_entities_attributes_values(ent_ids: Vec<Id>, attr_def_ids: Vec<Id>) -> Result<Vec<(Id, Id, String)>, ServerFnError> {
+    let session: Session = extract().await?;
+    let result = session.ent_mgmt().list_attributes_values(&ent_ids, &attr_def_ids).await;
+    result.map_err(|e| e.into())
+}
+
+/// List the entities with the same definition, whose attributes values match the provided filter.
+#[server(endpoint = "admin/list_ents_by_def_id_and_filter")]
+pub async fn list_entities_by_def_id_and_filter(
+    id: Id,
+    filter: AttributeFilter,
+    pagination: Option<Pagination>,
+) -> Result<Page<Entity>, ServerFnError> {
     let session: Session = extract().await?;
-    let result = session.ent_mgmt().list_by_def_id(&id).await;
+    let result = session
+        .ent_mgmt()
+        .list_by_def_id_and_filter(&id, &filter, pagination.as_ref())
+        .await;
     result.map_err(|e| e.into())
 }
 
@@ -0,0 +1,32 @@
+use dioxus_fullstack::prelude::*;
+
+use crate::domain::model::{CsvColumnMapping, CsvImportMode, CsvImportReport, Id};
+
+#[cfg(feature = "server")]
+use crate::server::Session;
+
+/// Parse and validate the CSV content as entities of the provided definition, without importing them.<br/>
+/// If `mapping` is empty, the columns are mapped to the attributes with the same name.
+#[server(endpoint = "admin/preview_ents_csv_import")]
+pub async fn preview_entities_csv_import(
+    def_id: Id,
+    content: String,
+    mapping: Vec<CsvColumnMapping>,
+) -> Result<CsvImportReport, ServerFnError> {
+    let session: Session = extract().await?;
+    let result = session.ent_import_mgmt().preview(&def_id, &content, mapping).await;
+    result.map_err(|e| e.into())
+}
+
+/// Import the entities of the provided definition from the CSV content.
+#[server(endpoint = "admin/import_ents_csv")]
+pub async fn import_entities_csv(
+    def_id: Id,
+    content: String,
+    mapping: Vec<CsvColumnMapping>,
+    mode: CsvImportMode,
+) -> Result<CsvImportReport, ServerFnError> {
+    let session: Session = extract().await?;
+    let result = session.ent_import_mgmt().import(&def_id, &content, mapping, mode).await;
+    result.map_err(|e| e.into())
+}
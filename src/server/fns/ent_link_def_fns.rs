@@ -1,16 +1,17 @@
 use dioxus_fullstack::prelude::*;
 use server_fn::codec::GetUrl;
 
-use crate::domain::model::{EntityLinkDef, Id};
+use crate::domain::model::{EntityLinkDef, Id, Page, Pagination};
 
 #[cfg(feature = "server")]
 use crate::server::Session;
 
-/// List the entity link definitions.
+/// List the entity link definitions, as per the provided pagination options.<br/>
+/// If no pagination is provided, all the entity link definitions are returned.
 #[server(endpoint = "admin/list_ent_link_defs", input = GetUrl)]
-pub async fn list_entity_link_defs() -> Result<Vec<EntityLinkDef>, ServerFnError> {
+pub async fn list_entity_link_defs(pagination: Option<Pagination>) -> Result<Page<EntityLinkDef>, ServerFnError> {
     let session: Session = extract().await?;
-    let result = session.ent_link_def_mgmt().list().await;
+    let result = session.ent_link_def_mgmt().list(pagination.as_ref()).await;
     result.map_err(|e| e.into())
 }
 
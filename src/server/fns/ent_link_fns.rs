@@ -3,7 +3,7 @@ use indexmap::IndexMap;
 use server_fn::codec::{GetUrl, PostUrl};
 
 use crate::{
-    domain::model::{EntityLink, Id},
+    domain::model::{CursorPage, EntityLink, Id, Page, Pagination},
     ui::pages::Name,
 };
 
@@ -12,19 +12,33 @@ use crate::server::Session;
 
 use super::get_entity_link_def;
 
-/// List the entity links.
+/// List the entity links, as per the provided pagination options.<br/>
+/// If no pagination is provided, all the entity links are returned.
 #[server(endpoint = "admin/list_ent_links", input = GetUrl)]
-pub async fn list_entity_links() -> Result<Vec<EntityLink>, ServerFnError> {
+pub async fn list_entity_links(pagination: Option<Pagination>) -> Result<Page<EntityLink>, ServerFnError> {
     let session: Session = extract().await?;
-    let result = session.ent_link_mgmt().list().await;
+    let result = session.ent_link_mgmt().list(pagination.as_ref()).await;
     result.map_err(|e| e.into())
 }
 
-/// List the entity links by their definition id.
+/// List the entity links (optionally, only the ones with the provided definition id) using keyset pagination.<br/>
+/// The `cursor` is the `next_cursor` of the previously returned page, or none for getting the first page.
+#[server(endpoint = "admin/list_ent_links_after")]
+pub async fn list_entity_links_after(
+    def_id: Option<Id>,
+    cursor: Option<String>,
+    limit: Option<i64>,
+) -> Result<CursorPage<EntityLink>, ServerFnError> {
+    let session: Session = extract().await?;
+    let result = session.ent_link_mgmt().list_after(def_id.as_ref(), cursor.as_deref(), limit).await;
+    result.map_err(|e| e.into())
+}
+
+/// List the entity links by their definition id, as per the provided pagination options.
 #[server(endpoint = "admin/list_ent_links_by_def_id/:id", input = GetUrl)]
-pub async fn list_entity_links_by_def_id(id: Id) -> Result<Vec<EntityLink>, ServerFnError> {
+pub async fn list_entity_links_by_def_id(id: Id, pagination: Option<Pagination>) -> Result<Page<EntityLink>, ServerFnError> {
     let session: Session = extract().await?;
-    let result = session.ent_link_mgmt().list_by_def_id(&id).await;
+    let result = session.ent_link_mgmt().list_by_def_id(&id, pagination.as_ref()).await;
     result.map_err(|e| e.into())
 }
 
@@ -32,10 +46,10 @@ pub async fn list_entity_links_by_def_id(id: Id) -> Result<Vec<EntityLink>, Serv
 #[server(endpoint = "admin/list_ent_links_refs_by_def_id/:id", input = GetUrl)]
 pub async fn list_entity_links_refs_by_def_id(id: Id) -> Result<Vec<(Id, Name)>, ServerFnError> {
     let session: Session = extract().await?;
-    let result = session.ent_link_mgmt().list_by_def_id(&id).await;
+    let result = session.ent_link_mgmt().list_by_def_id(&id, None).await;
     result
-        .map(|items| {
-            items
+        .map(|page| {
+            page.items
                 .into_iter()
                 .map(|ent_link| (ent_link.id.clone(), format!("{} (id: {})", ent_link.kind, ent_link.id)))
                 .collect()
@@ -77,9 +91,9 @@ pub async fn get_entity_link_page_data(id: Id) -> Result<Option<(EntityLink, Ind
     match get_entity_link_def(ent_link.def_id.clone()).await {
         Result::Ok(eld_opt) => {
             if let Some(eld) = eld_opt {
-                match session.ent_mgmt().list_by_def_id(&eld.source_entity_def_id).await {
+                match session.ent_mgmt().list_by_def_id(&eld.source_entity_def_id, None).await {
                     Ok(source_entities) => {
-                        for ent in source_entities {
+                        for ent in source_entities.items {
                             source_entities_id_name.insert(ent.id, format!("{}: {}", ent.listing_attr_name, ent.listing_attr_value));
                         }
                     }
@@ -91,9 +105,9 @@ pub async fn get_entity_link_page_data(id: Id) -> Result<Option<(EntityLink, Ind
                         );
                     }
                 }
-                match session.ent_mgmt().list_by_def_id(&eld.target_entity_def_id).await {
+                match session.ent_mgmt().list_by_def_id(&eld.target_entity_def_id, None).await {
                     Ok(target_entities) => {
-                        for ent in target_entities {
+                        for ent in target_entities.items {
                             target_entities_id_name.insert(ent.id, format!("{}: {}", ent.listing_attr_name, ent.listing_attr_value));
                         }
                     }
@@ -0,0 +1,39 @@
+use dioxus_fullstack::prelude::*;
+use server_fn::codec::GetUrl;
+
+use crate::domain::model::{EntityGraph, EntityGraphFilter, Id};
+
+#[cfg(feature = "server")]
+use crate::server::Session;
+
+/// Get the graph of entities and the links between them, as per the provided filter.
+#[server(endpoint = "admin/get_ent_graph")]
+pub async fn get_entity_graph(filter: EntityGraphFilter) -> Result<EntityGraph, ServerFnError> {
+    //
+    let session: Session = extract().await?;
+    let entities = session.ent_mgmt().list_by_graph_filter(&filter).await?;
+    let ent_ids: Vec<Id> = entities.iter().map(|ent| ent.id.clone()).collect();
+    let links = session
+        .ent_link_mgmt()
+        .list_by_entity_ids(&ent_ids, &filter.ent_link_def_ids)
+        .await?;
+    Ok(EntityGraph::new(entities, links))
+}
+
+/// Get the neighborhood of an entity, that is the entity itself, its links and the entities on the other end of them.
+#[server(endpoint = "admin/get_ent_neighborhood", input = GetUrl)]
+pub async fn get_entity_neighborhood(id: Id) -> Result<EntityGraph, ServerFnError> {
+    //
+    let session: Session = extract().await?;
+    let links = session.ent_link_mgmt().list_by_entity_id(&id).await?;
+    let mut ent_ids = vec![id];
+    for link in links.iter() {
+        for ent_id in [&link.source_entity_id, &link.target_entity_id] {
+            if !ent_ids.contains(ent_id) {
+                ent_ids.push(ent_id.clone());
+            }
+        }
+    }
+    let entities = session.ent_mgmt().list_by_ids(&ent_ids).await?;
+    Ok(EntityGraph::new(entities, links))
+}
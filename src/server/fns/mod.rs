@@ -16,8 +16,23 @@ pub use ent_def_fns::*;
 mod ent_fns;
 pub use ent_fns::*;
 
+mod ent_import_fns;
+pub use ent_import_fns::*;
+
 mod ent_link_def_fns;
 pub use ent_link_def_fns::*;
 
 mod ent_link_fns;
 pub use ent_link_fns::*;
+
+mod bundle_fns;
+pub use bundle_fns::*;
+
+mod graph_fns;
+pub use graph_fns::*;
+
+mod saved_view_fns;
+pub use saved_view_fns::*;
+
+mod search_fns;
+pub use search_fns::*;
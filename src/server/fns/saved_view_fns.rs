@@ -0,0 +1,62 @@
+use dioxus_fullstack::prelude::*;
+
+use crate::domain::model::{Entity, Id, Page, Pagination, SavedView};
+
+#[cfg(feature = "server")]
+use crate::server::Session;
+
+/// List the saved views that are visible to the current user, that is the own ones and the shared ones.
+#[server(endpoint = "list_saved_views")]
+pub async fn list_saved_views() -> Result<Vec<SavedView>, ServerFnError> {
+    //
+    let session: Session = extract().await?;
+    let user_id = session.current_user_id()?;
+    let views = session.saved_view_mgmt().list(&user_id).await?;
+    Ok(views)
+}
+
+#[server(endpoint = "get_saved_view")]
+pub async fn get_saved_view(id: Id) -> Result<Option<SavedView>, ServerFnError> {
+    //
+    let session: Session = extract().await?;
+    let user_id = session.current_user_id()?;
+    let view = session.saved_view_mgmt().get(&id, &user_id).await?;
+    Ok(view)
+}
+
+/// Create a saved view, owned by the current user. It returns the id of the created view.
+#[server(endpoint = "create_saved_view")]
+pub async fn create_saved_view(view: SavedView) -> Result<Id, ServerFnError> {
+    //
+    let session: Session = extract().await?;
+    let user_id = session.current_user_id()?;
+    let id = session.saved_view_mgmt().add(view, &user_id).await?;
+    Ok(id)
+}
+
+#[server(endpoint = "update_saved_view")]
+pub async fn update_saved_view(view: SavedView) -> Result<(), ServerFnError> {
+    //
+    let session: Session = extract().await?;
+    let user_id = session.current_user_id()?;
+    session.saved_view_mgmt().update(view, &user_id).await.map(|_| Ok(()))?
+}
+
+#[server(endpoint = "remove_saved_view")]
+pub async fn remove_saved_view(id: Id) -> Result<(), ServerFnError> {
+    //
+    let session: Session = extract().await?;
+    let user_id = session.current_user_id()?;
+    session.saved_view_mgmt().remove(&id, &user_id).await.map(|_| Ok(()))?
+}
+
+/// List the entities of a saved view, as per the provided pagination options.<br/>
+/// If the pagination has no sorting, the view's sorting is used.
+#[server(endpoint = "list_saved_view_entities")]
+pub async fn list_saved_view_entities(id: Id, pagination: Option<Pagination>) -> Result<Page<Entity>, ServerFnError> {
+    //
+    let session: Session = extract().await?;
+    let user_id = session.current_user_id()?;
+    let page = session.saved_view_mgmt().list_entities(&id, &user_id, pagination).await?;
+    Ok(page)
+}
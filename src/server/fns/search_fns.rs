@@ -0,0 +1,17 @@
+use dioxus_fullstack::prelude::*;
+
+use crate::domain::model::{Id, ItemType, SearchHit};
+
+#[cfg(feature = "server")]
+use crate::server::Session;
+
+/// Full-text search across entities, entity links and definitions.<br/>
+/// The results can be restricted to some item types (`kinds`) and definitions (`def_ids`),
+/// an empty list meaning no restriction. They are ordered by relevance, most relevant first.
+#[server(endpoint = "search")]
+pub async fn search(query: String, kinds: Vec<ItemType>, def_ids: Vec<Id>, limit: Option<i64>) -> Result<Vec<SearchHit>, ServerFnError> {
+    //
+    let session: Session = extract().await?;
+    let hits = session.search_mgmt().search(&query, &kinds, &def_ids, limit).await?;
+    Ok(hits)
+}
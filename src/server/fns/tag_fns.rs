@@ -1,15 +1,17 @@
-use crate::domain::model::{Id, Tag};
+use crate::domain::model::{Id, Page, Pagination, Tag};
 use dioxus_fullstack::prelude::*;
 use server_fn::codec::GetUrl;
 
 #[cfg(feature = "server")]
 use crate::server::Session;
 
+/// Get the tags, as per the provided pagination options.<br/>
+/// If no pagination is provided, all the tags are returned.
 #[server(endpoint = "get_tags", input = GetUrl)]
-pub async fn get_tags() -> Result<Vec<Tag>, ServerFnError> {
+pub async fn get_tags(pagination: Option<Pagination>) -> Result<Page<Tag>, ServerFnError> {
     //
     let session: Session = extract().await?;
-    let tags = session.tag_mgmt().list().await?;
+    let tags = session.tag_mgmt().list(pagination.as_ref()).await?;
     Ok(tags)
 }
 
@@ -0,0 +1,54 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_graphql::dataloader::{DataLoader, Loader};
+
+use crate::{
+    domain::model::{Entity, Id},
+    server::{AppError, ServerState},
+};
+
+use super::GqlAttribute;
+
+/// Create the data loaders of a query. These are per query, so that their cached items are not reused by other queries.
+pub fn data_loaders(state: &ServerState) -> (DataLoader<EntityLoader>, DataLoader<AttributesLoader>) {
+    (
+        DataLoader::new(EntityLoader(state.clone()), tokio::spawn),
+        DataLoader::new(AttributesLoader(state.clone()), tokio::spawn),
+    )
+}
+
+/// It loads the entities by their ids, in batches (such as the sources and targets of the listed links).<br/>
+/// Note that the attributes of the entities are not loaded (see `AttributesLoader`).
+pub struct EntityLoader(pub ServerState);
+
+impl Loader<Id> for EntityLoader {
+    type Value = Entity;
+    type Error = Arc<AppError>;
+
+    async fn load(&self, keys: &[Id]) -> Result<HashMap<Id, Entity>, Self::Error> {
+        let ents = self.0.ent_mgmt.list_by_ids(keys).await.map_err(Arc::new)?;
+        Ok(ents.into_iter().map(|ent| (ent.id.clone(), ent)).collect())
+    }
+}
+
+/// It loads the attributes of the entities or entity links by their ids, in batches.<br/>
+/// The owners without any attribute values are not included in the result.
+pub struct AttributesLoader(pub ServerState);
+
+impl Loader<Id> for AttributesLoader {
+    type Value = Vec<GqlAttribute>;
+    type Error = Arc<AppError>;
+
+    async fn load(&self, keys: &[Id]) -> Result<HashMap<Id, Vec<GqlAttribute>>, Self::Error> {
+        let attrs = self.0.ent_mgmt.list_attributes_by_owner_ids(keys).await.map_err(Arc::new)?;
+        let mut res: HashMap<Id, Vec<GqlAttribute>> = HashMap::new();
+        for (owner_id, attr) in attrs {
+            res.entry(owner_id).or_default().push(GqlAttribute {
+                def_id: attr.def_id.to_string(),
+                name: attr.name,
+                value: attr.value,
+            });
+        }
+        Ok(res)
+    }
+}
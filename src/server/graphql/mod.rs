@@ -0,0 +1,55 @@
+//! The GraphQL API over the knowledge graph, served at `/graphql`.<br/>
+//! It exposes the entities (with their nested links traversal), the definitions and the tags.<br/>
+//! The entities and attributes of the nested fields are loaded in batches, through the data loaders of each query.
+
+mod loaders;
+mod objects;
+pub use loaders::*;
+pub use objects::*;
+
+use async_graphql::{http::GraphiQLSource, EmptyMutation, EmptySubscription, Request, Response, Schema};
+use axum::{
+    response::{Html, IntoResponse},
+    routing::get,
+    Extension, Json, Router,
+};
+
+use crate::server::ServerState;
+
+pub type GraphSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// The maximum depth of a query, that limits the nesting of the links traversal.
+pub const MAX_QUERY_DEPTH: usize = 12;
+
+/// The maximum complexity of a query, where by default each field counts as one.
+pub const MAX_QUERY_COMPLEXITY: usize = 2000;
+
+/// Build the GraphQL schema, whose resolvers use the provided state's management services.
+pub fn build_graphql_schema(state: ServerState) -> GraphSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(state)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+/// The router of the GraphQL API. A `POST` executes a query, while a `GET` serves the GraphiQL page.
+pub fn graphql_router(state: ServerState) -> Router {
+    Router::new()
+        .route("/", get(graphiql).post(graphql_handler))
+        .layer(Extension(build_graphql_schema(state.clone())))
+        .layer(Extension(state))
+}
+
+async fn graphql_handler(
+    Extension(schema): Extension<GraphSchema>,
+    Extension(state): Extension<ServerState>,
+    Json(req): Json<Request>,
+) -> Json<Response> {
+    let (ent_loader, attrs_loader) = data_loaders(&state);
+    Json(schema.execute(req.data(ent_loader).data(attrs_loader)).await)
+}
+
+async fn graphiql() -> impl IntoResponse {
+    Html(GraphiQLSource::build().endpoint("/graphql").finish())
+}
@@ -0,0 +1,409 @@
+use async_graphql::{dataloader::DataLoader, Context, Enum, Object, Result, SimpleObject};
+
+use crate::{
+    domain::model::{AttributeDef, CursorPage, Entity, EntityDef, EntityLink, EntityLinkDef, Id, Pagination, Tag},
+    server::ServerState,
+};
+
+use super::{AttributesLoader, EntityLoader};
+
+/// The maximum number of items that a list field returns.
+const MAX_LIST_LIMIT: i32 = 100;
+
+fn pagination(page: Option<i32>, limit: Option<i32>) -> Pagination {
+    Pagination::new(
+        page.unwrap_or(1).max(1),
+        limit.unwrap_or(Pagination::DEFAULT_LIMIT).clamp(1, MAX_LIST_LIMIT),
+    )
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    //
+    async fn entity(&self, ctx: &Context<'_>, id: String) -> Result<Option<GqlEntity>> {
+        let state = ctx.data::<ServerState>()?;
+        Ok(state.ent_mgmt.get(&Id::from(id)).await?.map(GqlEntity))
+    }
+
+    /// List the entities, optionally just the ones of a definition.
+    async fn entities(&self, ctx: &Context<'_>, def_id: Option<String>, page: Option<i32>, limit: Option<i32>) -> Result<Vec<GqlEntity>> {
+        let state = ctx.data::<ServerState>()?;
+        let pagination = pagination(page, limit);
+        let page = match def_id {
+            Some(def_id) => state.ent_mgmt.list_by_def_id(&Id::from(def_id), Some(&pagination)).await?,
+            None => state.ent_mgmt.list(Some(&pagination)).await?,
+        };
+        Ok(page.items.into_iter().map(GqlEntity).collect())
+    }
+
+    async fn entity_link(&self, ctx: &Context<'_>, id: String) -> Result<Option<GqlEntityLink>> {
+        let state = ctx.data::<ServerState>()?;
+        Ok(state.ent_link_mgmt.get(&Id::from(id)).await?.map(GqlEntityLink))
+    }
+
+    async fn entity_def(&self, ctx: &Context<'_>, id: String) -> Result<Option<GqlEntityDef>> {
+        let state = ctx.data::<ServerState>()?;
+        Ok(state.ent_def_mgmt.get(&Id::from(id)).await.map(GqlEntityDef))
+    }
+
+    async fn entity_defs(&self, ctx: &Context<'_>) -> Result<Vec<GqlEntityDef>> {
+        let state = ctx.data::<ServerState>()?;
+        let page = state.ent_def_mgmt.list(None).await?;
+        Ok(page.items.into_iter().map(GqlEntityDef).collect())
+    }
+
+    async fn entity_link_defs(&self, ctx: &Context<'_>) -> Result<Vec<GqlEntityLinkDef>> {
+        let state = ctx.data::<ServerState>()?;
+        let page = state.ent_link_def_mgmt.list(None).await?;
+        Ok(page.items.into_iter().map(GqlEntityLinkDef).collect())
+    }
+
+    async fn attribute_defs(&self, ctx: &Context<'_>) -> Result<Vec<GqlAttributeDef>> {
+        let state = ctx.data::<ServerState>()?;
+        let page = state.attr_def_mgmt.list(None).await?;
+        Ok(page.items.into_iter().map(GqlAttributeDef).collect())
+    }
+
+    async fn tags(&self, ctx: &Context<'_>) -> Result<Vec<GqlTag>> {
+        let state = ctx.data::<ServerState>()?;
+        let page = state.tag_mgmt.list(None).await?;
+        Ok(page.items.into_iter().map(GqlTag).collect())
+    }
+}
+
+/// An attribute value of an entity or entity link.
+#[derive(Clone, SimpleObject)]
+pub struct GqlAttribute {
+    pub def_id: String,
+    pub name: String,
+    /// The value, in its text form.
+    pub value: String,
+}
+
+/// The direction of the links, relative to an entity.
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum LinkDirection {
+    Outgoing,
+    Incoming,
+    Both,
+}
+
+pub struct GqlEntity(pub Entity);
+
+#[Object(name = "Entity")]
+impl GqlEntity {
+    //
+    async fn id(&self) -> &str {
+        self.0.id.as_str()
+    }
+
+    /// Its kind, that is its definition name.
+    async fn kind(&self) -> &str {
+        &self.0.kind
+    }
+
+    async fn def_id(&self) -> &str {
+        self.0.def_id.as_str()
+    }
+
+    /// Its name, that is the value of its listing attribute.
+    async fn name(&self) -> &str {
+        &self.0.listing_attr_value
+    }
+
+    async fn definition(&self, ctx: &Context<'_>) -> Result<Option<GqlEntityDef>> {
+        let state = ctx.data::<ServerState>()?;
+        Ok(state.ent_def_mgmt.get(&self.0.def_id).await.map(GqlEntityDef))
+    }
+
+    /// Its attributes. These are loaded on demand (in batches), since the listed entities do not include them.
+    async fn attributes(&self, ctx: &Context<'_>) -> Result<Vec<GqlAttribute>> {
+        if has_attributes(&self.0) {
+            return Ok(entity_attributes(&self.0));
+        }
+        let loader = ctx.data::<DataLoader<AttributesLoader>>()?;
+        Ok(loader.load_one(self.0.id.clone()).await?.unwrap_or_default())
+    }
+
+    /// Its links, optionally filtered by their direction and definition, as a page of at most `first` of them,
+    /// starting after the `after` cursor (that is the `nextCursor` of the previous page).
+    #[graphql(complexity = "first.unwrap_or(Pagination::DEFAULT_LIMIT).clamp(1, MAX_LIST_LIMIT) as usize * child_complexity")]
+    async fn links(
+        &self,
+        ctx: &Context<'_>,
+        direction: Option<LinkDirection>,
+        def_id: Option<String>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> Result<GqlEntityLinkPage> {
+        let state = ctx.data::<ServerState>()?;
+        let (outgoing, incoming) = match direction.unwrap_or(LinkDirection::Both) {
+            LinkDirection::Outgoing => (true, false),
+            LinkDirection::Incoming => (false, true),
+            LinkDirection::Both => (true, true),
+        };
+        let def_id = def_id.map(Id::from);
+        let limit = first.unwrap_or(Pagination::DEFAULT_LIMIT).clamp(1, MAX_LIST_LIMIT);
+        let page = state
+            .ent_link_mgmt
+            .list_by_entity_id_after(&self.0.id, outgoing, incoming, def_id.as_ref(), after.as_deref(), Some(limit as i64))
+            .await?;
+        Ok(GqlEntityLinkPage(page))
+    }
+}
+
+/// A page of entity links.
+pub struct GqlEntityLinkPage(pub CursorPage<EntityLink>);
+
+#[Object(name = "EntityLinkPage")]
+impl GqlEntityLinkPage {
+    //
+    async fn items(&self) -> Vec<GqlEntityLink> {
+        self.0.items.iter().cloned().map(GqlEntityLink).collect()
+    }
+
+    /// The cursor (to be provided as `after`) of the next page, if there is one.
+    async fn next_cursor(&self) -> Option<&str> {
+        self.0.next_cursor.as_deref()
+    }
+}
+
+pub struct GqlEntityLink(pub EntityLink);
+
+#[Object(name = "EntityLink")]
+impl GqlEntityLink {
+    //
+    async fn id(&self) -> &str {
+        self.0.id.as_str()
+    }
+
+    /// Its kind, that is its definition name.
+    async fn kind(&self) -> &str {
+        &self.0.kind
+    }
+
+    async fn def_id(&self) -> &str {
+        self.0.def_id.as_str()
+    }
+
+    async fn source(&self, ctx: &Context<'_>) -> Result<Option<GqlEntity>> {
+        let loader = ctx.data::<DataLoader<EntityLoader>>()?;
+        Ok(loader.load_one(self.0.source_entity_id.clone()).await?.map(GqlEntity))
+    }
+
+    async fn target(&self, ctx: &Context<'_>) -> Result<Option<GqlEntity>> {
+        let loader = ctx.data::<DataLoader<EntityLoader>>()?;
+        Ok(loader.load_one(self.0.target_entity_id.clone()).await?.map(GqlEntity))
+    }
+
+    /// Its attributes. These are loaded on demand (in batches), since the listed links do not include them.
+    async fn attributes(&self, ctx: &Context<'_>) -> Result<Vec<GqlAttribute>> {
+        if has_link_attributes(&self.0) {
+            return Ok(link_attributes(&self.0));
+        }
+        let loader = ctx.data::<DataLoader<AttributesLoader>>()?;
+        Ok(loader.load_one(self.0.id.clone()).await?.unwrap_or_default())
+    }
+}
+
+pub struct GqlEntityDef(pub EntityDef);
+
+#[Object(name = "EntityDef")]
+impl GqlEntityDef {
+    //
+    async fn id(&self) -> &str {
+        self.0.id.as_str()
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn description(&self) -> Option<&str> {
+        self.0.description.as_deref()
+    }
+
+    async fn listing_attr_def_id(&self) -> &str {
+        self.0.listing_attr_def_id.as_str()
+    }
+
+    /// Its attributes definitions, in their show order.
+    async fn attributes(&self, ctx: &Context<'_>) -> Result<Vec<GqlAttributeDef>> {
+        if !self.0.attributes.is_empty() {
+            return Ok(self.0.attributes.iter().cloned().map(GqlAttributeDef).collect());
+        }
+        let state = ctx.data::<ServerState>()?;
+        let ent_def = state.ent_def_mgmt.get(&self.0.id).await;
+        Ok(ent_def
+            .map(|d| d.attributes.into_iter().map(GqlAttributeDef).collect())
+            .unwrap_or_default())
+    }
+
+    #[graphql(complexity = "10 * child_complexity")]
+    async fn entities(&self, ctx: &Context<'_>, page: Option<i32>, limit: Option<i32>) -> Result<Vec<GqlEntity>> {
+        let state = ctx.data::<ServerState>()?;
+        let page = state.ent_mgmt.list_by_def_id(&self.0.id, Some(&pagination(page, limit))).await?;
+        Ok(page.items.into_iter().map(GqlEntity).collect())
+    }
+}
+
+pub struct GqlEntityLinkDef(pub EntityLinkDef);
+
+#[Object(name = "EntityLinkDef")]
+impl GqlEntityLinkDef {
+    //
+    async fn id(&self) -> &str {
+        self.0.id.as_str()
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn description(&self) -> Option<&str> {
+        self.0.description.as_deref()
+    }
+
+    /// Its cardinality, as `1:1`, `1:M`, or `M:M`.
+    async fn cardinality(&self) -> String {
+        self.0.cardinality.as_string()
+    }
+
+    async fn source_entity_def(&self, ctx: &Context<'_>) -> Result<Option<GqlEntityDef>> {
+        let state = ctx.data::<ServerState>()?;
+        Ok(state.ent_def_mgmt.get(&self.0.source_entity_def_id).await.map(GqlEntityDef))
+    }
+
+    async fn target_entity_def(&self, ctx: &Context<'_>) -> Result<Option<GqlEntityDef>> {
+        let state = ctx.data::<ServerState>()?;
+        Ok(state.ent_def_mgmt.get(&self.0.target_entity_def_id).await.map(GqlEntityDef))
+    }
+
+    async fn attributes(&self) -> Vec<GqlAttributeDef> {
+        let attributes = self.0.attributes.clone().unwrap_or_default();
+        attributes.into_iter().map(GqlAttributeDef).collect()
+    }
+}
+
+pub struct GqlAttributeDef(pub AttributeDef);
+
+#[Object(name = "AttributeDef")]
+impl GqlAttributeDef {
+    //
+    async fn id(&self) -> &str {
+        self.0.id.as_str()
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn description(&self) -> Option<&str> {
+        self.0.description.as_deref()
+    }
+
+    /// The type of its values, as the PostgreSQL type name (such as `text` or `integer`).
+    async fn value_type(&self) -> String {
+        self.0.value_type.to_string()
+    }
+
+    async fn default_value(&self) -> &str {
+        &self.0.default_value
+    }
+
+    async fn is_required(&self) -> bool {
+        self.0.is_required
+    }
+
+    async fn tag(&self, ctx: &Context<'_>) -> Result<Option<GqlTag>> {
+        let Some(tag_id) = &self.0.tag_id else {
+            return Ok(None);
+        };
+        let state = ctx.data::<ServerState>()?;
+        Ok(state.tag_mgmt.get(tag_id.to_string()).await?.map(GqlTag))
+    }
+}
+
+pub struct GqlTag(pub Tag);
+
+#[Object(name = "Tag")]
+impl GqlTag {
+    //
+    async fn id(&self) -> &str {
+        self.0.id.as_str()
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn description(&self) -> Option<&str> {
+        self.0.description.as_deref()
+    }
+}
+
+fn has_attributes(ent: &Entity) -> bool {
+    !(ent.text_attributes.is_empty()
+        && ent.smallint_attributes.is_empty()
+        && ent.int_attributes.is_empty()
+        && ent.boolean_attributes.is_empty())
+}
+
+fn has_link_attributes(link: &EntityLink) -> bool {
+    !(link.text_attributes.is_empty()
+        && link.smallint_attributes.is_empty()
+        && link.int_attributes.is_empty()
+        && link.boolean_attributes.is_empty())
+}
+
+fn entity_attributes(ent: &Entity) -> Vec<GqlAttribute> {
+    let mut attrs = vec![];
+    attrs.extend(ent.text_attributes.iter().map(|a| attribute(&a.def_id, &a.name, a.value.clone())));
+    attrs.extend(
+        ent.smallint_attributes
+            .iter()
+            .map(|a| attribute(&a.def_id, &a.name, a.value.to_string())),
+    );
+    attrs.extend(
+        ent.int_attributes
+            .iter()
+            .map(|a| attribute(&a.def_id, &a.name, a.value.to_string())),
+    );
+    attrs.extend(
+        ent.boolean_attributes
+            .iter()
+            .map(|a| attribute(&a.def_id, &a.name, a.value.to_string())),
+    );
+    attrs
+}
+
+fn link_attributes(link: &EntityLink) -> Vec<GqlAttribute> {
+    let mut attrs = vec![];
+    attrs.extend(link.text_attributes.iter().map(|a| attribute(&a.def_id, &a.name, a.value.clone())));
+    attrs.extend(
+        link.smallint_attributes
+            .iter()
+            .map(|a| attribute(&a.def_id, &a.name, a.value.to_string())),
+    );
+    attrs.extend(
+        link.int_attributes
+            .iter()
+            .map(|a| attribute(&a.def_id, &a.name, a.value.to_string())),
+    );
+    attrs.extend(
+        link.boolean_attributes
+            .iter()
+            .map(|a| attribute(&a.def_id, &a.name, a.value.to_string())),
+    );
+    attrs
+}
+
+fn attribute(def_id: &Id, name: &str, value: String) -> GqlAttribute {
+    GqlAttribute {
+        def_id: def_id.to_string(),
+        name: name.to_string(),
+        value,
+    }
+}
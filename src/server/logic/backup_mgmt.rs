@@ -0,0 +1,227 @@
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read, Write},
+    sync::Arc,
+};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::server::{AppError, AppResult, BackupRepo, DumpPart};
+
+/// The description of a backup, stored in its archive as `manifest.json`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BackupManifest {
+    /// The version of the archive's format.
+    pub format_version: u32,
+    /// The version of the database schema (that is the latest applied migration) that the data belongs to.
+    pub schema_version: i64,
+    pub created_at: String,
+    pub tables: Vec<BackupTable>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BackupTable {
+    pub name: String,
+    pub rows: u64,
+}
+
+/// The management of backing up and restoring all the knowledge data, as a (gzip compressed) tar archive
+/// that contains the manifest (first) and then the JSON Lines files of each table's rows, in chunks.<br/>
+/// Being done at the application level, it does not depend on the version of PostgreSQL or of its tools.
+/// The rows are streamed into and out of the archive, thus they are not held in memory all at once.
+pub struct BackupMgmt {
+    repo: Arc<BackupRepo>,
+}
+
+impl BackupMgmt {
+    //
+    /// The current format version. Archives with a newer version cannot be restored.<br/>
+    /// Since version 2, the rows of a table are in (one or more) `tables/<table>/<chunk>.jsonl` files,
+    /// instead of a single `tables/<table>.jsonl` file.
+    pub const FORMAT_VERSION: u32 = 2;
+
+    const MANIFEST_PATH: &'static str = "manifest.json";
+
+    pub fn new(repo: Arc<BackupRepo>) -> Self {
+        Self { repo }
+    }
+
+    /// Write a backup of all the data to the provided writer.
+    pub async fn backup<W: Write>(&self, writer: W) -> AppResult<BackupManifest> {
+        //
+        let mut archive = tar::Builder::new(GzEncoder::new(writer, Compression::default()));
+        let mut manifest = None;
+        let mut chunks: HashMap<&str, u32> = HashMap::new();
+        self.repo
+            .dump(|part| match part {
+                DumpPart::Start { schema_version, tables } => {
+                    let started = BackupManifest {
+                        format_version: Self::FORMAT_VERSION,
+                        schema_version,
+                        created_at: chrono::Utc::now().to_rfc3339(),
+                        tables: tables
+                            .iter()
+                            .map(|(name, rows)| BackupTable {
+                                name: name.to_string(),
+                                rows: *rows,
+                            })
+                            .collect(),
+                    };
+                    let manifest_json = serde_json::to_vec_pretty(&started).map_err(|e| AppError::Err(e.to_string()))?;
+                    append_file(&mut archive, Self::MANIFEST_PATH, &manifest_json)?;
+                    manifest = Some(started);
+                    Ok(())
+                }
+                DumpPart::Rows { table, content } => {
+                    let chunk = chunks.entry(table).or_default();
+                    *chunk += 1;
+                    append_file(&mut archive, &format!("tables/{}/{:05}.jsonl", table, chunk), content)
+                }
+            })
+            .await?;
+        archive
+            .into_inner()
+            .and_then(|encoder| encoder.finish())
+            .map_err(|e| AppError::Err(format!("Failed to write the backup. Reason: {}", e)))?;
+        let manifest = manifest.ok_or(AppError::Err("Failed to write the backup.".into()))?;
+        log::info!(
+            "Backed up {} tables with schema version {}.",
+            manifest.tables.len(),
+            manifest.schema_version
+        );
+        Ok(manifest)
+    }
+
+    /// Restore the backup read from the provided reader, replacing all the existing data.<br/>
+    /// The backup must be complete, have the same schema version as the database, and have the rows of the tables
+    /// in the order that these were backed up.
+    /// Nothing is changed if its rows cannot be inserted or if their references are not valid.
+    pub async fn restore<R: Read>(&self, reader: R) -> AppResult<BackupManifest> {
+        //
+        let read_err = |e: std::io::Error| AppError::Err(format!("Failed to read the backup. Reason: {}", e));
+        let mut archive = tar::Archive::new(GzDecoder::new(reader));
+        let mut entries = archive.entries().map_err(read_err)?;
+        let manifest = read_manifest(entries.next())?;
+        if manifest.format_version > Self::FORMAT_VERSION {
+            return Err(AppError::Err(format!(
+                "Unsupported backup format version {}. The latest supported version is {}.",
+                manifest.format_version,
+                Self::FORMAT_VERSION
+            )));
+        }
+        for name in BackupRepo::TABLES {
+            if !manifest.tables.iter().any(|t| t.name == name) {
+                return Err(AppError::Err(format!("The backup does not include '{}' table.", name)));
+            }
+        }
+        if let Some(unknown) = manifest.tables.iter().find(|t| !BackupRepo::TABLES.contains(&t.name.as_str())) {
+            return Err(AppError::Err(format!("The backup includes the unknown '{}' table.", unknown.name)));
+        }
+
+        // The rows are read (and restored) chunk by chunk, and their count is checked once all of them are read.
+        // Since a table refers only to the ones before it, its rows must come after all the rows of these.
+        let mut read_rows: HashMap<String, u64> = HashMap::new();
+        let mut last_table_idx = 0;
+        let next_rows = || -> AppResult<Option<(String, Vec<Value>)>> {
+            // FYI: The directories are skipped, as the archive may have been repacked with them.
+            let entry = loop {
+                match entries.next().transpose().map_err(read_err)? {
+                    Some(entry) if entry.header().entry_type().is_dir() => continue,
+                    Some(entry) => break entry,
+                    None => {
+                        for table in manifest.tables.iter() {
+                            let rows = read_rows.get(&table.name).copied().unwrap_or_default();
+                            if rows != table.rows {
+                                return Err(AppError::Err(format!(
+                                    "The backup includes {} rows of '{}' table, instead of {}.",
+                                    rows, table.name, table.rows
+                                )));
+                            }
+                        }
+                        return Ok(None);
+                    }
+                }
+            };
+            let path = entry.path().map_err(read_err)?.to_string_lossy().to_string();
+            let table_idx = table_of(&path)
+                .and_then(|name| BackupRepo::TABLES.iter().position(|table| *table == name))
+                .ok_or(AppError::Err(format!("The backup includes the unknown '{}' file.", path)))?;
+            let name = BackupRepo::TABLES[table_idx].to_string();
+            if table_idx != last_table_idx {
+                let incomplete = BackupRepo::TABLES[..table_idx].iter().find(|table| {
+                    let rows = read_rows.get(**table).copied().unwrap_or_default();
+                    manifest.tables.iter().any(|t| &t.name == *table && t.rows != rows)
+                });
+                if let Some(table) = incomplete {
+                    return Err(AppError::Err(format!(
+                        "The rows of '{}' table come before all the ones of '{}' table, instead of in the backup's order of the tables.",
+                        name, table
+                    )));
+                }
+                last_table_idx = table_idx;
+            }
+            let mut rows = vec![];
+            for line in BufReader::new(entry).lines() {
+                let line = line.map_err(read_err)?;
+                if line.is_empty() {
+                    continue;
+                }
+                let row = serde_json::from_str::<Value>(&line)
+                    .map_err(|e| AppError::Err(format!("Invalid rows of '{}' table. Reason: {}", name, e)))?;
+                rows.push(row);
+            }
+            *read_rows.entry(name.clone()).or_default() += rows.len() as u64;
+            Ok(Some((name, rows)))
+        };
+
+        let failed = self.repo.restore(manifest.schema_version, next_rows).await?;
+        if !failed.is_empty() {
+            let reasons: Vec<String> = failed
+                .iter()
+                .map(|check| format!("{} ({} found)", check.description, check.count))
+                .collect();
+            return Err(AppError::Err(format!(
+                "The backup was not restored, since it has invalid references. {}",
+                reasons.join(" ")
+            )));
+        }
+        log::info!("Restored the backup created at {}.", manifest.created_at);
+        Ok(manifest)
+    }
+}
+
+/// Get the name of the table whose rows are in the provided file of the archive,
+/// that is either `tables/<table>/<chunk>.jsonl` or (in the format version 1) `tables/<table>.jsonl`.
+fn table_of(path: &str) -> Option<&str> {
+    let file = path.strip_prefix("tables/")?;
+    match file.split_once('/') {
+        Some((name, _)) => Some(name),
+        None => file.strip_suffix(".jsonl"),
+    }
+}
+
+fn append_file<W: Write>(archive: &mut tar::Builder<W>, path: &str, content: &[u8]) -> AppResult<()> {
+    //
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(chrono::Utc::now().timestamp() as u64);
+    archive
+        .append_data(&mut header, path, content)
+        .map_err(|e| AppError::Err(format!("Failed to write the backup. Reason: {}", e)))
+}
+
+/// Read the manifest, that must be the first file of the archive.
+fn read_manifest<R: Read>(entry: Option<std::io::Result<tar::Entry<'_, R>>>) -> AppResult<BackupManifest> {
+    //
+    let read_err = |e: std::io::Error| AppError::Err(format!("Failed to read the backup. Reason: {}", e));
+    let mut entry = entry.ok_or(AppError::Err("The backup is empty.".into()))?.map_err(read_err)?;
+    if entry.path().map_err(read_err)?.to_string_lossy() != BackupMgmt::MANIFEST_PATH {
+        return Err(AppError::Err("The backup does not start with its manifest.".into()));
+    }
+    let mut manifest = vec![];
+    entry.read_to_end(&mut manifest).map_err(read_err)?;
+    serde_json::from_slice(&manifest).map_err(|e| AppError::Err(format!("Invalid backup manifest. Reason: {}", e)))
+}
@@ -0,0 +1,170 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use crate::{
+    domain::model::{AttributeDef, ConfigKey, ConfigLayer, ConfigRevision, EffectiveConfig, NewConfigRevision, ResolvedConfig},
+    server::{AppError, AppResult, AttributeDefRepo, ConfigRepo},
+};
+
+/// The management of the centralized configuration.<br/>
+/// There is a configuration set per layer (the global one, and the ones of each service, environment, version and instance),
+/// and every change of it is a new (immutable) revision. The items of a revision are typed and validated as per their
+/// attribute definitions. The configuration of a service version in an environment is resolved through all its layers.
+pub struct ConfigMgmt {
+    repo: Arc<ConfigRepo>,
+    attr_def_repo: Arc<AttributeDefRepo>,
+}
+
+impl ConfigMgmt {
+    //
+    /// The maximum length of the service, version, environment, instance and item keys.
+    const MAX_NAME_LEN: usize = 64;
+    const MAX_COMMENT_LEN: usize = 256;
+
+    pub fn new(repo: Arc<ConfigRepo>, attr_def_repo: Arc<AttributeDefRepo>) -> Self {
+        Self { repo, attr_def_repo }
+    }
+
+    /// Add a new revision of a configuration set, by the provided (authenticated) author.
+    /// It fails, with all the problems found, if any item is not valid.
+    pub async fn add_revision(&self, key: ConfigKey, author: &str, new: NewConfigRevision) -> AppResult<ConfigRevision> {
+        //
+        let mut errors = vec![];
+        if key.layer().is_none() {
+            errors.push(format!(
+                "'{}' is not a configuration layer: the environment is required for a version, and the version for an instance.",
+                key
+            ));
+        }
+        let components = [
+            ("service", &key.service),
+            ("version", &key.version),
+            ("environment", &key.env),
+            ("instance", &key.instance),
+        ];
+        for (what, name) in components.into_iter().filter(|(_, name)| !name.is_empty()) {
+            if let Err(e) = validate_name(what, name) {
+                errors.push(e);
+            }
+        }
+        let comment = new.comment.trim();
+        if comment.len() > Self::MAX_COMMENT_LEN {
+            errors.push(format!("The comment can have at most {} characters.", Self::MAX_COMMENT_LEN));
+        }
+
+        let mut attr_defs: HashMap<String, Option<AttributeDef>> = HashMap::new();
+        let mut keys = HashSet::new();
+        let mut items = Vec::with_capacity(new.items.len());
+        for mut item in new.items.into_iter() {
+            item.key = item.key.trim().to_string();
+            if let Err(e) = validate_name("key", &item.key) {
+                errors.push(e);
+                continue;
+            }
+            if !keys.insert(item.key.clone()) {
+                errors.push(format!("The key '{}' is provided more than once.", item.key));
+                continue;
+            }
+            if !attr_defs.contains_key(item.attr_def_id.as_str()) {
+                let attr_def = self.attr_def_repo.get(&item.attr_def_id).await?;
+                attr_defs.insert(item.attr_def_id.to_string(), attr_def);
+            }
+            let Some(attr_def) = &attr_defs[item.attr_def_id.as_str()] else {
+                errors.push(format!("Key '{}': unknown attribute definition '{}'.", item.key, item.attr_def_id));
+                continue;
+            };
+            item.value = item.value.trim().to_string();
+            if item.value.is_empty() {
+                item.value = attr_def.default_value.trim().to_string();
+            }
+            if item.value.is_empty() && attr_def.is_required {
+                errors.push(format!(
+                    "Key '{}': a value is required, as per attribute '{}'.",
+                    item.key, attr_def.name
+                ));
+                continue;
+            }
+            if attr_def.value_type.parse_value(&item.value).is_none() {
+                errors.push(format!(
+                    "Key '{}': invalid {} value '{}', as per attribute '{}'.",
+                    item.key,
+                    attr_def.value_type.label(),
+                    item.value,
+                    attr_def.name
+                ));
+                continue;
+            }
+            item.value_type = attr_def.value_type.clone();
+            items.push(item);
+        }
+        if !errors.is_empty() {
+            return Err(AppError::Err(errors.join(" ")));
+        }
+
+        items.sort_by(|a, b| a.key.cmp(&b.key));
+        let revision = self.repo.add_revision(&key, author, comment, &items).await?;
+        log::info!("Added revision {} of config '{}' by '{}'.", revision.revision, key, author);
+        Ok(revision)
+    }
+
+    /// Get a revision of a configuration set, or its latest one if no revision is provided.
+    pub async fn get_revision(&self, key: &ConfigKey, revision: Option<i32>) -> AppResult<Option<ConfigRevision>> {
+        self.repo.get_revision(key, revision).await
+    }
+
+    /// List the revisions of a configuration set, the latest first.
+    pub async fn list_revisions(&self, key: &ConfigKey) -> AppResult<Vec<ConfigRevision>> {
+        self.repo.list_revisions(key).await
+    }
+
+    /// Resolve the configuration of a service version in an environment (or of one of its instances)
+    /// through the latest revisions of its layers. It is `None` if none of these layers has any revision.<br/>
+    /// The unused overrides of its layers are found across all the known configurations of the service.
+    pub async fn resolve(&self, key: &ConfigKey) -> AppResult<Option<ResolvedConfig>> {
+        //
+        let revisions = self.list_layers_revisions(key).await?;
+        if revisions.is_empty() {
+            return Ok(None);
+        }
+        let service_revisions = self.repo.list_service_revisions(&key.service).await?;
+        Ok(Some(ResolvedConfig::resolve(key.clone(), revisions, &service_revisions)))
+    }
+
+    /// Get the effective configuration, that is the one resolved through its layers, as values only.
+    /// It does not look for the unused overrides, as it is what the services get on each (re)load of their configuration.
+    pub async fn get_effective(&self, key: &ConfigKey) -> AppResult<Option<EffectiveConfig>> {
+        //
+        let revisions = self.list_layers_revisions(key).await?;
+        Ok((!revisions.is_empty()).then(|| EffectiveConfig::resolve(key.clone(), revisions)))
+    }
+
+    /// The latest revisions of the layers of a service version in an environment (or of one of its instances).
+    async fn list_layers_revisions(&self, key: &ConfigKey) -> AppResult<Vec<ConfigRevision>> {
+        //
+        if !matches!(key.layer(), Some(ConfigLayer::Version | ConfigLayer::Instance)) {
+            return Err(AppError::Err(format!(
+                "'{}' cannot be resolved: the service, version and environment are required.",
+                key
+            )));
+        }
+        self.repo.list_layers_revisions(key).await
+    }
+}
+
+/// Validate a name (of a service, version, environment, instance, or key): it must have letters, digits, `.`, `_` or `-` only.
+fn validate_name(what: &str, name: &str) -> Result<(), String> {
+    //
+    if name.is_empty() || name.len() > ConfigMgmt::MAX_NAME_LEN {
+        return Err(format!(
+            "The {} is required, with at most {} characters.",
+            what,
+            ConfigMgmt::MAX_NAME_LEN
+        ));
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')) {
+        return Err(format!("The {} '{}' can have letters, digits, '.', '_' and '-' only.", what, name));
+    }
+    Ok(())
+}
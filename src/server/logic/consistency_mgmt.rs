@@ -0,0 +1,21 @@
+use std::sync::Arc;
+
+use crate::server::{AppResult, ConsistencyCheck, ConsistencyRepo};
+
+/// The management of checking the consistency of the stored data,
+/// that is of the relations that are maintained by the application (and not by the database).
+pub struct ConsistencyMgmt {
+    repo: Arc<ConsistencyRepo>,
+}
+
+impl ConsistencyMgmt {
+    //
+    pub fn new(repo: Arc<ConsistencyRepo>) -> Self {
+        Self { repo }
+    }
+
+    /// Run all the checks. It returns their outcome, including the ones that found no inconsistencies.
+    pub async fn check(&self) -> AppResult<Vec<ConsistencyCheck>> {
+        self.repo.check_all().await
+    }
+}
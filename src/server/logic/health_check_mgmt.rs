@@ -0,0 +1,288 @@
+use std::{
+    sync::{Arc, OnceLock},
+    time::{Duration, Instant},
+};
+
+use futures::StreamExt;
+use http::{header::USER_AGENT, Request, Uri};
+use http_body_util::Empty;
+use hyper::body::Bytes;
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use hyper_util::{
+    client::legacy::{connect::HttpConnector, Client},
+    rt::TokioExecutor,
+};
+use tokio::net::TcpStream;
+
+use crate::{
+    domain::model::ServiceInstance,
+    server::{AppResult, ServiceRegistryMgmt},
+};
+
+/// The configuration of the active health checks, that can be set using the `HEALTH_CHECK_*` environment variables.
+#[derive(Clone, Debug)]
+pub struct HealthCheckConfig {
+    /// How often all the instances are checked (`HEALTH_CHECK_INTERVAL_SECS`, defaults to 10).
+    pub interval: Duration,
+    /// How long a check may take, before it is considered failed (`HEALTH_CHECK_TIMEOUT_SECS`, defaults to 2).
+    pub timeout: Duration,
+    /// How many instances are checked at the same time (`HEALTH_CHECK_CONCURRENCY`, defaults to 16).
+    pub concurrency: usize,
+    /// After how many consecutive failed checks an instance is down (`HEALTH_CHECK_FAILURE_THRESHOLD`, defaults to 3).
+    pub failure_threshold: i32,
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(10),
+            timeout: Duration::from_secs(2),
+            concurrency: 16,
+            failure_threshold: 3,
+        }
+    }
+}
+
+impl HealthCheckConfig {
+    //
+    /// Get the configuration from the environment variables, using the defaults for the ones that are missing or invalid.
+    pub fn from_env() -> Self {
+        //
+        fn var<T: std::str::FromStr>(name: &str) -> Option<T> {
+            std::env::var(name).ok().and_then(|value| value.trim().parse().ok())
+        }
+        let default = Self::default();
+        Self {
+            interval: var("HEALTH_CHECK_INTERVAL_SECS")
+                .filter(|i| *i > 0)
+                .map(Duration::from_secs)
+                .unwrap_or(default.interval),
+            timeout: var("HEALTH_CHECK_TIMEOUT_SECS")
+                .filter(|t| *t > 0)
+                .map(Duration::from_secs)
+                .unwrap_or(default.timeout),
+            concurrency: var("HEALTH_CHECK_CONCURRENCY").filter(|c| *c > 0).unwrap_or(default.concurrency),
+            failure_threshold: var("HEALTH_CHECK_FAILURE_THRESHOLD")
+                .filter(|t| *t > 0)
+                .unwrap_or(default.failure_threshold),
+        }
+    }
+}
+
+/// The management of the active health checks of the registered service instances.<br/>
+/// The instances that have a health check are periodically probed, and the outcome is recorded on their entities.
+pub struct HealthCheckMgmt {
+    registry_mgmt: Arc<ServiceRegistryMgmt>,
+    config: HealthCheckConfig,
+}
+
+impl HealthCheckMgmt {
+    //
+    /// The supported schemes of the health checks.
+    pub const SCHEMES: [&'static str; 3] = ["http://", "https://", "tcp://"];
+
+    pub fn new(registry_mgmt: Arc<ServiceRegistryMgmt>, config: HealthCheckConfig) -> Self {
+        Self { registry_mgmt, config }
+    }
+
+    pub fn config(&self) -> &HealthCheckConfig {
+        &self.config
+    }
+
+    /// Check all the instances that have a health check, at most `concurrency` at a time.
+    /// It returns the number of checked instances.
+    pub async fn check_all(&self) -> AppResult<usize> {
+        //
+        let instances: Vec<ServiceInstance> = self
+            .registry_mgmt
+            .list(None)
+            .await?
+            .into_iter()
+            .filter(|instance| !instance.health_check.is_empty())
+            .collect();
+        let count = instances.len();
+        futures::stream::iter(instances)
+            .for_each_concurrent(self.config.concurrency, |instance| async move {
+                let started = Instant::now();
+                let outcome = probe_health_check(&instance.health_check, self.config.timeout).await;
+                if let Err(e) = &outcome {
+                    log::debug!("Health check of service instance id:'{}' failed: {}", instance.id, e);
+                }
+                if let Err(e) = self
+                    .registry_mgmt
+                    .record_health_check(&instance, outcome.is_ok(), started.elapsed(), self.config.failure_threshold)
+                    .await
+                {
+                    log::error!(
+                        "Failed to record the health check of service instance id:'{}'. Cause: '{}'.",
+                        instance.id,
+                        e
+                    );
+                }
+            })
+            .await;
+        Ok(count)
+    }
+}
+
+/// Probe a service endpoint, as per its health check. It passes if:
+/// - for an `http://` or `https://` URL, the response to a `GET` of it has a 2xx or 3xx status.
+/// - for a `tcp://host:port` address, the connection is accepted.
+///
+/// It fails if it takes longer than the provided timeout.
+pub async fn probe_health_check(health_check: &str, timeout: Duration) -> Result<(), String> {
+    tokio::time::timeout(timeout, probe_endpoint(health_check))
+        .await
+        .map_err(|_| format!("Timed out after {} ms.", timeout.as_millis()))?
+}
+
+async fn probe_endpoint(health_check: &str) -> Result<(), String> {
+    //
+    if let Some(address) = health_check.strip_prefix("tcp://") {
+        TcpStream::connect(address.trim_end_matches('/')).await.map_err(|e| e.to_string())?;
+        return Ok(());
+    }
+    if !health_check.starts_with("http://") && !health_check.starts_with("https://") {
+        return Err(format!("Unsupported health check '{}'.", health_check));
+    }
+    let uri: Uri = health_check
+        .parse()
+        .map_err(|e| format!("Invalid health check URL '{}'. Reason: {}", health_check, e))?;
+    let request = Request::get(uri)
+        .header(USER_AGENT, "cognitive-health-check")
+        .body(Empty::<Bytes>::new())
+        .map_err(|e| e.to_string())?;
+    let response = http_client().request(request).await.map_err(|e| {
+        // The client's errors tell what failed (like "client error (Connect)"), and their sources tell why.
+        let mut message = e.to_string();
+        let mut source = std::error::Error::source(&e);
+        while let Some(cause) = source {
+            message.push_str(&format!(": {}", cause));
+            source = cause.source();
+        }
+        message
+    })?;
+    match response.status().as_u16() {
+        200..=399 => Ok(()),
+        status => Err(format!("HTTP status {}.", status)),
+    }
+}
+
+/// The HTTP(S) client of the health checks, that is shared by all of them.<br/>
+/// It keeps no idle connections, thus each check connects to the endpoint, just like its clients would.
+fn http_client() -> &'static Client<HttpsConnector<HttpConnector>, Empty<Bytes>> {
+    //
+    static CLIENT: OnceLock<Client<HttpsConnector<HttpConnector>, Empty<Bytes>>> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        let connector = HttpsConnectorBuilder::new()
+            .with_webpki_roots()
+            .https_or_http()
+            .enable_http1()
+            .build();
+        Client::builder(TokioExecutor::new()).pool_max_idle_per_host(0).build(connector)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    //
+    use std::time::Duration;
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::probe_health_check;
+
+    const TIMEOUT: Duration = Duration::from_millis(500);
+
+    /// Start a dummy HTTP server that responds once with the provided response. It returns its address.
+    async fn http_server(response: &'static str) -> String {
+        //
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request).await;
+            let _ = stream.write_all(response.as_bytes()).await;
+        });
+        address
+    }
+
+    #[tokio::test]
+    async fn http_check_passes_on_200() {
+        let address = http_server("HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok").await;
+        let outcome = probe_health_check(&format!("http://{}/health", address), TIMEOUT).await;
+        assert_eq!(outcome, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn http_check_passes_on_redirect() {
+        let address = http_server("HTTP/1.1 302 Found\r\nLocation: /\r\n\r\n").await;
+        let outcome = probe_health_check(&format!("http://{}", address), TIMEOUT).await;
+        assert_eq!(outcome, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn http_check_fails_on_503() {
+        let address = http_server("HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n").await;
+        let outcome = probe_health_check(&format!("http://{}/health", address), TIMEOUT).await;
+        assert_eq!(outcome, Err("HTTP status 503.".to_string()));
+    }
+
+    #[tokio::test]
+    async fn http_check_fails_on_invalid_response() {
+        let address = http_server("hello\n").await;
+        let outcome = probe_health_check(&format!("http://{}/health", address), TIMEOUT).await;
+        assert!(outcome.is_err());
+    }
+
+    #[tokio::test]
+    async fn http_check_passes_with_a_query_and_no_path() {
+        let address = http_server("HTTP/1.1 204 No Content\r\n\r\n").await;
+        let outcome = probe_health_check(&format!("http://{}?ready=1", address), TIMEOUT).await;
+        assert_eq!(outcome, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn https_check_fails_on_a_plain_http_server() {
+        let address = http_server("HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n").await;
+        let outcome = probe_health_check(&format!("https://{}/health", address), TIMEOUT).await;
+        assert!(outcome.is_err());
+    }
+
+    #[tokio::test]
+    async fn http_check_fails_on_timeout_of_a_silent_server() {
+        // The connection is accepted (in the listener's backlog), but nothing is ever responded.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let outcome = probe_health_check(&format!("http://{}/health", address), Duration::from_millis(200)).await;
+        assert_eq!(outcome, Err("Timed out after 200 ms.".to_string()));
+        drop(listener);
+    }
+
+    #[tokio::test]
+    async fn tcp_check_passes_when_the_connection_is_accepted() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let outcome = probe_health_check(&format!("tcp://{}", address), TIMEOUT).await;
+        assert_eq!(outcome, Ok(()));
+        drop(listener);
+    }
+
+    #[tokio::test]
+    async fn tcp_check_fails_when_the_connection_is_refused() {
+        // The port of a listener that is closed right away, thus nothing listens on it anymore.
+        let address = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap();
+        let outcome = probe_health_check(&format!("tcp://{}", address), TIMEOUT).await;
+        assert!(outcome.is_err());
+    }
+
+    #[tokio::test]
+    async fn unsupported_check_fails() {
+        let outcome = probe_health_check("udp://127.0.0.1:53", TIMEOUT).await;
+        assert_eq!(outcome, Err("Unsupported health check 'udp://127.0.0.1:53'.".to_string()));
+    }
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use crate::{
-    domain::model::{AttributeDef, Id},
+    domain::model::{AttributeDef, Id, Page, Pagination},
     server::{AppResult, AttributeDefRepo},
 };
 
@@ -15,14 +15,14 @@ impl AttributeDefMgmt {
         Self { attr_repo }
     }
 
-    pub async fn get(&self, id: &Id) -> Option<AttributeDef> {
+    pub async fn get(&self, id: &Id) -> AppResult<Option<AttributeDef>> {
         //
         self.attr_repo.get(id).await
     }
 
-    pub async fn list(&self) -> Vec<AttributeDef> {
+    pub async fn list(&self, pagination: Option<&Pagination>) -> AppResult<Page<AttributeDef>> {
         //
-        self.attr_repo.list(None).await
+        self.attr_repo.list(pagination).await
     }
 
     /// Add a new attribute definition. It returns the id of the stored entry.
@@ -0,0 +1,380 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    domain::model::{Id, ImportAction, ImportActionKind, ImportConflict, ImportPlan, ItemType, MetamodelBundle, MetamodelDefs},
+    server::{AppError, AppResult, AttributeValueCounts, MetamodelBundleRepo},
+};
+
+/// The management of exporting and importing the metamodel as a (portable) bundle.
+pub struct MetamodelBundleMgmt {
+    repo: Arc<MetamodelBundleRepo>,
+}
+
+impl MetamodelBundleMgmt {
+    //
+    pub fn new(repo: Arc<MetamodelBundleRepo>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn export(&self) -> AppResult<MetamodelBundle> {
+        self.repo.export().await
+    }
+
+    /// Import a bundle. Its items are matched to the existing ones by their id or else by their name.<br/>
+    /// The existing items that are absent from the bundle are reported (as `Absent`), but they are left as they are.<br/>
+    /// If `dry_run` is true or there are conflicts, nothing is changed and just the plan is returned.
+    /// Otherwise, the plan is applied in the same (single) transaction that it is made in.
+    pub async fn import(&self, bundle: MetamodelBundle, dry_run: bool) -> AppResult<ImportPlan> {
+        //
+        if bundle.version > MetamodelBundle::VERSION {
+            return Err(AppError::Err(format!(
+                "Unsupported bundle version {}. The latest supported version is {}.",
+                bundle.version,
+                MetamodelBundle::VERSION
+            )));
+        }
+        self.repo
+            .import(dry_run, |current, value_counts| Self::plan(current, value_counts, bundle))
+            .await
+    }
+
+    /// Export the metamodel as code, that is a YAML document (see `MetamodelDefs`).
+    pub async fn export_defs(&self) -> AppResult<String> {
+        let bundle = self.repo.export().await?;
+        serde_norway::to_string(&MetamodelDefs::from_bundle(&bundle)).map_err(|e| AppError::Err(e.to_string()))
+    }
+
+    /// Plan (if `dry_run` is true) or apply the metamodel described as code (a YAML document) against the current one.<br/>
+    /// The items are matched to the existing ones by their names, and then imported as a bundle, thus the same plan is returned.
+    /// Note that the existing items that are not described in the document are left as they are, and they can be referred to.
+    pub async fn import_defs(&self, yaml: &str, dry_run: bool) -> AppResult<ImportPlan> {
+        //
+        let defs: MetamodelDefs =
+            serde_norway::from_str(yaml).map_err(|e| AppError::Err(format!("Invalid metamodel definitions. Reason: {}", e)))?;
+        let current = self.repo.export().await?;
+        let bundle = defs
+            .to_bundle(&current)
+            .map_err(|errors| AppError::Err(format!("Invalid metamodel definitions. {}", errors.join(" "))))?;
+        self.import(bundle, dry_run).await
+    }
+
+    /// Plan the import of the bundle against the current metamodel.<br/>
+    /// It returns the plan and the bundle with its ids (and references) resolved to the ones of this instance.
+    /// An attribute cannot be removed from a definition whose instances have values of it, as these would be left orphaned.
+    fn plan(current: &MetamodelBundle, value_counts: &AttributeValueCounts, mut bundle: MetamodelBundle) -> (ImportPlan, MetamodelBundle) {
+        //
+        let mut plan = ImportPlan::default();
+
+        // First, resolve the ids of all the items.
+        let tag_ids = resolve_ids(
+            &mut plan,
+            ItemType::Tag,
+            bundle.tags.iter().map(|t| (t.id.clone(), t.name.clone())).collect(),
+            current.tags.iter().map(|t| (t.id.clone(), t.name.clone())).collect(),
+        );
+        // FYI: The attributes definitions are unique by their name and description.
+        let attr_def_key =
+            |name: &String, description: &Option<String>| format!("{}\u{1f}{}", name, description.clone().unwrap_or_default());
+        let attr_def_ids = resolve_ids(
+            &mut plan,
+            ItemType::AttributeDef,
+            bundle
+                .attribute_defs
+                .iter()
+                .map(|a| (a.id.clone(), attr_def_key(&a.name, &a.description)))
+                .collect(),
+            current
+                .attribute_defs
+                .iter()
+                .map(|a| (a.id.clone(), attr_def_key(&a.name, &a.description)))
+                .collect(),
+        );
+        let ent_def_ids = resolve_ids(
+            &mut plan,
+            ItemType::EntityDef,
+            bundle.entity_defs.iter().map(|d| (d.id.clone(), d.name.clone())).collect(),
+            current.entity_defs.iter().map(|d| (d.id.clone(), d.name.clone())).collect(),
+        );
+        let ent_link_def_ids = resolve_ids(
+            &mut plan,
+            ItemType::EntityLinkDef,
+            bundle.entity_link_defs.iter().map(|d| (d.id.clone(), d.name.clone())).collect(),
+            current.entity_link_defs.iter().map(|d| (d.id.clone(), d.name.clone())).collect(),
+        );
+
+        // Then, rewrite the ids and references, and compare each item with the existing one.
+        let mut conflicts = vec![];
+        for tag in bundle.tags.iter_mut() {
+            let bundle_id = tag.id.clone();
+            tag.id = tag_ids[&bundle_id].clone();
+            let existing = current.tags.iter().find(|t| t.id == tag.id);
+            plan.actions.push(action(
+                ItemType::Tag,
+                &tag.name,
+                bundle_id,
+                tag.id.clone(),
+                existing.map(|t| t == &*tag),
+            ));
+        }
+        for attr_def in bundle.attribute_defs.iter_mut() {
+            let bundle_id = attr_def.id.clone();
+            attr_def.id = attr_def_ids[&bundle_id].clone();
+            if let Some(tag_id) = &attr_def.tag_id {
+                match resolve_ref(tag_id, &tag_ids, current.tags.iter().map(|t| &t.id)) {
+                    Some(id) => attr_def.tag_id = Some(id),
+                    None => conflicts.push(conflict(
+                        ItemType::AttributeDef,
+                        &attr_def.name,
+                        format!("Unknown tag id '{tag_id}'."),
+                    )),
+                }
+            }
+            let existing = current.attribute_defs.iter().find(|a| a.id == attr_def.id);
+            if let Some(existing) = existing {
+                if existing.value_type != attr_def.value_type {
+                    conflicts.push(conflict(
+                        ItemType::AttributeDef,
+                        &attr_def.name,
+                        format!(
+                            "Its value type cannot be changed from {} to {}.",
+                            existing.value_type, attr_def.value_type
+                        ),
+                    ));
+                }
+            }
+            let unchanged = existing.map(|a| a == &*attr_def);
+            plan.actions.push(action(
+                ItemType::AttributeDef,
+                &attr_def.name,
+                bundle_id,
+                attr_def.id.clone(),
+                unchanged,
+            ));
+        }
+        let current_attr_def_ids = || current.attribute_defs.iter().map(|a| &a.id);
+        for ent_def in bundle.entity_defs.iter_mut() {
+            let bundle_id = ent_def.id.clone();
+            ent_def.id = ent_def_ids[&bundle_id].clone();
+            let mut attr_ids = vec![];
+            for attr_def_id in ent_def.attribute_def_ids.iter() {
+                match resolve_ref(attr_def_id, &attr_def_ids, current_attr_def_ids()) {
+                    Some(id) => attr_ids.push(id),
+                    None => conflicts.push(conflict(
+                        ItemType::EntityDef,
+                        &ent_def.name,
+                        format!("Unknown attribute definition id '{attr_def_id}'."),
+                    )),
+                }
+            }
+            ent_def.attribute_def_ids = attr_ids;
+            if let Some(existing) = current.entity_defs.iter().find(|d| d.id == ent_def.id) {
+                conflicts.extend(removed_attrs_conflicts(
+                    ItemType::EntityDef,
+                    &ent_def.name,
+                    &ent_def.id,
+                    &existing.attribute_def_ids,
+                    &ent_def.attribute_def_ids,
+                    current,
+                    value_counts,
+                ));
+            }
+            match resolve_ref(&ent_def.listing_attr_def_id, &attr_def_ids, current_attr_def_ids()) {
+                Some(id) if ent_def.attribute_def_ids.contains(&id) => ent_def.listing_attr_def_id = id,
+                _ => conflicts.push(conflict(
+                    ItemType::EntityDef,
+                    &ent_def.name,
+                    "Its listing attribute must be one of its attributes.".into(),
+                )),
+            }
+            let unchanged = current.entity_defs.iter().find(|d| d.id == ent_def.id).map(|d| d == &*ent_def);
+            plan.actions
+                .push(action(ItemType::EntityDef, &ent_def.name, bundle_id, ent_def.id.clone(), unchanged));
+        }
+        for link_def in bundle.entity_link_defs.iter_mut() {
+            let bundle_id = link_def.id.clone();
+            link_def.id = ent_link_def_ids[&bundle_id].clone();
+            for ent_def_id in [&mut link_def.source_entity_def_id, &mut link_def.target_entity_def_id] {
+                match resolve_ref(ent_def_id, &ent_def_ids, current.entity_defs.iter().map(|d| &d.id)) {
+                    Some(id) => *ent_def_id = id,
+                    None => conflicts.push(conflict(
+                        ItemType::EntityLinkDef,
+                        &link_def.name,
+                        format!("Unknown entity definition id '{ent_def_id}'."),
+                    )),
+                }
+            }
+            let mut attr_ids = vec![];
+            for attr_def_id in link_def.attribute_def_ids.iter() {
+                match resolve_ref(attr_def_id, &attr_def_ids, current_attr_def_ids()) {
+                    Some(id) => attr_ids.push(id),
+                    None => conflicts.push(conflict(
+                        ItemType::EntityLinkDef,
+                        &link_def.name,
+                        format!("Unknown attribute definition id '{attr_def_id}'."),
+                    )),
+                }
+            }
+            link_def.attribute_def_ids = attr_ids;
+            if let Some(existing) = current.entity_link_defs.iter().find(|d| d.id == link_def.id) {
+                conflicts.extend(removed_attrs_conflicts(
+                    ItemType::EntityLinkDef,
+                    &link_def.name,
+                    &link_def.id,
+                    &existing.attribute_def_ids,
+                    &link_def.attribute_def_ids,
+                    current,
+                    value_counts,
+                ));
+            }
+            let unchanged = current
+                .entity_link_defs
+                .iter()
+                .find(|d| d.id == link_def.id)
+                .map(|d| d == &*link_def);
+            plan.actions.push(action(
+                ItemType::EntityLinkDef,
+                &link_def.name,
+                bundle_id,
+                link_def.id.clone(),
+                unchanged,
+            ));
+        }
+        plan.conflicts.extend(conflicts);
+
+        // Finally, report the existing items that are absent from the bundle, as these are left as they are.
+        let absent = |plan: &ImportPlan, item_type: ItemType, items: Vec<(&Id, &String)>| -> Vec<ImportAction> {
+            items
+                .into_iter()
+                .filter(|(id, _)| !plan.actions.iter().any(|a| a.item_type == item_type && &a.target_id == *id))
+                .map(|(id, name)| ImportAction {
+                    kind: ImportActionKind::Absent,
+                    item_type: item_type.clone(),
+                    name: name.clone(),
+                    bundle_id: Id::default(),
+                    target_id: id.clone(),
+                })
+                .collect()
+        };
+        let absent_actions = [
+            absent(&plan, ItemType::Tag, current.tags.iter().map(|t| (&t.id, &t.name)).collect()),
+            absent(
+                &plan,
+                ItemType::AttributeDef,
+                current.attribute_defs.iter().map(|a| (&a.id, &a.name)).collect(),
+            ),
+            absent(&plan, ItemType::EntityDef, current.entity_defs.iter().map(|d| (&d.id, &d.name)).collect()),
+            absent(
+                &plan,
+                ItemType::EntityLinkDef,
+                current.entity_link_defs.iter().map(|d| (&d.id, &d.name)).collect(),
+            ),
+        ];
+        plan.actions.extend(absent_actions.into_iter().flatten());
+
+        (plan, bundle)
+    }
+}
+
+/// Resolve the ids of the bundle's items (provided as (id, key) pairs) to the ones of the existing items.<br/>
+/// An item is matched by its id first, and then by its key (name). If not matched, it keeps its id (or gets a new one).
+/// The empty or duplicate ids and the duplicate keys in the bundle, and the id matches whose key is taken by another item,
+/// are reported as conflicts.
+fn resolve_ids(plan: &mut ImportPlan, item_type: ItemType, items: Vec<(Id, String)>, existing: Vec<(Id, String)>) -> HashMap<Id, Id> {
+    //
+    let mut ids = HashMap::new();
+    let mut seen_keys: Vec<&String> = vec![];
+    for (id, key) in items.iter() {
+        let name = key.split('\u{1f}').next().unwrap_or_default();
+        if seen_keys.contains(&key) {
+            plan.conflicts
+                .push(conflict(item_type.clone(), name, "It is included more than once.".into()));
+        }
+        seen_keys.push(key);
+        if id.is_empty() || ids.contains_key(id) {
+            let reason = match id.is_empty() {
+                true => "It has no id.".to_string(),
+                false => format!("Its id '{id}' is used by another item of the bundle."),
+            };
+            plan.conflicts.push(conflict(item_type.clone(), name, reason));
+            // The first mapping is kept, just for the rest of the plan to be made, since a plan with conflicts is not applied.
+            ids.entry(id.clone()).or_insert_with(|| id.clone());
+            continue;
+        }
+        let by_id = existing.iter().find(|(existing_id, _)| existing_id == id);
+        let by_key = existing.iter().find(|(_, existing_key)| existing_key == key);
+        let target_id = match (by_id, by_key) {
+            (Some(_), Some((other_id, _))) if other_id != id => {
+                plan.conflicts.push(conflict(
+                    item_type.clone(),
+                    name,
+                    format!("Its name is used by another item (id '{other_id}')."),
+                ));
+                id.clone()
+            }
+            (Some(_), _) => id.clone(),
+            (None, Some((existing_id, _))) => existing_id.clone(),
+            (None, None) => id.clone(),
+        };
+        ids.insert(id.clone(), target_id);
+    }
+    ids
+}
+
+/// Resolve a reference, that is either to an item of the bundle or to an existing one.
+fn resolve_ref<'a>(id: &Id, bundle_ids: &HashMap<Id, Id>, mut existing_ids: impl Iterator<Item = &'a Id>) -> Option<Id> {
+    match bundle_ids.get(id) {
+        Some(target_id) => Some(target_id.clone()),
+        None => existing_ids.find(|existing_id| *existing_id == id).cloned(),
+    }
+}
+
+/// The conflicts of removing attributes (from an existing entity or entity link definition) that its instances have values of.
+fn removed_attrs_conflicts(
+    item_type: ItemType,
+    name: &str,
+    def_id: &Id,
+    existing_attr_def_ids: &[Id],
+    attr_def_ids: &[Id],
+    current: &MetamodelBundle,
+    value_counts: &AttributeValueCounts,
+) -> Vec<ImportConflict> {
+    //
+    existing_attr_def_ids
+        .iter()
+        .filter(|id| !attr_def_ids.contains(id))
+        .filter_map(|id| {
+            let count = value_counts.get(&(def_id.clone(), id.clone())).copied().unwrap_or_default();
+            let attr_name = current.attribute_defs.iter().find(|a| &a.id == id).map_or(id.as_str(), |a| a.name.as_str());
+            (count > 0).then(|| {
+                conflict(
+                    item_type.clone(),
+                    name,
+                    format!("Its attribute '{attr_name}' cannot be removed, as {count} of its instances have values of it."),
+                )
+            })
+        })
+        .collect()
+}
+
+fn action(item_type: ItemType, name: &str, bundle_id: Id, target_id: Id, unchanged: Option<bool>) -> ImportAction {
+    let kind = match unchanged {
+        None => ImportActionKind::Create,
+        Some(false) => ImportActionKind::Update,
+        Some(true) => ImportActionKind::Unchanged,
+    };
+    ImportAction {
+        kind,
+        item_type,
+        name: name.to_string(),
+        bundle_id,
+        target_id,
+    }
+}
+
+fn conflict(item_type: ItemType, name: &str, reason: String) -> ImportConflict {
+    ImportConflict {
+        item_type,
+        name: name.to_string(),
+        reason,
+    }
+}
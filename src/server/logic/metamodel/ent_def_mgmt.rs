@@ -1,7 +1,7 @@
 use indexmap::IndexMap;
 
 use crate::{
-    domain::model::{EntityDef, Id},
+    domain::model::{EntityDef, Id, Page, Pagination},
     server::{AppResult, EntityDefRepo},
     ui::pages::Name,
 };
@@ -21,8 +21,8 @@ impl EntityDefMgmt {
         self.ent_repo.list_ids_names().await
     }
 
-    pub async fn list(&self) -> AppResult<Vec<EntityDef>> {
-        self.ent_repo.list(None).await
+    pub async fn list(&self, pagination: Option<&Pagination>) -> AppResult<Page<EntityDef>> {
+        self.ent_repo.list(pagination).await
     }
 
     pub async fn list_refs_by_attr_def_id(&self, attr_def_id: Id) -> AppResult<Vec<(Id, Name)>> {
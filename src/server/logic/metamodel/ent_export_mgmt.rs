@@ -0,0 +1,180 @@
+use std::sync::Arc;
+
+use futures::{channel::mpsc, SinkExt, StreamExt};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    domain::model::{EntityDef, ExportFormat, Id},
+    server::{AppError, AppResult, EntityExportRepo, ExportedEntity, ExportedEntityLink},
+};
+
+/// The chunks of an export's content, as they get produced.
+pub type ExportChunks = mpsc::Receiver<AppResult<Vec<u8>>>;
+
+/// The management of exporting the entities of a definition (and their links).<br/>
+/// The content is produced by a background task that streams the rows from the database,
+/// so that the export is not loaded into memory as a whole.
+pub struct EntityExportMgmt {
+    repo: Arc<EntityExportRepo>,
+}
+
+impl EntityExportMgmt {
+    //
+    /// The number of produced chunks that can wait for being consumed, before pausing the reading from the database.
+    const BUFFERED_CHUNKS: usize = 64;
+
+    pub fn new(repo: Arc<EntityExportRepo>) -> Self {
+        Self { repo }
+    }
+
+    /// Export the entities of the provided definition, including all their attributes (in the definition's order).<br/>
+    /// If `with_links` is true, the targets of their outgoing links are included as well, grouped by the link definition.
+    pub async fn export_entities(&self, def: EntityDef, format: ExportFormat, with_links: bool) -> AppResult<ExportChunks> {
+        //
+        let link_defs = match with_links {
+            true => self.repo.list_outgoing_link_defs(&def.id).await?,
+            false => vec![],
+        };
+        let (mut tx, rx) = mpsc::channel(Self::BUFFERED_CHUNKS);
+        let repo = self.repo.clone();
+
+        tokio::spawn(async move {
+            let encoder = Encoder::new(format);
+            let mut header = vec!["id".to_string()];
+            header.extend(def.attributes.iter().map(|a| a.name.clone()));
+            header.extend(link_defs.iter().map(|(_, name)| name.clone()));
+            if tx.send(encoder.header(&header)).await.is_err() {
+                return;
+            }
+            let mut rows = repo.stream_entities(&def.id, with_links);
+            while let Some(row) = rows.next().await {
+                let chunk = row
+                    .map_err(|e| {
+                        log::error!("Failed to read the entities to export. Cause: '{}'.", e);
+                        AppError::from(e)
+                    })
+                    .and_then(|ent| encoder.entity(&def, &link_defs, ent));
+                let failed = chunk.is_err();
+                // An error of sending means that the consumer is gone, thus there is no need to continue.
+                if tx.send(chunk).await.is_err() || failed {
+                    return;
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+
+    /// Export the (outgoing) links of the entities of the provided definition, as an edge list.
+    pub fn export_links(&self, def_id: &Id, format: ExportFormat) -> ExportChunks {
+        //
+        let (mut tx, rx) = mpsc::channel(Self::BUFFERED_CHUNKS);
+        let repo = self.repo.clone();
+        let def_id = def_id.clone();
+
+        tokio::spawn(async move {
+            let encoder = Encoder::new(format);
+            let header = ["id", "def_id", "source_entity_id", "target_entity_id"].map(String::from);
+            if tx.send(encoder.header(&header)).await.is_err() {
+                return;
+            }
+            let mut rows = repo.stream_links(&def_id);
+            while let Some(row) = rows.next().await {
+                let chunk = row
+                    .map_err(|e| {
+                        log::error!("Failed to read the entity links to export. Cause: '{}'.", e);
+                        AppError::from(e)
+                    })
+                    .and_then(|link| encoder.link(link));
+                let failed = chunk.is_err();
+                if tx.send(chunk).await.is_err() || failed {
+                    return;
+                }
+            }
+        });
+
+        rx
+    }
+}
+
+/// It encodes the exported rows in the requested format, one chunk per row.
+struct Encoder {
+    format: ExportFormat,
+}
+
+impl Encoder {
+    //
+    fn new(format: ExportFormat) -> Self {
+        Self { format }
+    }
+
+    /// The header is only relevant in the CSV format.
+    fn header(&self, header: &[String]) -> AppResult<Vec<u8>> {
+        match self.format {
+            ExportFormat::Csv => self.csv_record(header),
+            ExportFormat::Jsonl => Ok(vec![]),
+        }
+    }
+
+    fn entity(&self, def: &EntityDef, link_defs: &[(Id, String)], ent: ExportedEntity) -> AppResult<Vec<u8>> {
+        //
+        let attr_value = |attr_def_id: &Id| ent.attributes.get(attr_def_id.as_str()).cloned().unwrap_or(Value::Null);
+        let link_targets = |link_def_id: &Id| ent.links.get(link_def_id.as_str()).cloned().unwrap_or(json!([]));
+        match self.format {
+            ExportFormat::Csv => {
+                let mut record = vec![ent.id.to_string()];
+                record.extend(def.attributes.iter().map(|a| csv_value(attr_value(&a.id))));
+                record.extend(link_defs.iter().map(|(id, _)| csv_value(link_targets(id))));
+                self.csv_record(&record)
+            }
+            ExportFormat::Jsonl => {
+                let attributes: Map<String, Value> = def.attributes.iter().map(|a| (a.name.clone(), attr_value(&a.id))).collect();
+                let mut obj = json!({ "id": ent.id, "attributes": attributes });
+                if !link_defs.is_empty() {
+                    let links: Map<String, Value> = link_defs.iter().map(|(id, name)| (name.clone(), link_targets(id))).collect();
+                    obj["links"] = Value::Object(links);
+                }
+                json_line(&obj)
+            }
+        }
+    }
+
+    fn link(&self, link: ExportedEntityLink) -> AppResult<Vec<u8>> {
+        match self.format {
+            ExportFormat::Csv => self.csv_record(&[
+                link.id.to_string(),
+                link.def_id.to_string(),
+                link.source_entity_id.to_string(),
+                link.target_entity_id.to_string(),
+            ]),
+            ExportFormat::Jsonl => json_line(&json!({
+                "id": link.id,
+                "def_id": link.def_id,
+                "source_entity_id": link.source_entity_id,
+                "target_entity_id": link.target_entity_id,
+            })),
+        }
+    }
+
+    fn csv_record(&self, record: &[String]) -> AppResult<Vec<u8>> {
+        let mut writer = csv::WriterBuilder::new().has_headers(false).from_writer(vec![]);
+        writer.write_record(record).map_err(|e| AppError::Err(e.to_string()))?;
+        writer.into_inner().map_err(|e| AppError::Err(e.to_string()))
+    }
+}
+
+/// The representation of a value in a CSV cell. The multiple values (like links' targets) are separated by `;`.
+fn csv_value(value: Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s,
+        Value::Array(items) => items.into_iter().map(csv_value).collect::<Vec<String>>().join(";"),
+        other => other.to_string(),
+    }
+}
+
+fn json_line(value: &Value) -> AppResult<Vec<u8>> {
+    let mut line = serde_json::to_vec(value).map_err(|e| AppError::Err(e.to_string()))?;
+    line.push(b'\n');
+    Ok(line)
+}
@@ -0,0 +1,288 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    domain::model::{
+        AttributeValueType, BooleanAttribute, CsvColumnMapping, CsvImportMode, CsvImportReport, CsvRowError, Entity, EntityDef, Id,
+        IntegerAttribute, SmallintAttribute, TextAttribute,
+    },
+    server::{AppError, AppResult, EntityDefRepo, EntityRepo},
+};
+
+/// The management of importing entities in bulk, from CSV content.
+pub struct EntityImportMgmt {
+    ent_def_repo: Arc<EntityDefRepo>,
+    ent_repo: Arc<EntityRepo>,
+}
+
+impl EntityImportMgmt {
+    //
+    /// The maximum length of the entities' listing value, as stored.
+    const MAX_LISTING_VALUE_LEN: usize = 64;
+
+    pub fn new(ent_def_repo: Arc<EntityDefRepo>, ent_repo: Arc<EntityRepo>) -> Self {
+        Self { ent_def_repo, ent_repo }
+    }
+
+    /// Parse and validate the CSV content, without importing anything.<br/>
+    /// If no mapping is provided, the columns are mapped to the attributes with the same name (ignoring the case).
+    pub async fn preview(&self, def_id: &Id, content: &str, mapping: Vec<CsvColumnMapping>) -> AppResult<CsvImportReport> {
+        let ent_def = self.get_ent_def(def_id).await?;
+        Self::parse(&ent_def, content, mapping).map(|(report, _)| report)
+    }
+
+    /// Import the entities from the CSV content.<br/>
+    /// In `AllOrNothing` mode nothing is imported if any row has errors, while in `SkipBadRows` mode only the valid rows are imported.
+    /// Either way, the valid rows are inserted in a single transaction.
+    pub async fn import(
+        &self,
+        def_id: &Id,
+        content: &str,
+        mapping: Vec<CsvColumnMapping>,
+        mode: CsvImportMode,
+    ) -> AppResult<CsvImportReport> {
+        //
+        let ent_def = self.get_ent_def(def_id).await?;
+        let (mut report, mut ents) = Self::parse(&ent_def, content, mapping)?;
+        if ents.is_empty() || (mode == CsvImportMode::AllOrNothing && !report.errors.is_empty()) {
+            return Ok(report);
+        }
+        for ent in ents.iter_mut() {
+            ent.id = Id::new();
+        }
+        self.ent_repo.add_batch(&ents).await?;
+        report.imported = ents.len();
+        log::info!("Imported {} entities of definition id:'{}' from CSV.", report.imported, def_id);
+        Ok(report)
+    }
+
+    async fn get_ent_def(&self, def_id: &Id) -> AppResult<EntityDef> {
+        self.ent_def_repo
+            .get(def_id)
+            .await
+            .ok_or(AppError::Err(format!("Unknown entity definition id:'{}'.", def_id)))
+    }
+
+    /// Parse the CSV content into entities of the provided definition, collecting the errors of each row.
+    fn parse(ent_def: &EntityDef, content: &str, mapping: Vec<CsvColumnMapping>) -> AppResult<(CsvImportReport, Vec<Entity>)> {
+        //
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(content.as_bytes());
+        let headers: Vec<String> = reader
+            .headers()
+            .map_err(|e| AppError::Err(format!("Invalid CSV header: {}", e)))?
+            .iter()
+            .map(|h| h.to_string())
+            .collect();
+
+        let mapping = match mapping.is_empty() {
+            true => Self::suggest_mapping(ent_def, &headers),
+            false => mapping,
+        };
+        // The column index of each mapped attribute definition.
+        let mut columns: HashMap<&Id, usize> = HashMap::new();
+        for m in mapping.iter() {
+            let Some(attr_def_id) = &m.attr_def_id else {
+                continue;
+            };
+            if !ent_def.attributes.iter().any(|a| &a.id == attr_def_id) {
+                return Err(AppError::Err(format!(
+                    "Column '{}' is mapped to an attribute that is not part of '{}' entity definition.",
+                    m.column, ent_def.name
+                )));
+            }
+            let idx = headers
+                .iter()
+                .position(|h| h == &m.column)
+                .ok_or(AppError::Err(format!("Column '{}' does not exist in the CSV content.", m.column)))?;
+            if columns.insert(attr_def_id, idx).is_some() {
+                return Err(AppError::Err(format!(
+                    "More than one column is mapped to the same attribute (see column '{}').",
+                    m.column
+                )));
+            }
+        }
+
+        let mut report = CsvImportReport {
+            headers: headers.clone(),
+            mapping: mapping.clone(),
+            ..Default::default()
+        };
+        let mut ents = Vec::new();
+
+        for record in reader.records() {
+            report.total_rows += 1;
+            let record = match record {
+                Ok(record) => record,
+                Err(e) => {
+                    report.errors.push(CsvRowError {
+                        line: e.position().map(|p| p.line()).unwrap_or_default(),
+                        column: None,
+                        message: e.to_string(),
+                    });
+                    continue;
+                }
+            };
+            let line = record.position().map(|p| p.line()).unwrap_or_default();
+            let mut ent = Entity::new(
+                ent_def.id.clone(),
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                ent_def.listing_attr_def_id.clone(),
+                String::new(),
+                String::new(),
+            );
+            let mut preview = Vec::new();
+            let mut row_errors = Vec::new();
+
+            for attr_def in ent_def.attributes.iter() {
+                let column = columns.get(&attr_def.id).map(|idx| headers[*idx].clone());
+                let raw = columns.get(&attr_def.id).and_then(|idx| record.get(*idx)).unwrap_or_default();
+                let value = match raw.is_empty() {
+                    true => attr_def.default_value.trim(),
+                    false => raw,
+                };
+                if value.is_empty() {
+                    if attr_def.is_required {
+                        row_errors.push(CsvRowError {
+                            line,
+                            column,
+                            message: format!("Missing value of the required attribute '{}'.", attr_def.name),
+                        });
+                        continue;
+                    }
+                    // An optional attribute with no value is still kept when it is a text one.
+                    if attr_def.value_type != AttributeValueType::Text {
+                        continue;
+                    }
+                }
+                if attr_def.id == ent_def.listing_attr_def_id && value.chars().count() > Self::MAX_LISTING_VALUE_LEN {
+                    row_errors.push(CsvRowError {
+                        line,
+                        column,
+                        message: format!(
+                            "The value of attribute '{}' (the listing one) can have at most {} characters.",
+                            attr_def.name,
+                            Self::MAX_LISTING_VALUE_LEN
+                        ),
+                    });
+                    continue;
+                }
+                let invalid = |column: Option<String>| CsvRowError {
+                    line,
+                    column,
+                    message: format!(
+                        "Invalid {} value '{}' of attribute '{}'.",
+                        attr_def.value_type.label(),
+                        value,
+                        attr_def.name
+                    ),
+                };
+                match attr_def.value_type {
+                    AttributeValueType::Text => ent.text_attributes.push(TextAttribute::new(
+                        Id::default(),
+                        attr_def.name.clone(),
+                        value.to_string(),
+                        attr_def.id.clone(),
+                        Id::default(),
+                    )),
+                    AttributeValueType::SmallInteger => match value.parse::<i16>() {
+                        Ok(v) => ent.smallint_attributes.push(SmallintAttribute::new(
+                            Id::default(),
+                            attr_def.name.clone(),
+                            v,
+                            attr_def.id.clone(),
+                            Id::default(),
+                        )),
+                        Err(_) => {
+                            row_errors.push(invalid(column));
+                            continue;
+                        }
+                    },
+                    AttributeValueType::Integer => match value.parse::<i32>() {
+                        Ok(v) => ent.int_attributes.push(IntegerAttribute::new(
+                            Id::default(),
+                            attr_def.name.clone(),
+                            v,
+                            attr_def.id.clone(),
+                            Id::default(),
+                        )),
+                        Err(_) => {
+                            row_errors.push(invalid(column));
+                            continue;
+                        }
+                    },
+                    AttributeValueType::Boolean => match parse_bool(value) {
+                        Some(v) => ent.boolean_attributes.push(BooleanAttribute::new(
+                            Id::default(),
+                            attr_def.name.clone(),
+                            v,
+                            attr_def.id.clone(),
+                            Id::default(),
+                        )),
+                        None => {
+                            row_errors.push(invalid(column));
+                            continue;
+                        }
+                    },
+                    _ => {
+                        row_errors.push(CsvRowError {
+                            line,
+                            column,
+                            message: format!(
+                                "Attribute '{}' is of type {}, which is not yet supported for entities.",
+                                attr_def.name,
+                                attr_def.value_type.label()
+                            ),
+                        });
+                        continue;
+                    }
+                }
+                if attr_def.id == ent_def.listing_attr_def_id {
+                    ent.listing_attr_name = attr_def.name.clone();
+                    ent.listing_attr_value = value.to_string();
+                }
+                preview.push((attr_def.name.clone(), value.to_string()));
+            }
+
+            if row_errors.is_empty() {
+                report.valid_rows += 1;
+                if report.preview.len() < CsvImportReport::PREVIEW_ROWS {
+                    report.preview.push(preview);
+                }
+                ents.push(ent);
+            } else {
+                report.errors.append(&mut row_errors);
+            }
+        }
+
+        Ok((report, ents))
+    }
+
+    /// Map each column to the attribute (of the entity definition) with the same name, ignoring the case.
+    fn suggest_mapping(ent_def: &EntityDef, headers: &[String]) -> Vec<CsvColumnMapping> {
+        headers
+            .iter()
+            .map(|h| CsvColumnMapping {
+                column: h.clone(),
+                attr_def_id: ent_def
+                    .attributes
+                    .iter()
+                    .find(|a| a.name.trim().eq_ignore_ascii_case(h.trim()))
+                    .map(|a| a.id.clone()),
+            })
+            .collect()
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "y" | "1" => Some(true),
+        "false" | "no" | "n" | "0" => Some(false),
+        _ => None,
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    domain::model::{EntityLinkDef, Id},
+    domain::model::{EntityLinkDef, Id, Page, Pagination},
     server::{AppResult, EntityLinkDefRepo},
 };
 use std::sync::Arc;
@@ -14,8 +14,8 @@ impl EntityLinkDefMgmt {
         Self { ent_link_def_repo }
     }
 
-    pub async fn list(&self) -> AppResult<Vec<EntityLinkDef>> {
-        self.ent_link_def_repo.list().await
+    pub async fn list(&self, pagination: Option<&Pagination>) -> AppResult<Page<EntityLinkDef>> {
+        self.ent_link_def_repo.list(pagination).await
     }
 
     pub async fn add(&self, mut item: EntityLinkDef) -> AppResult<Id> {
@@ -1,6 +1,6 @@
 use crate::{
-    domain::model::{EntityLink, Id},
-    server::{AppResult, EntityLinkRepo},
+    domain::model::{Cursor, CursorPage, EntityLink, Id, Page, Pagination},
+    server::{AppError, AppResult, EntityLinkRepo},
 };
 use std::sync::Arc;
 
@@ -14,12 +14,55 @@ impl EntityLinkMgmt {
         Self { repo }
     }
 
-    pub async fn list(&self) -> AppResult<Vec<EntityLink>> {
-        self.repo.list(None).await
+    pub async fn list(&self, pagination: Option<&Pagination>) -> AppResult<Page<EntityLink>> {
+        self.repo.list(pagination).await
     }
 
-    pub async fn list_by_def_id(&self, def_id: &Id) -> AppResult<Vec<EntityLink>> {
-        self.repo.list_by_def_id(def_id).await
+    /// List the entity links using keyset pagination, starting after the provided (encoded) cursor.
+    pub async fn list_after(&self, def_id: Option<&Id>, after: Option<&str>, limit: Option<i64>) -> AppResult<CursorPage<EntityLink>> {
+        let cursor = match after {
+            Some(encoded) => Some(Cursor::decode(encoded).ok_or(AppError::Err("Invalid cursor.".into()))?),
+            None => None,
+        };
+        let limit = limit
+            .unwrap_or(Pagination::DEFAULT_LIMIT as i64)
+            .clamp(1, Pagination::MAX_LIMIT as i64);
+        self.repo.list_after(def_id, cursor.as_ref(), limit).await
+    }
+
+    pub async fn list_by_def_id(&self, def_id: &Id, pagination: Option<&Pagination>) -> AppResult<Page<EntityLink>> {
+        self.repo.list_by_def_id(def_id, pagination).await
+    }
+
+    pub async fn list_by_entity_ids(&self, ent_ids: &[Id], def_ids: &[Id]) -> AppResult<Vec<EntityLink>> {
+        self.repo.list_by_entity_ids(ent_ids, def_ids).await
+    }
+
+    pub async fn list_by_entity_id(&self, ent_id: &Id) -> AppResult<Vec<EntityLink>> {
+        self.repo.list_by_entity_id(ent_id).await
+    }
+
+    /// List the links of an entity (the outgoing ones, the incoming ones, or both), optionally only the ones with `def_id`,
+    /// using keyset pagination, starting after the provided (encoded) cursor.
+    pub async fn list_by_entity_id_after(
+        &self,
+        ent_id: &Id,
+        outgoing: bool,
+        incoming: bool,
+        def_id: Option<&Id>,
+        after: Option<&str>,
+        limit: Option<i64>,
+    ) -> AppResult<CursorPage<EntityLink>> {
+        let cursor = match after {
+            Some(encoded) => Some(Cursor::decode(encoded).ok_or(AppError::Err("Invalid cursor.".into()))?),
+            None => None,
+        };
+        let limit = limit
+            .unwrap_or(Pagination::DEFAULT_LIMIT as i64)
+            .clamp(1, Pagination::MAX_LIMIT as i64);
+        self.repo
+            .list_by_entity_id_after(ent_id, outgoing, incoming, def_id, cursor.as_ref(), limit)
+            .await
     }
 
     pub async fn add(&self, mut ent_link: EntityLink) -> AppResult<Id> {
@@ -1,6 +1,6 @@
 use crate::{
-    domain::model::{Entity, Id},
-    server::{AppResult, EntityRepo},
+    domain::model::{AttributeFilter, Cursor, CursorPage, Entity, EntityGraphFilter, GraphExportAttribute, Id, Page, Pagination},
+    server::{AppError, AppResult, EntityRepo},
     ui::pages::Name,
 };
 use std::sync::Arc;
@@ -15,12 +15,50 @@ impl EntityMgmt {
         Self { repo }
     }
 
-    pub async fn list(&self) -> AppResult<Vec<Entity>> {
-        self.repo.list(None).await
+    pub async fn list(&self, pagination: Option<&Pagination>) -> AppResult<Page<Entity>> {
+        self.repo.list(pagination).await
     }
 
-    pub async fn list_by_def_id(&self, def_id: &Id) -> AppResult<Vec<Entity>> {
-        self.repo.list_by_def_id(def_id).await
+    /// List the entities using keyset pagination, starting after the provided (encoded) cursor.
+    pub async fn list_after(&self, def_id: Option<&Id>, after: Option<&str>, limit: Option<i64>) -> AppResult<CursorPage<Entity>> {
+        let cursor = match after {
+            Some(encoded) => Some(Cursor::decode(encoded).ok_or(AppError::Err("Invalid cursor.".into()))?),
+            None => None,
+        };
+        let limit = limit
+            .unwrap_or(Pagination::DEFAULT_LIMIT as i64)
+            .clamp(1, Pagination::MAX_LIMIT as i64);
+        self.repo.list_after(def_id, cursor.as_ref(), limit).await
+    }
+
+    pub async fn list_by_def_id(&self, def_id: &Id, pagination: Option<&Pagination>) -> AppResult<Page<Entity>> {
+        self.repo.list_by_def_id(def_id, pagination).await
+    }
+
+    pub async fn list_by_def_id_and_filter(
+        &self,
+        def_id: &Id,
+        filter: &AttributeFilter,
+        pagination: Option<&Pagination>,
+    ) -> AppResult<Page<Entity>> {
+        self.repo.list_by_def_id_and_filter(def_id, filter, pagination).await
+    }
+
+    pub async fn list_attributes_values(&self, ent_ids: &[Id], attr_def_ids: &[Id]) -> AppResult<Vec<(Id, Id, String)>> {
+        self.repo.list_attributes_values(ent_ids, attr_def_ids).await
+    }
+
+    /// Get all the attributes (as name, type and text value) of the provided entities or entity links.
+    pub async fn list_attributes_by_owner_ids(&self, owner_ids: &[Id]) -> AppResult<Vec<(Id, GraphExportAttribute)>> {
+        self.repo.list_attributes_by_owner_ids(owner_ids).await
+    }
+
+    pub async fn list_by_graph_filter(&self, filter: &EntityGraphFilter) -> AppResult<Vec<Entity>> {
+        self.repo.list_by_graph_filter(filter).await
+    }
+
+    pub async fn list_by_ids(&self, ids: &[Id]) -> AppResult<Vec<Entity>> {
+        self.repo.list_by_ids(ids).await
     }
 
     pub async fn list_refs_by_def_id(&self, def_id: &Id) -> AppResult<Vec<(Id, Name)>> {
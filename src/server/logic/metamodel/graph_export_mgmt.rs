@@ -0,0 +1,75 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    domain::model::{Entity, EntityGraphFilter, GraphExport, GraphExportAttribute, GraphExportEdge, GraphExportNode, Id},
+    server::{AppResult, EntityLinkRepo, EntityRepo},
+};
+
+/// The management of exporting the instance graph, that is the entities (as nodes) and the links between them (as edges).
+pub struct GraphExportMgmt {
+    ent_repo: Arc<EntityRepo>,
+    ent_link_repo: Arc<EntityLinkRepo>,
+}
+
+impl GraphExportMgmt {
+    //
+    /// The maximum depth of a traversal that starts from an entity.
+    pub const MAX_DEPTH: i32 = 10;
+
+    pub fn new(ent_repo: Arc<EntityRepo>, ent_link_repo: Arc<EntityLinkRepo>) -> Self {
+        Self { ent_repo, ent_link_repo }
+    }
+
+    /// Get the graph to export, including the attributes of its nodes and edges.<br/>
+    /// If a starting entity is provided, the graph contains the entities reachable from it through at most `depth` links
+    /// (further restricted by the filter's entity definitions, if any). Otherwise, the graph contains the entities that match the filter.
+    /// Either way, the edges are the links (with the filter's link definitions, if any) between the included entities.
+    pub async fn export(&self, filter: &EntityGraphFilter, start_ent_id: Option<&Id>, depth: i32) -> AppResult<GraphExport> {
+        //
+        let entities = match start_ent_id {
+            Some(start_ent_id) => {
+                let depth = depth.clamp(0, Self::MAX_DEPTH);
+                let ids = self
+                    .ent_link_repo
+                    .list_reachable_entity_ids(start_ent_id, depth, &filter.ent_link_def_ids)
+                    .await?;
+                let mut entities = self.ent_repo.list_by_ids(&ids).await?;
+                if !filter.ent_def_ids.is_empty() {
+                    entities.retain(|ent| &ent.id == start_ent_id || filter.ent_def_ids.contains(&ent.def_id));
+                }
+                entities
+            }
+            None => self.ent_repo.list_by_graph_filter(filter).await?,
+        };
+        let ent_ids: Vec<Id> = entities.iter().map(|ent| ent.id.clone()).collect();
+        let links = self.ent_link_repo.list_by_entity_ids(&ent_ids, &filter.ent_link_def_ids).await?;
+
+        let mut owner_ids = ent_ids;
+        owner_ids.extend(links.iter().map(|link| link.id.clone()));
+        let mut attributes: HashMap<Id, Vec<GraphExportAttribute>> = HashMap::new();
+        for (owner_id, attr) in self.ent_repo.list_attributes_by_owner_ids(&owner_ids).await? {
+            attributes.entry(owner_id).or_default().push(attr);
+        }
+
+        let nodes = entities
+            .into_iter()
+            .map(|ent: Entity| GraphExportNode {
+                attributes: attributes.remove(&ent.id).unwrap_or_default(),
+                id: ent.id,
+                kind: ent.kind,
+                label: ent.listing_attr_value,
+            })
+            .collect();
+        let edges = links
+            .into_iter()
+            .map(|link| GraphExportEdge {
+                attributes: attributes.remove(&link.id).unwrap_or_default(),
+                id: link.id,
+                kind: link.kind,
+                source: link.source_entity_id,
+                target: link.target_entity_id,
+            })
+            .collect();
+        Ok(GraphExport { nodes, edges })
+    }
+}
@@ -1,12 +1,24 @@
 mod attr_def_mgmt;
 pub use attr_def_mgmt::*;
 
+mod bundle_mgmt;
+pub use bundle_mgmt::*;
+
+mod rdf_export_mgmt;
+pub use rdf_export_mgmt::*;
+
 mod tag_mgmt;
 pub use tag_mgmt::*;
 
 mod ent_def_mgmt;
 pub use ent_def_mgmt::*;
 
+mod ent_export_mgmt;
+pub use ent_export_mgmt::*;
+
+mod ent_import_mgmt;
+pub use ent_import_mgmt::*;
+
 mod ent_mgmt;
 pub use ent_mgmt::*;
 
@@ -16,5 +28,5 @@ pub use ent_link_def_mgmt::*;
 mod ent_link_mgmt;
 pub use ent_link_mgmt::*;
 
-mod pagination;
-pub use pagination::*;
+mod graph_export_mgmt;
+pub use graph_export_mgmt::*;
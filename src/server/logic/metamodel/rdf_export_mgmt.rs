@@ -0,0 +1,186 @@
+use std::{collections::HashMap, sync::Arc};
+
+use futures::{channel::mpsc, SinkExt, StreamExt};
+use serde_json::Value;
+
+use crate::{
+    domain::model::{AttributeDef, Id, MetamodelBundle, RdfFormat, RdfObject, RdfResource, RdfVocabulary},
+    server::{AppError, AppResult, EntityExportRepo, ExportChunks, ExportedEntity, MetamodelBundleRepo},
+};
+
+/// The management of exporting the knowledge base (the metamodel and its instances) as RDF.<br/>
+/// The content is streamed, the entities being read (definition by definition) as the content is consumed.
+/// Note that the attributes of the entity links are not exported, since RDF statements cannot have attributes.
+pub struct RdfExportMgmt {
+    bundle_repo: Arc<MetamodelBundleRepo>,
+    export_repo: Arc<EntityExportRepo>,
+    /// The base IRI to use when none is provided on export.
+    default_base_iri: String,
+}
+
+impl RdfExportMgmt {
+    //
+    /// The number of produced chunks that can wait for being consumed, before pausing the reading from the database.
+    const BUFFERED_CHUNKS: usize = 64;
+
+    pub fn new(bundle_repo: Arc<MetamodelBundleRepo>, export_repo: Arc<EntityExportRepo>, default_base_iri: String) -> Self {
+        Self {
+            bundle_repo,
+            export_repo,
+            default_base_iri,
+        }
+    }
+
+    /// Export the knowledge base in the provided format.
+    /// If `base_iri` is not provided, the configured one is used.
+    pub async fn export(&self, format: RdfFormat, base_iri: Option<&str>) -> AppResult<ExportChunks> {
+        //
+        let base_iri = base_iri.unwrap_or(&self.default_base_iri);
+        let vocab = RdfVocabulary::new(base_iri).ok_or(AppError::Err(format!("Invalid base IRI '{}'.", base_iri)))?;
+        let metamodel = self.bundle_repo.export().await?;
+        let (mut tx, rx) = mpsc::channel(Self::BUFFERED_CHUNKS);
+        let repo = self.export_repo.clone();
+
+        tokio::spawn(async move {
+            let mut writer = RdfWriter::new(format);
+            if tx.send(Ok(writer.start())).await.is_err() {
+                return;
+            }
+            for resource in metamodel_resources(&vocab, &metamodel) {
+                if tx.send(Ok(writer.resource(&resource))).await.is_err() {
+                    return;
+                }
+            }
+            let attr_defs: HashMap<&Id, &AttributeDef> = metamodel.attribute_defs.iter().map(|a| (&a.id, a)).collect();
+            for ent_def in metamodel.entity_defs.iter() {
+                let mut rows = repo.stream_entities(&ent_def.id, true);
+                while let Some(row) = rows.next().await {
+                    let chunk = match row {
+                        Ok(ent) => Ok(writer.resource(&entity_resource(&vocab, &ent_def.id, &attr_defs, ent))),
+                        Err(e) => {
+                            log::error!("Failed to read the entities to export as RDF. Cause: '{}'.", e);
+                            Err(AppError::from(e))
+                        }
+                    };
+                    let failed = chunk.is_err();
+                    if tx.send(chunk).await.is_err() || failed {
+                        return;
+                    }
+                }
+            }
+            let _ = tx.send(Ok(writer.end())).await;
+        });
+
+        Ok(rx)
+    }
+}
+
+/// The classes, datatype properties and object properties of the metamodel.
+fn metamodel_resources(vocab: &RdfVocabulary, metamodel: &MetamodelBundle) -> Vec<RdfResource> {
+    //
+    let mut resources = Vec::new();
+    for ent_def in metamodel.entity_defs.iter() {
+        let mut class = RdfResource::new(vocab.class_iri(&ent_def.id), "owl:Class");
+        add_label_comment(&mut class, &ent_def.name, &ent_def.description);
+        resources.push(class);
+    }
+    for attr_def in metamodel.attribute_defs.iter() {
+        let mut property = RdfResource::new(vocab.datatype_property_iri(&attr_def.id), "owl:DatatypeProperty");
+        add_label_comment(&mut property, &attr_def.name, &attr_def.description);
+        for ent_def in metamodel
+            .entity_defs
+            .iter()
+            .filter(|ed| ed.attribute_def_ids.contains(&attr_def.id))
+        {
+            property.add("rdfs:domain", RdfObject::Iri(vocab.class_iri(&ent_def.id)));
+        }
+        let range = format!("xsd:{}", RdfVocabulary::xsd_type(&attr_def.value_type));
+        property.add("rdfs:range", RdfObject::Iri(range));
+        resources.push(property);
+    }
+    for link_def in metamodel.entity_link_defs.iter() {
+        let mut property = RdfResource::new(vocab.object_property_iri(&link_def.id), "owl:ObjectProperty");
+        add_label_comment(&mut property, &link_def.name, &link_def.description);
+        property.add("rdfs:domain", RdfObject::Iri(vocab.class_iri(&link_def.source_entity_def_id)));
+        property.add("rdfs:range", RdfObject::Iri(vocab.class_iri(&link_def.target_entity_def_id)));
+        resources.push(property);
+    }
+    resources
+}
+
+/// An entity as an individual, with its attributes and its outgoing links.
+fn entity_resource(vocab: &RdfVocabulary, ent_def_id: &Id, attr_defs: &HashMap<&Id, &AttributeDef>, ent: ExportedEntity) -> RdfResource {
+    //
+    let mut individual = RdfResource::new(vocab.individual_iri(&ent.id), "owl:NamedIndividual");
+    individual.types.push(vocab.class_iri(ent_def_id));
+    individual.add("rdfs:label", RdfObject::Literal(ent.label, "string"));
+    for (attr_def_id, value) in ent.attributes {
+        let attr_def_id = Id::new_from(attr_def_id);
+        let Some(attr_def) = attr_defs.get(&attr_def_id) else {
+            continue;
+        };
+        let value = match value {
+            Value::Null => continue,
+            Value::String(s) => s,
+            other => other.to_string(),
+        };
+        let xsd_type = RdfVocabulary::xsd_type(&attr_def.value_type);
+        individual.add(&vocab.datatype_property_iri(&attr_def_id), RdfObject::Literal(value, xsd_type));
+    }
+    for (link_def_id, targets) in ent.links {
+        let predicate = vocab.object_property_iri(&Id::new_from(link_def_id));
+        for target in targets.as_array().into_iter().flatten().filter_map(|t| t.as_str()) {
+            individual.add(&predicate, RdfObject::Iri(vocab.individual_iri(&Id::new_from(target.to_string()))));
+        }
+    }
+    individual
+}
+
+fn add_label_comment(resource: &mut RdfResource, name: &str, description: &Option<String>) {
+    resource.add("rdfs:label", RdfObject::Literal(name.to_string(), "string"));
+    if let Some(description) = description.as_ref().filter(|d| !d.is_empty()) {
+        resource.add("rdfs:comment", RdfObject::Literal(description.clone(), "string"));
+    }
+}
+
+/// It writes the resources in the requested format, one chunk per resource.<br/>
+/// A JSON-LD document is written as a `@graph` array, so its start and end are written separately.
+struct RdfWriter {
+    format: RdfFormat,
+    written: usize,
+}
+
+impl RdfWriter {
+    //
+    fn new(format: RdfFormat) -> Self {
+        Self { format, written: 0 }
+    }
+
+    fn start(&self) -> Vec<u8> {
+        match self.format {
+            RdfFormat::Turtle => RdfVocabulary::turtle_prefixes().into_bytes(),
+            RdfFormat::JsonLd => {
+                let context = RdfVocabulary::json_ld_context().to_string();
+                format!("{{\"@context\":{},\"@graph\":[\n", context).into_bytes()
+            }
+        }
+    }
+
+    fn resource(&mut self, resource: &RdfResource) -> Vec<u8> {
+        self.written += 1;
+        match self.format {
+            RdfFormat::Turtle => resource.to_turtle().into_bytes(),
+            RdfFormat::JsonLd => {
+                let separator = if self.written > 1 { ",\n" } else { "" };
+                format!("{}{}", separator, resource.to_json_ld()).into_bytes()
+            }
+        }
+    }
+
+    fn end(&self) -> Vec<u8> {
+        match self.format {
+            RdfFormat::Turtle => vec![],
+            RdfFormat::JsonLd => b"\n]}\n".to_vec(),
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    domain::model::{Id, Tag},
+    domain::model::{Id, Page, Pagination, Tag},
     server::{AppResult, TagsRepo},
 };
 use std::sync::Arc;
@@ -20,9 +20,9 @@ impl TagMgmt {
         self.tag_repo.get(id).await
     }
 
-    pub async fn list(&self) -> AppResult<Vec<Tag>> {
+    pub async fn list(&self, pagination: Option<&Pagination>) -> AppResult<Page<Tag>> {
         //
-        self.tag_repo.list(None).await
+        self.tag_repo.list(pagination).await
     }
 
     pub async fn add(&self, name: String, description: Option<String>) -> AppResult<Id> {
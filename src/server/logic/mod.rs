@@ -1,6 +1,27 @@
+mod backup_mgmt;
+pub use backup_mgmt::*;
+
+mod config_mgmt;
+pub use config_mgmt::*;
+
+mod consistency_mgmt;
+pub use consistency_mgmt::*;
+
+mod health_check_mgmt;
+pub use health_check_mgmt::*;
+
 mod metamodel;
 pub use metamodel::*;
 
+mod registry_mgmt;
+pub use registry_mgmt::*;
+
+mod saved_view_mgmt;
+pub use saved_view_mgmt::*;
+
+mod search_mgmt;
+pub use search_mgmt::*;
+
 mod user_mgmt;
 
 #[cfg(feature = "server")]
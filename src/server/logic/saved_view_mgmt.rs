@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::model::{AttributeFilter, Entity, Id, Page, Pagination, SavedView},
+    server::{AppError, AppResult, EntityRepo, SavedViewsRepo},
+};
+
+/// The management of the saved views.<br/>
+/// A view can be seen by its owner and, if shared, by everyone. But it can be changed or removed by its owner only.
+pub struct SavedViewMgmt {
+    repo: Arc<SavedViewsRepo>,
+    ent_repo: Arc<EntityRepo>,
+}
+
+impl SavedViewMgmt {
+    //
+    pub fn new(repo: Arc<SavedViewsRepo>, ent_repo: Arc<EntityRepo>) -> Self {
+        Self { repo, ent_repo }
+    }
+
+    /// Get a view, if it is visible to the user.
+    pub async fn get(&self, id: &Id, user_id: &Id) -> AppResult<Option<SavedView>> {
+        //
+        let view = self.repo.get(id).await?;
+        Ok(view.filter(|view| view.shared || &view.owner_id == user_id))
+    }
+
+    /// List the views that are visible to the user. The owned ones are listed first.
+    pub async fn list(&self, user_id: &Id) -> AppResult<Vec<SavedView>> {
+        //
+        self.repo.list_visible_to(user_id).await
+    }
+
+    pub async fn add(&self, mut view: SavedView, user_id: &Id) -> AppResult<Id> {
+        //
+        Self::validate(&view)?;
+        view.id = Id::new();
+        view.owner_id = user_id.clone();
+        self.repo.add(&view).await?;
+        Ok(view.id)
+    }
+
+    pub async fn update(&self, view: SavedView, user_id: &Id) -> AppResult<()> {
+        //
+        Self::validate(&view)?;
+        self.get_owned(&view.id, user_id).await?;
+        self.repo.update(&view).await
+    }
+
+    pub async fn remove(&self, id: &Id, user_id: &Id) -> AppResult<()> {
+        //
+        self.get_owned(id, user_id).await?;
+        self.repo.remove(id).await
+    }
+
+    /// List the entities of a view, as per its definition and filter.<br/>
+    /// If the provided pagination has no sorting, the view's sorting is used.
+    pub async fn list_entities(&self, id: &Id, user_id: &Id, pagination: Option<Pagination>) -> AppResult<Page<Entity>> {
+        //
+        let view = self.get(id, user_id).await?.ok_or(AppError::Err("Saved view not found.".into()))?;
+        let pagination = match pagination {
+            Some(pagination) if pagination.sort_by.is_some() => pagination,
+            Some(pagination) => Pagination {
+                sort_by: view.sort_by.clone(),
+                sort_desc: Some(view.sort_desc),
+                ..pagination
+            },
+            None => view.pagination(),
+        };
+        match &view.def_id {
+            Some(def_id) => {
+                let filter = view.filter.clone().unwrap_or(AttributeFilter::and(vec![]));
+                self.ent_repo.list_by_def_id_and_filter(def_id, &filter, Some(&pagination)).await
+            }
+            None => self.ent_repo.list(Some(&pagination)).await,
+        }
+    }
+
+    async fn get_owned(&self, id: &Id, user_id: &Id) -> AppResult<SavedView> {
+        //
+        match self.repo.get(id).await? {
+            Some(view) if &view.owner_id == user_id => Ok(view),
+            Some(_) => Err(AppError::Unauthorized("only the owner can change a saved view".into())),
+            None => Err(AppError::Err("Saved view not found.".into())),
+        }
+    }
+
+    fn validate(view: &SavedView) -> AppResult<()> {
+        //
+        if view.name.trim().is_empty() {
+            return Err(AppError::Err("The name of the saved view is required.".into()));
+        }
+        if view.filter.is_some() && view.def_id.is_none() {
+            return Err(AppError::Err(
+                "A filter can be used only if the entity definition is provided.".into(),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::model::{Id, ItemType, SearchHit},
+    server::{AppResult, SearchRepo},
+};
+
+#[derive(Clone)]
+pub struct SearchMgmt {
+    search_repo: Arc<SearchRepo>,
+}
+
+impl SearchMgmt {
+    //
+    pub const DEFAULT_LIMIT: i64 = 20;
+    pub const MAX_LIMIT: i64 = 100;
+
+    pub fn new(search_repo: Arc<SearchRepo>) -> Self {
+        Self { search_repo }
+    }
+
+    /// Search the entities, entity links and definitions that contain all the words of the `query`,
+    /// optionally restricted to some item types (`kinds`) and definitions (`def_ids`).<br/>
+    /// Each word is matched as a prefix, so that the results show up while the user is typing.
+    pub async fn search(&self, query: &str, kinds: &[ItemType], def_ids: &[Id], limit: Option<i64>) -> AppResult<Vec<SearchHit>> {
+        //
+        let tsquery = Self::to_tsquery(query);
+        if tsquery.is_empty() {
+            return Ok(vec![]);
+        }
+        let limit = limit.unwrap_or(Self::DEFAULT_LIMIT).clamp(1, Self::MAX_LIMIT);
+        self.search_repo.search(&tsquery, kinds, def_ids, limit).await
+    }
+
+    /// Convert a free text query into a `tsquery` expression (such as `word1:* & word2:*`).<br/>
+    /// Anything but letters and digits is dropped, so that the user input cannot break the `tsquery` syntax.
+    fn to_tsquery(query: &str) -> String {
+        query
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| format!("{}:*", word.to_lowercase()))
+            .collect::<Vec<String>>()
+            .join(" & ")
+    }
+}
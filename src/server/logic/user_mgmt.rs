@@ -23,19 +23,40 @@ impl UserMgmt {
     }
 
     pub async fn register_admin_user(&self, email: &String, username: &String, pwd: String) -> AppResult<Id> {
+        //
+        let permissions = vec!["Admin::Read".to_string(), "Admin::Write".to_string()];
+        self.register_user_with_permissions(email, username, pwd, permissions).await
+    }
+
+    pub async fn register_user_with_permissions(
+        &self,
+        email: &String,
+        username: &String,
+        pwd: String,
+        permissions: Vec<String>,
+    ) -> AppResult<Id> {
         //
         let (pwd, salt) = Self::generate_password(pwd);
         self.user_repo
-            .save_with_permissions(
-                email,
-                username,
-                &pwd,
-                &salt,
-                vec!["Admin::Read".to_string(), "Admin::Write".to_string()],
-            )
+            .save_with_permissions(email, username, &pwd, &salt, permissions)
             .await
     }
 
+    /// Grant the permissions to the user with the provided email.
+    pub async fn grant_permissions(&self, email: &str, permissions: Vec<String>) -> AppResult<()> {
+        //
+        let user_id = self.get_id_by_email(email).await?;
+        self.user_repo.add_permissions(&user_id, &permissions).await
+    }
+
+    /// Set a new password for the user with the provided email, without checking the current one.
+    pub async fn reset_password(&self, email: &str, new_password: String) -> AppResult<()> {
+        //
+        let user_id = self.get_id_by_email(email).await?;
+        let (pwd, salt) = Self::generate_password(new_password);
+        self.user_repo.update_password_salt(&user_id, pwd, salt).await
+    }
+
     pub async fn authenticate_user(&self, email: String, pwd: String) -> AppResult<UserAccount> {
         //
         let user_entry = self.user_repo.get_by_email(&email, AppUseCase::UserLogin).await?;
@@ -62,6 +83,13 @@ impl UserMgmt {
         self.user_repo.update(ua).await
     }
 
+    async fn get_id_by_email(&self, email: &str) -> AppResult<Id> {
+        self.user_repo
+            .get_id_by_email(email)
+            .await?
+            .ok_or(AppError::Err(format!("Unknown user with email '{}'.", email)))
+    }
+
     fn generate_password(pwd: String) -> (String, String) {
         //
         let salt: String = std::iter::repeat_with(fastrand::alphanumeric).take(12).collect();
@@ -3,6 +3,12 @@ pub use app_err_uc::*;
 
 pub mod fns;
 
+#[cfg(feature = "server")]
+pub mod api;
+
+#[cfg(feature = "server")]
+pub mod graphql;
+
 #[cfg(feature = "server")]
 pub mod model;
 
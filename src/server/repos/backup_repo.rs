@@ -0,0 +1,208 @@
+use std::sync::Arc;
+
+use futures::TryStreamExt;
+use serde_json::{json, Value};
+use sqlx::{types::Json, PgConnection, PgPool};
+
+use crate::server::{AppError, AppResult, ConsistencyCheck, ConsistencyRepo, SearchRepo};
+
+/// A part of a dump, as provided (in this order) to its writer.
+pub enum DumpPart<'a> {
+    /// The schema version (that is the latest applied migration), and the number of rows of each table.
+    Start { schema_version: i64, tables: &'a [(&'static str, u64)] },
+    /// A chunk of a table's rows, each one as a JSON object (by column name) in its own line.
+    Rows { table: &'static str, content: &'a [u8] },
+}
+
+/// The repository for taking and restoring a snapshot of all the (domain) data.<br/>
+/// The rows are read and written as JSON objects, thus the same code works for all the tables,
+/// as long as the backup and the database have the same schema version.
+pub struct BackupRepo {
+    dbcp: Arc<PgPool>,
+}
+
+impl BackupRepo {
+    //
+    /// The tables that are backed up, in the order of their dependencies (a table refers only to the ones before it).<br/>
+    /// The search index is not included, since it is rebuilt on restore.
+    pub const TABLES: [&'static str; 22] = [
+        "user_accounts",
+        "user_permissions",
+        "tags",
+        "attribute_defs",
+        "entity_defs",
+        "entity_def_tags_xref",
+        "entity_defs_attribute_defs_xref",
+        "entities",
+        "entity_link_defs",
+        "entity_link_defs_attribute_defs_xref",
+        "entity_links",
+        "text_attributes",
+        "smallint_attributes",
+        "integer_attributes",
+        "bigint_attributes",
+        "real_attributes",
+        "boolean_attributes",
+        "date_attributes",
+        "timestamp_attributes",
+        "saved_views",
+        "config_sets",
+        "config_revisions",
+    ];
+
+    /// The number of rows inserted by one statement on restore.
+    const INSERT_BATCH_SIZE: usize = 1000;
+
+    /// The (approximate) size of a chunk of a table's rows on dump.
+    const DUMP_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+    pub fn new(dbcp: Arc<PgPool>) -> Self {
+        Self { dbcp }
+    }
+
+    /// Read all the tables from the same (consistent) snapshot. The users' secrets (password and salt) are left out.<br/>
+    /// The rows are streamed to the provided writer in chunks, after the schema version and the number of rows of each table.
+    pub async fn dump<F>(&self, mut write: F) -> AppResult<()>
+    where
+        F: FnMut(DumpPart<'_>) -> AppResult<()>,
+    {
+        //
+        let mut txn = self.dbcp.begin().await?;
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+            .execute(&mut *txn)
+            .await?;
+        let schema_version = Self::schema_version(&mut txn).await?;
+        let mut tables = Vec::with_capacity(Self::TABLES.len());
+        for name in Self::TABLES {
+            let rows: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", name))
+                .fetch_one(&mut *txn)
+                .await?;
+            tables.push((name, rows as u64));
+        }
+        write(DumpPart::Start {
+            schema_version,
+            tables: &tables,
+        })?;
+
+        for name in Self::TABLES {
+            let row = match name {
+                "user_accounts" => "to_jsonb(t) - 'password' - 'salt'",
+                _ => "to_jsonb(t)",
+            };
+            let query = format!("SELECT ({})::TEXT FROM {} t", row, name);
+            let mut content = Vec::with_capacity(Self::DUMP_CHUNK_SIZE);
+            let mut rows = sqlx::query_scalar::<_, String>(&query).fetch(&mut *txn);
+            while let Some(row) = rows.try_next().await? {
+                content.extend_from_slice(row.as_bytes());
+                content.push(b'\n');
+                if content.len() >= Self::DUMP_CHUNK_SIZE {
+                    write(DumpPart::Rows { table: name, content: &content })?;
+                    content.clear();
+                }
+            }
+            if !content.is_empty() {
+                write(DumpPart::Rows { table: name, content: &content })?;
+            }
+        }
+        txn.commit().await?;
+        Ok(())
+    }
+
+    /// Replace all the data with the rows pulled from `next_rows`, in a single transaction.<br/>
+    /// These are provided as (table name, rows) chunks, in the order of the tables, until `None` is returned.<br/>
+    /// The restored users keep the secrets of the existing ones with the same email, or else they get none
+    /// (thus their password needs to be reset). Before committing, the references that are not enforced by foreign keys
+    /// are checked and, if any of these checks fail, nothing is changed and the failed checks are returned.
+    pub async fn restore<F>(&self, schema_version: i64, next_rows: F) -> AppResult<Vec<ConsistencyCheck>>
+    where
+        F: FnMut() -> AppResult<Option<(String, Vec<Value>)>>,
+    {
+        //
+        let mut txn = self.dbcp.begin().await?;
+        let current_schema_version = Self::schema_version(&mut txn).await?;
+        if schema_version != current_schema_version {
+            txn.rollback().await?;
+            log::warn!(
+                "Not restoring the backup with schema version {}, since the database has {}.",
+                schema_version,
+                current_schema_version
+            );
+            return Err(AppError::Err(format!(
+                "The backup has the schema version {}, while the database has {}.",
+                schema_version, current_schema_version
+            )));
+        }
+        let failed = match Self::restore_in(&mut txn, next_rows).await {
+            Ok(failed) => failed,
+            Err(e) => {
+                txn.rollback().await?;
+                return Err(e);
+            }
+        };
+        if !failed.is_empty() {
+            txn.rollback().await?;
+            return Ok(failed);
+        }
+        txn.commit().await?;
+        Ok(vec![])
+    }
+
+    async fn restore_in<F>(conn: &mut PgConnection, mut next_rows: F) -> AppResult<Vec<ConsistencyCheck>>
+    where
+        F: FnMut() -> AppResult<Option<(String, Vec<Value>)>>,
+    {
+        //
+        sqlx::query(
+            "CREATE TEMPORARY TABLE restored_user_secrets ON COMMIT DROP AS
+             SELECT email, password, salt FROM user_accounts",
+        )
+        .execute(&mut *conn)
+        .await?;
+        sqlx::query("DELETE FROM search_documents").execute(&mut *conn).await?;
+        for name in Self::TABLES.iter().rev() {
+            sqlx::query(&format!("DELETE FROM {}", name)).execute(&mut *conn).await?;
+        }
+
+        while let Some((name, mut rows)) = next_rows()? {
+            if name == "user_accounts" {
+                for row in rows.iter_mut().filter_map(|row| row.as_object_mut()) {
+                    row.entry("password").or_insert(json!(""));
+                    row.entry("salt").or_insert(json!(""));
+                }
+            }
+            let query = format!("INSERT INTO {0} SELECT * FROM jsonb_populate_recordset(NULL::{0}, $1)", name);
+            for batch in rows.chunks(Self::INSERT_BATCH_SIZE) {
+                sqlx::query(&query).bind(Json(batch)).execute(&mut *conn).await.map_err(|e| {
+                    log::error!("Failed to restore the rows of '{}' table. Cause: '{}'.", name, e);
+                    AppError::Err(format!("Failed to restore the rows of '{}' table: {}", name, e))
+                })?;
+            }
+        }
+        sqlx::query(
+            "UPDATE user_accounts u SET password = s.password, salt = s.salt
+             FROM restored_user_secrets s WHERE s.email = u.email",
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        let failed: Vec<ConsistencyCheck> = ConsistencyRepo::check(&mut *conn, &ConsistencyRepo::REFERENTIAL_CHECKS)
+            .await?
+            .into_iter()
+            .filter(|check| check.count > 0)
+            .collect();
+        if failed.is_empty() {
+            SearchRepo::reindex_all(&mut *conn).await?;
+        }
+        Ok(failed)
+    }
+
+    async fn schema_version(conn: &mut PgConnection) -> AppResult<i64> {
+        sqlx::query_scalar("SELECT COALESCE(MAX(version), 0) FROM _sqlx_migrations WHERE success")
+            .fetch_one(conn)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to get the schema version. Cause: '{}'.", e);
+                AppError::Err("Failed to get the schema version of the database.".into())
+            })
+    }
+}
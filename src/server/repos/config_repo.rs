@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, types::Json, PgPool, Row};
+
+use crate::{
+    domain::model::{ConfigItem, ConfigKey, ConfigRevision, Id},
+    server::{AppError, AppResult},
+};
+
+/// The repository of the configuration sets and their revisions.<br/>
+/// The revisions are only added (and never changed), which is also enforced by the database.
+pub struct ConfigRepo {
+    dbcp: Arc<PgPool>,
+}
+
+impl ConfigRepo {
+    //
+    pub fn new(dbcp: Arc<PgPool>) -> Self {
+        Self { dbcp }
+    }
+
+    /// Get a revision of a configuration set, or its latest one if no revision is provided.
+    pub async fn get_revision(&self, key: &ConfigKey, revision: Option<i32>) -> AppResult<Option<ConfigRevision>> {
+        //
+        let item = sqlx::query(
+            "SELECT s.service, s.version, s.env, s.instance, r.revision, r.author, r.comment, r.created_at, r.items
+             FROM config_revisions r JOIN config_sets s ON s.id = r.config_set_id
+             WHERE s.service = $1 AND s.version = $2 AND s.env = $3 AND s.instance = $4 AND ($5::INT IS NULL OR r.revision = $5)
+             ORDER BY r.revision DESC LIMIT 1",
+        )
+        .bind(&key.service)
+        .bind(&key.version)
+        .bind(&key.env)
+        .bind(&key.instance)
+        .bind(revision)
+        .map(|row: PgRow| revision_from_row(&row))
+        .fetch_optional(self.dbcp.as_ref())
+        .await?;
+        Ok(item)
+    }
+
+    /// List the revisions of a configuration set, the latest first.
+    pub async fn list_revisions(&self, key: &ConfigKey) -> AppResult<Vec<ConfigRevision>> {
+        //
+        let items = sqlx::query(
+            "SELECT s.service, s.version, s.env, s.instance, r.revision, r.author, r.comment, r.created_at, r.items
+             FROM config_revisions r JOIN config_sets s ON s.id = r.config_set_id
+             WHERE s.service = $1 AND s.version = $2 AND s.env = $3 AND s.instance = $4
+             ORDER BY r.revision DESC",
+        )
+        .bind(&key.service)
+        .bind(&key.version)
+        .bind(&key.env)
+        .bind(&key.instance)
+        .map(|row: PgRow| revision_from_row(&row))
+        .fetch_all(self.dbcp.as_ref())
+        .await?;
+        Ok(items)
+    }
+
+    /// Get the latest revisions of all the layers that the configuration of a service version in an environment
+    /// (or of one of its instances) is resolved through, in no particular order.<br/>
+    /// Due to the layers' (enforced) shapes, these are the sets whose components are either empty or the same as the key's ones.
+    pub async fn list_layers_revisions(&self, key: &ConfigKey) -> AppResult<Vec<ConfigRevision>> {
+        //
+        let items = sqlx::query(
+            "SELECT DISTINCT ON (s.id) s.service, s.version, s.env, s.instance, r.revision, r.author, r.comment, r.created_at, r.items
+             FROM config_revisions r JOIN config_sets s ON s.id = r.config_set_id
+             WHERE s.service IN ('', $1) AND s.version IN ('', $2) AND s.env IN ('', $3) AND s.instance IN ('', $4)
+             ORDER BY s.id, r.revision DESC",
+        )
+        .bind(&key.service)
+        .bind(&key.version)
+        .bind(&key.env)
+        .bind(&key.instance)
+        .map(|row: PgRow| revision_from_row(&row))
+        .fetch_all(self.dbcp.as_ref())
+        .await?;
+        Ok(items)
+    }
+
+    /// Get the latest revisions of all the configuration sets of a service (that is, of its service, environment,
+    /// version and instance layers), in no particular order.
+    pub async fn list_service_revisions(&self, service: &str) -> AppResult<Vec<ConfigRevision>> {
+        //
+        let items = sqlx::query(
+            "SELECT DISTINCT ON (s.id) s.service, s.version, s.env, s.instance, r.revision, r.author, r.comment, r.created_at, r.items
+             FROM config_revisions r JOIN config_sets s ON s.id = r.config_set_id
+             WHERE s.service = $1
+             ORDER BY s.id, r.revision DESC",
+        )
+        .bind(service)
+        .map(|row: PgRow| revision_from_row(&row))
+        .fetch_all(self.dbcp.as_ref())
+        .await?;
+        Ok(items)
+    }
+
+    /// Add the next revision of a configuration set, that is created if it does not exist.
+    pub async fn add_revision(&self, key: &ConfigKey, author: &str, comment: &str, items: &[ConfigItem]) -> AppResult<ConfigRevision> {
+        //
+        let mut txn = self.dbcp.begin().await?;
+        sqlx::query("INSERT INTO config_sets (id, service, version, env, instance) VALUES ($1, $2, $3, $4, $5) ON CONFLICT DO NOTHING")
+            .bind(Id::new().as_str())
+            .bind(&key.service)
+            .bind(&key.version)
+            .bind(&key.env)
+            .bind(&key.instance)
+            .execute(&mut *txn)
+            .await?;
+        // The set's row is locked, thus the concurrent additions get consecutive revision numbers.
+        let set_id: String =
+            sqlx::query_scalar("SELECT id FROM config_sets WHERE service = $1 AND version = $2 AND env = $3 AND instance = $4 FOR UPDATE")
+                .bind(&key.service)
+                .bind(&key.version)
+                .bind(&key.env)
+                .bind(&key.instance)
+                .fetch_one(&mut *txn)
+                .await?;
+        let revision: i32 = sqlx::query_scalar("SELECT COALESCE(MAX(revision), 0) + 1 FROM config_revisions WHERE config_set_id = $1")
+            .bind(&set_id)
+            .fetch_one(&mut *txn)
+            .await?;
+        let created_at: DateTime<Utc> = sqlx::query_scalar(
+            "INSERT INTO config_revisions (config_set_id, revision, author, comment, items) VALUES ($1, $2, $3, $4, $5)
+             RETURNING created_at",
+        )
+        .bind(&set_id)
+        .bind(revision)
+        .bind(author)
+        .bind(comment)
+        .bind(Json(items))
+        .fetch_one(&mut *txn)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to add revision {} of config '{}'. Cause: '{}'.", revision, key, e);
+            AppError::from(e)
+        })?;
+        txn.commit().await?;
+
+        Ok(ConfigRevision {
+            key: key.clone(),
+            revision,
+            author: author.to_string(),
+            comment: comment.to_string(),
+            created_at: created_at.to_rfc3339(),
+            items: items.to_vec(),
+        })
+    }
+}
+
+fn revision_from_row(row: &PgRow) -> ConfigRevision {
+    //
+    let created_at: DateTime<Utc> = row.get("created_at");
+    let items: Json<Vec<ConfigItem>> = row.get("items");
+    ConfigRevision {
+        key: ConfigKey {
+            service: row.get("service"),
+            version: row.get("version"),
+            env: row.get("env"),
+            instance: row.get("instance"),
+        },
+        revision: row.get("revision"),
+        author: row.get("author"),
+        comment: row.get("comment"),
+        created_at: created_at.to_rfc3339(),
+        items: items.0,
+    }
+}
@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use sqlx::{postgres::PgRow, PgConnection, PgPool, Row};
+
+use crate::{
+    domain::model::Id,
+    server::{AppError, AppResult},
+};
+
+/// The outcome of a consistency check of the stored data.
+#[derive(Clone, Debug)]
+pub struct ConsistencyCheck {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// The number of the inconsistent items.
+    pub count: i64,
+    /// The ids of some of the inconsistent items.
+    pub sample_ids: Vec<Id>,
+}
+
+/// The attributes of all the value types, as a single (`id`, `owner_id`, `def_id`) relation.
+const ALL_ATTRIBUTES: &str = "(SELECT id, owner_id, def_id FROM text_attributes
+    UNION ALL SELECT id, owner_id, def_id FROM smallint_attributes
+    UNION ALL SELECT id, owner_id, def_id FROM integer_attributes
+    UNION ALL SELECT id, owner_id, def_id FROM bigint_attributes
+    UNION ALL SELECT id, owner_id, def_id FROM real_attributes
+    UNION ALL SELECT id, owner_id, def_id FROM boolean_attributes
+    UNION ALL SELECT id, owner_id, def_id FROM date_attributes
+    UNION ALL SELECT id, owner_id, def_id FROM timestamp_attributes)";
+
+/// The checks, as (name, description, query) triplets. Each query selects the ids of the inconsistent items.<br/>
+/// These cover the relations that are not enforced by the database constraints, but by the application.
+const CHECKS: [(&str, &str, &str); 8] = [
+    (
+        "orphan_attributes",
+        "Attributes whose owner is neither an entity nor an entity link.",
+        "SELECT a.id FROM {attributes} a
+         WHERE NOT EXISTS (SELECT 1 FROM entities e WHERE e.id = a.owner_id)
+           AND NOT EXISTS (SELECT 1 FROM entity_links l WHERE l.id = a.owner_id)",
+    ),
+    (
+        "undefined_attributes",
+        "Attributes that are not part of their owner's definition.",
+        "SELECT a.id FROM {attributes} a JOIN entities e ON e.id = a.owner_id
+         WHERE NOT EXISTS (SELECT 1 FROM entity_defs_attribute_defs_xref x
+                           WHERE x.entity_def_id = e.def_id AND x.attribute_def_id = a.def_id)
+         UNION ALL
+         SELECT a.id FROM {attributes} a JOIN entity_links l ON l.id = a.owner_id
+         WHERE NOT EXISTS (SELECT 1 FROM entity_link_defs_attribute_defs_xref x
+                           WHERE x.entity_link_def_id = l.def_id AND x.attribute_def_id = a.def_id)",
+    ),
+    (
+        "missing_required_attributes",
+        "Entities without a value for a required attribute of their definition.",
+        "SELECT DISTINCT e.id FROM entities e
+         JOIN entity_defs_attribute_defs_xref x ON x.entity_def_id = e.def_id
+         JOIN attribute_defs ad ON ad.id = x.attribute_def_id AND ad.required
+         WHERE NOT EXISTS (SELECT 1 FROM {attributes} a WHERE a.owner_id = e.id AND a.def_id = ad.id)",
+    ),
+    (
+        "stale_listing_attributes",
+        "Entities whose listing attribute is not the one of their definition.",
+        "SELECT e.id FROM entities e
+         JOIN entity_defs d ON d.id = e.def_id
+         JOIN attribute_defs ad ON ad.id = d.listing_attr_def_id
+         WHERE e.listing_attr_def_id <> d.listing_attr_def_id OR e.listing_attr_name <> ad.name",
+    ),
+    (
+        "mismatched_link_ends",
+        "Entity links whose source or target entity is not of the definition required by the link definition.",
+        "SELECT l.id FROM entity_links l
+         JOIN entity_link_defs d ON d.id = l.def_id
+         JOIN entities s ON s.id = l.source_entity_id
+         JOIN entities t ON t.id = l.target_entity_id
+         WHERE s.def_id <> d.source_entity_def_id OR t.def_id <> d.target_entity_def_id",
+    ),
+    (
+        "cardinality_violations",
+        "Entity links that exceed the cardinality of their definition.",
+        "SELECT l.id FROM entity_links l
+         JOIN entity_link_defs d ON d.id = l.def_id
+         WHERE d.cardinality IN ('1:1', '1:M') AND EXISTS (
+             SELECT 1 FROM entity_links o WHERE o.def_id = l.def_id AND o.id <> l.id
+             AND (o.target_entity_id = l.target_entity_id
+                  OR (d.cardinality = '1:1' AND o.source_entity_id = l.source_entity_id)))",
+    ),
+    (
+        "unindexed_items",
+        "Entities and entity links that are missing from the search index.",
+        "SELECT e.id FROM entities e WHERE NOT EXISTS (SELECT 1 FROM search_documents s WHERE s.item_id = e.id)
+         UNION ALL
+         SELECT l.id FROM entity_links l WHERE NOT EXISTS (SELECT 1 FROM search_documents s WHERE s.item_id = l.id)",
+    ),
+    (
+        "dangling_search_documents",
+        "Search index documents of entities or entity links that no longer exist.",
+        "SELECT s.item_id AS id FROM search_documents s
+         WHERE (s.item_type = 'eni' AND NOT EXISTS (SELECT 1 FROM entities e WHERE e.id = s.item_id))
+            OR (s.item_type = 'enl' AND NOT EXISTS (SELECT 1 FROM entity_links l WHERE l.id = s.item_id))",
+    ),
+];
+
+pub struct ConsistencyRepo {
+    dbcp: Arc<PgPool>,
+}
+
+impl ConsistencyRepo {
+    //
+    /// The maximum number of the ids of the inconsistent items to include in the outcome of a check.
+    const SAMPLE_SIZE: i64 = 10;
+
+    /// The checks of the references between the items, that are not enforced by foreign keys.
+    pub const REFERENTIAL_CHECKS: [&'static str; 3] = ["orphan_attributes", "undefined_attributes", "mismatched_link_ends"];
+
+    pub fn new(dbcp: Arc<PgPool>) -> Self {
+        Self { dbcp }
+    }
+
+    /// Run all the consistency checks.
+    pub async fn check_all(&self) -> AppResult<Vec<ConsistencyCheck>> {
+        let mut conn = self.dbcp.acquire().await?;
+        Self::check(&mut conn, &[]).await
+    }
+
+    /// Run the checks with the provided names (or all of them, if none is provided) using the provided connection,
+    /// so that they can also be run as part of a transaction.
+    pub async fn check(conn: &mut PgConnection, names: &[&str]) -> AppResult<Vec<ConsistencyCheck>> {
+        //
+        let mut checks = Vec::with_capacity(CHECKS.len());
+        for (name, description, query) in CHECKS.into_iter().filter(|(name, _, _)| names.is_empty() || names.contains(name)) {
+            let query = format!(
+                "SELECT id, COUNT(*) OVER () AS total FROM ({}) items ORDER BY id LIMIT $1",
+                query.replace("{attributes}", ALL_ATTRIBUTES)
+            );
+            let rows: Vec<(Id, i64)> = sqlx::query(&query)
+                .bind(Self::SAMPLE_SIZE)
+                .map(|row: PgRow| (Id::new_from(row.get("id")), row.get("total")))
+                .fetch_all(&mut *conn)
+                .await
+                .map_err(|e| {
+                    log::error!("Failed to run the '{}' consistency check. Cause: '{}'.", name, e);
+                    AppError::from(e)
+                })?;
+            checks.push(ConsistencyCheck {
+                name,
+                description,
+                count: rows.first().map(|(_, total)| *total).unwrap_or_default(),
+                sample_ids: rows.into_iter().map(|(id, _)| id).collect(),
+            });
+        }
+        Ok(checks)
+    }
+}
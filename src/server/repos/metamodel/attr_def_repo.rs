@@ -1,7 +1,7 @@
 use crate::domain::model::Id;
 use crate::{
-    domain::model::{AttributeDef, AttributeValueType},
-    server::{AppError, AppResult, Pagination},
+    domain::model::{AttributeDef, AttributeValueType, ItemType, Page, Pagination},
+    server::{AppError, AppResult, SearchRepo},
 };
 use sqlx::{postgres::PgRow, FromRow, PgPool, Row};
 use std::sync::Arc;
@@ -12,42 +12,60 @@ pub struct AttributeDefRepo {
 
 impl AttributeDefRepo {
     //
+    /// The fields that the attribute definitions can be sorted by, and their columns.
+    pub const SORTABLE: &'static [(&'static str, &'static str)] =
+        &[("name", "name"), ("description", "description"), ("value_type", "value_type")];
+
     pub fn new(dbcp: Arc<PgPool>) -> Self {
         Self { dbcp }
     }
 
-    pub async fn get(&self, id: &Id) -> Option<AttributeDef> {
+    pub async fn get(&self, id: &Id) -> AppResult<Option<AttributeDef>> {
         //
-        sqlx::query_as::<_, AttributeDef>(
+        let attr_def = sqlx::query_as::<_, AttributeDef>(
             "SELECT id, name, description, value_type, default_value, required, tag_id 
              FROM attribute_defs WHERE id = $1",
         )
         .bind(id.as_str())
-        .fetch_one(self.dbcp.as_ref())
-        .await
-        .ok()
+        .fetch_optional(self.dbcp.as_ref())
+        .await?;
+        Ok(attr_def)
     }
 
-    pub async fn list(&self, pagination_opts: Option<&Pagination>) -> Vec<AttributeDef> {
+    /// List the attribute definitions, as per the provided pagination options.<br/>
+    /// If no pagination is provided, all the attribute definitions are returned.
+    pub async fn list(&self, pagination_opts: Option<&Pagination>) -> AppResult<Page<AttributeDef>> {
         //
-        let (offset, limit) = Pagination::from(pagination_opts).get_offset_limit();
+        let (offset, limit) = Pagination::get_offset_limit_opt(pagination_opts);
+        let order_by = Pagination::get_order_by(pagination_opts, Self::SORTABLE, "name");
         let query = format!(
             "SELECT id, name, description, value_type, default_value, required, tag_id 
-             FROM attribute_defs ORDER BY name LIMIT {limit} OFFSET {offset}"
+             FROM attribute_defs ORDER BY {order_by}, id LIMIT $1 OFFSET $2"
+        );
+        log::debug!(
+            "Listing attribute defs w/ limit: {:?}, offset: {}, order by: {}.",
+            limit,
+            offset,
+            order_by
         );
-        log::debug!("Listing attribute defs w/ limit: {}, offset: {}.", limit, offset);
 
-        sqlx::query_as::<_, AttributeDef>(query.as_str())
+        let items = sqlx::query_as::<_, AttributeDef>(query.as_str())
+            .bind(limit)
+            .bind(offset)
             .fetch_all(self.dbcp.as_ref())
-            .await
-            .ok()
-            .unwrap_or_default()
+            .await?;
+        let total = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM attribute_defs")
+            .fetch_one(self.dbcp.as_ref())
+            .await?;
+
+        Ok(Page::new(items, pagination_opts, total))
     }
 
     /// Add a new attribute definition. It returns the id of the repository entry.
     pub async fn add(&self, item: &AttributeDef) -> AppResult<()> {
         //
-        sqlx::query(
+        let mut txn = self.dbcp.begin().await?;
+        if let Err(e) = sqlx::query(
             "INSERT INTO attribute_defs (id, name, description, value_type, default_value, required, tag_id)
              VALUES ($1, $2, $3, $4, $5, $6, $7)",
         )
@@ -58,24 +76,33 @@ impl AttributeDefRepo {
         .bind(&item.default_value)
         .bind(item.is_required)
         .bind(item.tag_id.as_ref().map(|id| id.as_str()))
-        .execute(self.dbcp.as_ref())
+        .execute(&mut *txn)
         .await
-        .map(|_| Ok(()))
-        .map_err(|e| {
+        {
+            txn.rollback().await?;
             if e.to_string().contains("name_desc_unique") {
-                AppError::NameDescriptionNotUnique
-            } else {
-                log::error!("Failed to add attribute definition. Reason: '{}'.", e);
-                AppError::Err("An internal error occurred.".into())
+                return Err(AppError::NameDescriptionNotUnique);
             }
-        })?
+            log::error!("Failed to add attribute definition. Reason: '{}'.", e);
+            return Err(AppError::Err("An internal error occurred.".into()));
+        }
+
+        if let Err(e) = SearchRepo::index_definition(&mut *txn, ItemType::AttributeDef, &item.id, &item.name, &item.description).await {
+            txn.rollback().await?;
+            log::error!("Failed to index attribute def id:'{}' for search. Cause: '{}'.", item.id, e);
+            return Err(e.into());
+        }
+
+        txn.commit().await?;
+        Ok(())
     }
 
     /// Edit an existing attribute definition.
     pub async fn update(&self, item: &AttributeDef) -> AppResult<()> {
         //
         let tag_id = item.tag_id.as_ref().map(|id| id.as_str());
-        sqlx::query(
+        let mut txn = self.dbcp.begin().await?;
+        if let Err(e) = sqlx::query(
             "UPDATE attribute_defs 
              SET name=$2, description=$3, value_type=$4, default_value=$5, required=$6, tag_id=$7 
              WHERE id = $1",
@@ -87,29 +114,47 @@ impl AttributeDefRepo {
         .bind(&item.default_value)
         .bind(item.is_required)
         .bind(tag_id)
-        .execute(self.dbcp.as_ref())
+        .execute(&mut *txn)
         .await
-        .map(|_| Ok(()))
-        .map_err(|e| {
+        {
+            txn.rollback().await?;
             if e.to_string().contains("name_desc_unique") {
-                AppError::NameDescriptionNotUnique
-            } else {
-                log::error!("Failed to update attribute definition. Reason: '{}'.", e);
-                AppError::Err("An internal error occurred.".into())
+                return Err(AppError::NameDescriptionNotUnique);
             }
-        })?
+            log::error!("Failed to update attribute definition. Reason: '{}'.", e);
+            return Err(AppError::Err("An internal error occurred.".into()));
+        }
+
+        if let Err(e) = SearchRepo::index_definition(&mut *txn, ItemType::AttributeDef, &item.id, &item.name, &item.description).await {
+            txn.rollback().await?;
+            log::error!("Failed to index attribute def id:'{}' for search. Cause: '{}'.", item.id, e);
+            return Err(e.into());
+        }
+
+        txn.commit().await?;
+        Ok(())
     }
 
     /// Remove (delete) an existing attribute definition.
     pub async fn remove(&self, id: &Id) -> AppResult<()> {
         //
+        let mut txn = self.dbcp.begin().await?;
         match sqlx::query("DELETE FROM attribute_defs WHERE id = $1")
             .bind(id.as_str())
-            .execute(self.dbcp.as_ref())
+            .execute(&mut *txn)
             .await
         {
-            Ok(_) => AppResult::Ok(()),
+            Ok(_) => {
+                if let Err(e) = SearchRepo::unindex(&mut *txn, id).await {
+                    txn.rollback().await?;
+                    log::error!("Failed to unindex attribute def id:'{}' from search. Cause: '{}'.", id, e);
+                    return Err(e.into());
+                }
+                txn.commit().await?;
+                AppResult::Ok(())
+            }
             Err(e) => {
+                txn.rollback().await?;
                 if let Some(db_err) = e.as_database_error() {
                     if let Some(db_err_code) = db_err.code() {
                         // 23503 is postgres specific code for dependencies (named "foreign_key_violation").
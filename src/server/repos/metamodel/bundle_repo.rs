@@ -0,0 +1,308 @@
+use std::{collections::HashMap, sync::Arc};
+
+use sqlx::{PgConnection, PgPool, Row};
+
+use crate::{
+    domain::model::{
+        AttributeDef, BundleEntityDef, BundleEntityLinkDef, Cardinality, Id, ImportActionKind, ImportPlan, ItemType, MetamodelBundle, Tag,
+    },
+    server::{AppResult, SearchRepo},
+};
+
+/// The number of values of each attribute definition, by the (entity or entity link) definition of their owners,
+/// that is by (owner definition id, attribute definition id).
+pub type AttributeValueCounts = HashMap<(Id, Id), i64>;
+
+/// The repository for exporting and importing the whole metamodel as a bundle.
+pub struct MetamodelBundleRepo {
+    pub dbcp: Arc<PgPool>,
+}
+
+impl MetamodelBundleRepo {
+    //
+    pub fn new(dbcp: Arc<PgPool>) -> Self {
+        Self { dbcp }
+    }
+
+    /// Export the whole metamodel (tags and definitions).
+    pub async fn export(&self) -> AppResult<MetamodelBundle> {
+        let mut conn = self.dbcp.acquire().await?;
+        Self::export_in(&mut conn).await
+    }
+
+    /// Import a bundle, in a single transaction: the plan is made (by the provided function) against the current metamodel
+    /// and the number of values of its attributes, as read in the same transaction that then stores it.<br/>
+    /// The planned bundle is stored only if it is not a dry run and the plan has no conflicts.
+    /// Its ids are expected to be the ones of this instance (that is resolved, by the plan function).
+    pub async fn import<F>(&self, dry_run: bool, plan: F) -> AppResult<ImportPlan>
+    where
+        F: FnOnce(&MetamodelBundle, &AttributeValueCounts) -> (ImportPlan, MetamodelBundle),
+    {
+        //
+        let mut txn = self.dbcp.begin().await?;
+        // The plan must still hold when it is applied, thus its reads and the writes see the same snapshot.
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ").execute(&mut *txn).await?;
+        let current = Self::export_in(&mut txn).await?;
+        let value_counts = Self::count_attribute_values_in(&mut txn).await?;
+        let (mut import_plan, bundle) = plan(&current, &value_counts);
+        if dry_run || import_plan.has_conflicts() {
+            txn.rollback().await?;
+            return Ok(import_plan);
+        }
+        let res = match Self::store(&mut txn, &bundle).await {
+            Ok(()) => Self::refresh_listing_attrs(&mut txn, &import_plan).await,
+            Err(e) => Err(e),
+        };
+        match res {
+            Ok(()) => {
+                txn.commit().await?;
+                import_plan.applied = true;
+                Ok(import_plan)
+            }
+            Err(e) => {
+                txn.rollback().await?;
+                log::error!("Failed to import the metamodel bundle. Cause: '{}'.", e);
+                AppResult::Err(e.into())
+            }
+        }
+    }
+
+    async fn export_in(conn: &mut PgConnection) -> AppResult<MetamodelBundle> {
+        //
+        let tags = sqlx::query_as::<_, Tag>("SELECT id, name, description FROM tags ORDER BY name, id")
+            .fetch_all(&mut *conn)
+            .await?;
+
+        let attribute_defs = sqlx::query_as::<_, AttributeDef>(
+            "SELECT id, name, description, value_type, default_value, required, tag_id FROM attribute_defs ORDER BY name, id",
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let ent_defs_attrs = Self::list_xref_attr_def_ids(
+            conn,
+            "SELECT entity_def_id AS owner_id, attribute_def_id FROM entity_defs_attribute_defs_xref ORDER BY show_index",
+        )
+        .await?;
+        let entity_defs = sqlx::query("SELECT id, name, description, listing_attr_def_id FROM entity_defs ORDER BY name, id")
+            .fetch_all(&mut *conn)
+            .await?
+            .into_iter()
+            .map(|row| {
+                let id = Id::new_from(row.get("id"));
+                BundleEntityDef {
+                    attribute_def_ids: Self::attr_def_ids_of(&ent_defs_attrs, &id),
+                    id,
+                    name: row.get("name"),
+                    description: row.get("description"),
+                    listing_attr_def_id: Id::new_from(row.get("listing_attr_def_id")),
+                }
+            })
+            .collect();
+
+        let ent_link_defs_attrs = Self::list_xref_attr_def_ids(
+            conn,
+            "SELECT entity_link_def_id AS owner_id, attribute_def_id FROM entity_link_defs_attribute_defs_xref ORDER BY attribute_def_id",
+        )
+        .await?;
+        let entity_link_defs = sqlx::query(
+            "SELECT id, name, description, cardinality, source_entity_def_id, target_entity_def_id FROM entity_link_defs ORDER BY name, id",
+        )
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .map(|row| {
+            let id = Id::new_from(row.get("id"));
+            BundleEntityLinkDef {
+                attribute_def_ids: Self::attr_def_ids_of(&ent_link_defs_attrs, &id),
+                id,
+                name: row.get::<Option<String>, &str>("name").unwrap_or_default(),
+                description: row.get("description"),
+                cardinality: Cardinality::from(row.get::<&str, &str>("cardinality")),
+                source_entity_def_id: Id::new_from(row.get("source_entity_def_id")),
+                target_entity_def_id: Id::new_from(row.get("target_entity_def_id")),
+            }
+        })
+        .collect();
+
+        Ok(MetamodelBundle {
+            version: MetamodelBundle::VERSION,
+            tags,
+            attribute_defs,
+            entity_defs,
+            entity_link_defs,
+        })
+    }
+
+    /// Count the values of each attribute definition, by the (entity or entity link) definition of their owners.
+    async fn count_attribute_values_in(conn: &mut PgConnection) -> AppResult<AttributeValueCounts> {
+        //
+        let values = ["text", "smallint", "integer", "bigint", "real", "boolean", "date", "timestamp"]
+            .iter()
+            .map(|value_type| format!("SELECT owner_id, def_id FROM {value_type}_attributes"))
+            .collect::<Vec<String>>()
+            .join(" UNION ALL ");
+        let query = format!(
+            "SELECT o.def_id AS owner_def_id, v.def_id AS attr_def_id, COUNT(*) AS count FROM ({values}) v 
+             JOIN (SELECT id, def_id FROM entities UNION ALL SELECT id, def_id FROM entity_links) o ON o.id = v.owner_id 
+             GROUP BY o.def_id, v.def_id"
+        );
+        let rows = sqlx::query(query.as_str()).fetch_all(&mut *conn).await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let key = (Id::new_from(row.get("owner_def_id")), Id::new_from(row.get("attr_def_id")));
+                (key, row.get::<i64, &str>("count"))
+            })
+            .collect())
+    }
+
+    async fn store(conn: &mut PgConnection, bundle: &MetamodelBundle) -> Result<(), sqlx::Error> {
+        //
+        for tag in bundle.tags.iter() {
+            sqlx::query(
+                "INSERT INTO tags (id, name, description) VALUES ($1, $2, $3) 
+                 ON CONFLICT (id) DO UPDATE SET name = EXCLUDED.name, description = EXCLUDED.description",
+            )
+            .bind(tag.id.as_str())
+            .bind(&tag.name)
+            .bind(&tag.description)
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        for item in bundle.attribute_defs.iter() {
+            sqlx::query(
+                "INSERT INTO attribute_defs (id, name, description, value_type, default_value, required, tag_id) 
+                 VALUES ($1, $2, $3, $4, $5, $6, $7) 
+                 ON CONFLICT (id) DO UPDATE SET name = EXCLUDED.name, description = EXCLUDED.description, 
+                    value_type = EXCLUDED.value_type, default_value = EXCLUDED.default_value, 
+                    required = EXCLUDED.required, tag_id = EXCLUDED.tag_id",
+            )
+            .bind(item.id.as_str())
+            .bind(&item.name)
+            .bind(&item.description)
+            .bind(item.value_type.to_string())
+            .bind(&item.default_value)
+            .bind(item.is_required)
+            .bind(item.tag_id.as_ref().map(|id| id.as_str()))
+            .execute(&mut *conn)
+            .await?;
+            SearchRepo::index_definition(&mut *conn, ItemType::AttributeDef, &item.id, &item.name, &item.description).await?;
+        }
+
+        for item in bundle.entity_defs.iter() {
+            sqlx::query(
+                "INSERT INTO entity_defs (id, name, description, listing_attr_def_id) VALUES ($1, $2, $3, $4) 
+                 ON CONFLICT (id) DO UPDATE SET name = EXCLUDED.name, description = EXCLUDED.description, 
+                    listing_attr_def_id = EXCLUDED.listing_attr_def_id",
+            )
+            .bind(item.id.as_str())
+            .bind(&item.name)
+            .bind(&item.description)
+            .bind(item.listing_attr_def_id.as_str())
+            .execute(&mut *conn)
+            .await?;
+            sqlx::query("DELETE FROM entity_defs_attribute_defs_xref WHERE entity_def_id = $1")
+                .bind(item.id.as_str())
+                .execute(&mut *conn)
+                .await?;
+            for (index, attr_def_id) in item.attribute_def_ids.iter().enumerate() {
+                sqlx::query(
+                    "INSERT INTO entity_defs_attribute_defs_xref (entity_def_id, attribute_def_id, show_index) VALUES ($1, $2, $3)",
+                )
+                .bind(item.id.as_str())
+                .bind(attr_def_id.as_str())
+                .bind((index + 1) as i16)
+                .execute(&mut *conn)
+                .await?;
+            }
+            SearchRepo::index_definition(&mut *conn, ItemType::EntityDef, &item.id, &item.name, &item.description).await?;
+        }
+
+        for item in bundle.entity_link_defs.iter() {
+            sqlx::query(
+                "INSERT INTO entity_link_defs (id, name, description, cardinality, source_entity_def_id, target_entity_def_id) 
+                 VALUES ($1, $2, $3, $4, $5, $6) 
+                 ON CONFLICT (id) DO UPDATE SET name = EXCLUDED.name, description = EXCLUDED.description, 
+                    cardinality = EXCLUDED.cardinality, source_entity_def_id = EXCLUDED.source_entity_def_id, 
+                    target_entity_def_id = EXCLUDED.target_entity_def_id",
+            )
+            .bind(item.id.as_str())
+            .bind(&item.name)
+            .bind(&item.description)
+            .bind(item.cardinality.as_string())
+            .bind(item.source_entity_def_id.as_str())
+            .bind(item.target_entity_def_id.as_str())
+            .execute(&mut *conn)
+            .await?;
+            sqlx::query("DELETE FROM entity_link_defs_attribute_defs_xref WHERE entity_link_def_id = $1")
+                .bind(item.id.as_str())
+                .execute(&mut *conn)
+                .await?;
+            for attr_def_id in item.attribute_def_ids.iter() {
+                sqlx::query("INSERT INTO entity_link_defs_attribute_defs_xref (entity_link_def_id, attribute_def_id) VALUES ($1, $2)")
+                    .bind(item.id.as_str())
+                    .bind(attr_def_id.as_str())
+                    .execute(&mut *conn)
+                    .await?;
+            }
+            SearchRepo::index_definition(&mut *conn, ItemType::EntityLinkDef, &item.id, &item.name, &item.description).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Refresh the copy of the listing attribute (its id, name and value) that the entities keep, for the updated
+    /// entity definitions and for the ones whose listing attribute definition was updated (such as renamed).
+    async fn refresh_listing_attrs(conn: &mut PgConnection, plan: &ImportPlan) -> Result<(), sqlx::Error> {
+        //
+        let updated_ids = |item_type: ItemType| -> Vec<&str> {
+            plan.actions
+                .iter()
+                .filter(|a| a.kind == ImportActionKind::Update && a.item_type == item_type)
+                .map(|a| a.target_id.as_str())
+                .collect()
+        };
+        let ent_def_ids = updated_ids(ItemType::EntityDef);
+        let attr_def_ids = updated_ids(ItemType::AttributeDef);
+        if ent_def_ids.is_empty() && attr_def_ids.is_empty() {
+            return Ok(());
+        }
+        let values = ["text", "smallint", "integer", "boolean"]
+            .iter()
+            .map(|value_type| format!("SELECT owner_id, def_id, value::text AS value FROM {value_type}_attributes"))
+            .collect::<Vec<String>>()
+            .join(" UNION ALL ");
+        let query = format!(
+            "UPDATE entities e SET listing_attr_def_id = ed.listing_attr_def_id, listing_attr_name = ad.name, 
+                listing_attr_value = COALESCE((SELECT LEFT(v.value, 64) FROM ({values}) v 
+                    WHERE v.owner_id = e.id AND v.def_id = ed.listing_attr_def_id LIMIT 1), '') 
+             FROM entity_defs ed JOIN attribute_defs ad ON ad.id = ed.listing_attr_def_id 
+             WHERE e.def_id = ed.id AND (ed.id = ANY($1) OR ed.listing_attr_def_id = ANY($2))"
+        );
+        sqlx::query(query.as_str())
+            .bind(ent_def_ids)
+            .bind(attr_def_ids)
+            .execute(&mut *conn)
+            .await?;
+        Ok(())
+    }
+
+    /// List the (owner id, attribute def id) pairs, using the provided query on a `..._attribute_defs_xref` table.
+    async fn list_xref_attr_def_ids(conn: &mut PgConnection, query: &str) -> AppResult<Vec<(Id, Id)>> {
+        //
+        let rows = sqlx::query(query).fetch_all(&mut *conn).await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| (Id::new_from(row.get("owner_id")), Id::new_from(row.get("attribute_def_id"))))
+            .collect())
+    }
+
+    fn attr_def_ids_of(xref: &[(Id, Id)], owner_id: &Id) -> Vec<Id> {
+        xref.iter()
+            .filter(|(id, _)| id == owner_id)
+            .map(|(_, attr_def_id)| attr_def_id.clone())
+            .collect()
+    }
+}
@@ -3,8 +3,8 @@ use sqlx::{postgres::PgRow, FromRow, PgPool, Row};
 use std::sync::Arc;
 
 use crate::{
-    domain::model::{AttributeDef, EntityDef, Id},
-    server::{AppError, AppResult, Pagination},
+    domain::model::{AttributeDef, EntityDef, Id, ItemType, Page, Pagination},
+    server::{AppError, AppResult, SearchRepo},
     ui::pages::Name,
 };
 
@@ -14,6 +14,9 @@ pub struct EntityDefRepo {
 
 impl EntityDefRepo {
     //
+    /// The fields that the entity definitions can be sorted by, and their columns.
+    pub const SORTABLE: &'static [(&'static str, &'static str)] = &[("name", "name"), ("description", "description")];
+
     pub fn new(dbcp: Arc<PgPool>) -> Self {
         Self { dbcp }
     }
@@ -30,17 +33,25 @@ impl EntityDefRepo {
             })?
     }
 
-    pub async fn list(&self, pagination_opts: Option<&Pagination>) -> AppResult<Vec<EntityDef>> {
+    /// List the entity definitions, as per the provided pagination options.<br/>
+    /// If no pagination is provided, all the entity definitions are returned.
+    pub async fn list(&self, pagination_opts: Option<&Pagination>) -> AppResult<Page<EntityDef>> {
         //
-        let (offset, limit) = Pagination::from(pagination_opts).get_offset_limit();
+        let (offset, limit) = Pagination::get_offset_limit_opt(pagination_opts);
+        let order_by = Pagination::get_order_by(pagination_opts, Self::SORTABLE, "name");
         let query = format!(
             "SELECT id, name, description, listing_attr_def_id 
-             FROM entity_defs ORDER BY name LIMIT {limit} OFFSET {offset}"
+             FROM entity_defs ORDER BY {order_by}, id LIMIT $1 OFFSET $2"
         );
 
-        let mut ent_defs = sqlx::query_as::<_, EntityDef>(query.as_str()) // FYI: Binding (such as .bind(limit) didn't work, that's why the query.
+        let mut ent_defs = sqlx::query_as::<_, EntityDef>(query.as_str())
+            .bind(limit)
+            .bind(offset)
             .fetch_all(self.dbcp.as_ref())
             .await?;
+        let total = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM entity_defs")
+            .fetch_one(self.dbcp.as_ref())
+            .await?;
 
         for ent_def in &mut ent_defs {
             if let Ok(attrs) = sqlx::query_as::<_, AttributeDef>(
@@ -59,7 +70,7 @@ impl EntityDefRepo {
             }
         }
 
-        Ok(ent_defs)
+        Ok(Page::new(ent_defs, pagination_opts, total))
     }
 
     pub async fn list_refs_by_attr_def_id(&self, attr_def_id: &Id) -> AppResult<Vec<(Id, Name)>> {
@@ -112,6 +123,13 @@ impl EntityDefRepo {
             }
         }
 
+        if let Err(e) = SearchRepo::index_definition(&mut *txn, ItemType::EntityDef, &ent_def.id, &ent_def.name, &ent_def.description).await
+        {
+            txn.rollback().await?;
+            log::error!("Failed to index entity def id:'{}' for search. Cause: '{}'.", ent_def.id, e);
+            return AppResult::Err(e.into());
+        }
+
         txn.commit().await?;
         AppResult::Ok(())
     }
@@ -185,6 +203,13 @@ impl EntityDefRepo {
             }
         }
 
+        if let Err(e) = SearchRepo::index_definition(&mut *txn, ItemType::EntityDef, &ent_def.id, &ent_def.name, &ent_def.description).await
+        {
+            txn.rollback().await?;
+            log::error!("Failed to index entity def id:'{}' for search. Cause: '{}'.", ent_def.id, e);
+            return AppResult::Err(e.into());
+        }
+
         txn.commit().await?;
         AppResult::Ok(())
     }
@@ -220,6 +245,12 @@ impl EntityDefRepo {
             return AppResult::Err(e.into());
         }
 
+        if let Err(e) = SearchRepo::unindex(&mut *txn, id).await {
+            txn.rollback().await?;
+            log::error!("Failed to remove entity def id:'{}' from search index. Cause: '{}'.", id, e);
+            return AppResult::Err(e.into());
+        }
+
         txn.commit().await?;
         AppResult::Ok(())
     }
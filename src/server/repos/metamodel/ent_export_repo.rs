@@ -0,0 +1,114 @@
+use futures::{stream::BoxStream, StreamExt};
+use serde_json::{Map, Value};
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+
+use crate::{domain::model::Id, server::AppResult};
+
+/// An entity, as read for an export.<br/>
+/// Its attribute values are keyed by their definition id, and its (outgoing) links
+/// are the target entity ids keyed by the link definition id.
+pub struct ExportedEntity {
+    pub id: Id,
+    /// Its listing attribute value.
+    pub label: String,
+    pub attributes: Map<String, Value>,
+    pub links: Map<String, Value>,
+}
+
+/// An (outgoing) entity link, as read for an export.
+pub struct ExportedEntityLink {
+    pub id: Id,
+    pub def_id: Id,
+    pub source_entity_id: Id,
+    pub target_entity_id: Id,
+}
+
+/// The repository of reading the entities (and their links) in a streamed manner, for exporting them.
+pub struct EntityExportRepo {
+    pub dbcp: Arc<PgPool>,
+}
+
+impl EntityExportRepo {
+    //
+    pub fn new(dbcp: Arc<PgPool>) -> Self {
+        Self { dbcp }
+    }
+
+    /// List the definitions (ids and names) of the links whose source is an entity of the provided definition.
+    pub async fn list_outgoing_link_defs(&self, ent_def_id: &Id) -> AppResult<Vec<(Id, String)>> {
+        let rows = sqlx::query("SELECT id, name FROM entity_link_defs WHERE source_entity_def_id = $1 ORDER BY name")
+            .bind(ent_def_id.as_str())
+            .fetch_all(self.dbcp.as_ref())
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                (
+                    Id::new_from(row.get("id")),
+                    row.get::<Option<String>, _>("name").unwrap_or_default(),
+                )
+            })
+            .collect())
+    }
+
+    /// Stream all the entities of the provided definition, ordered by their id.<br/>
+    /// The rows are fetched from the database as the stream is consumed.
+    pub fn stream_entities<'a>(&'a self, ent_def_id: &'a Id, with_links: bool) -> BoxStream<'a, Result<ExportedEntity, sqlx::Error>> {
+        sqlx::query(
+            "SELECT e.id, e.listing_attr_value,
+                    COALESCE((SELECT jsonb_object_agg(TRIM(a.def_id), a.value) FROM (
+                        SELECT def_id, to_jsonb(value) AS value FROM text_attributes WHERE owner_id = e.id
+                        UNION ALL SELECT def_id, to_jsonb(value) FROM smallint_attributes WHERE owner_id = e.id
+                        UNION ALL SELECT def_id, to_jsonb(value) FROM integer_attributes WHERE owner_id = e.id
+                        UNION ALL SELECT def_id, to_jsonb(value) FROM bigint_attributes WHERE owner_id = e.id
+                        UNION ALL SELECT def_id, to_jsonb(value) FROM real_attributes WHERE owner_id = e.id
+                        UNION ALL SELECT def_id, to_jsonb(value) FROM boolean_attributes WHERE owner_id = e.id
+                        UNION ALL SELECT def_id, to_jsonb(value) FROM date_attributes WHERE owner_id = e.id
+                        UNION ALL SELECT def_id, to_jsonb(value) FROM timestamp_attributes WHERE owner_id = e.id) a), '{}') AS attributes,
+                    COALESCE((SELECT jsonb_object_agg(TRIM(l.def_id), l.targets) FROM (
+                        SELECT def_id, jsonb_agg(TRIM(target_entity_id) ORDER BY target_entity_id) AS targets
+                        FROM entity_links WHERE $2 AND source_entity_id = e.id GROUP BY def_id) l), '{}') AS links
+             FROM entities e WHERE e.def_id = $1 ORDER BY e.id",
+        )
+        .bind(ent_def_id.as_str())
+        .bind(with_links)
+        .fetch(self.dbcp.as_ref())
+        .map(|res| {
+            res.map(|row| ExportedEntity {
+                id: Id::new_from(row.get("id")),
+                label: row.get("listing_attr_value"),
+                attributes: json_object(row.get("attributes")),
+                links: json_object(row.get("links")),
+            })
+        })
+        .boxed()
+    }
+
+    /// Stream the links whose source is an entity of the provided definition, ordered by their id.
+    pub fn stream_links<'a>(&'a self, ent_def_id: &'a Id) -> BoxStream<'a, Result<ExportedEntityLink, sqlx::Error>> {
+        sqlx::query(
+            "SELECT el.id, el.def_id, el.source_entity_id, el.target_entity_id
+             FROM entity_links el JOIN entities e ON el.source_entity_id = e.id
+             WHERE e.def_id = $1 ORDER BY el.id",
+        )
+        .bind(ent_def_id.as_str())
+        .fetch(self.dbcp.as_ref())
+        .map(|res| {
+            res.map(|row| ExportedEntityLink {
+                id: Id::new_from(row.get("id")),
+                def_id: Id::new_from(row.get("def_id")),
+                source_entity_id: Id::new_from(row.get("source_entity_id")),
+                target_entity_id: Id::new_from(row.get("target_entity_id")),
+            })
+        })
+        .boxed()
+    }
+}
+
+fn json_object(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    }
+}
@@ -2,8 +2,8 @@ use sqlx::{postgres::PgRow, FromRow, PgPool, Row};
 use std::sync::Arc;
 
 use crate::{
-    domain::model::{AttributeDef, Cardinality, EntityLinkDef, Id},
-    server::{AppError, AppResult},
+    domain::model::{AttributeDef, Cardinality, EntityLinkDef, Id, ItemType, Page, Pagination},
+    server::{AppError, AppResult, SearchRepo},
 };
 
 pub struct EntityLinkDefRepo {
@@ -12,18 +12,33 @@ pub struct EntityLinkDefRepo {
 
 impl EntityLinkDefRepo {
     //
+    /// The fields that the entity link definitions can be sorted by, and their columns.
+    pub const SORTABLE: &'static [(&'static str, &'static str)] =
+        &[("name", "name"), ("description", "description"), ("cardinality", "cardinality")];
+
     pub fn new(dbcp: Arc<PgPool>) -> Self {
         Self { dbcp }
     }
 
-    pub async fn list(&self) -> AppResult<Vec<EntityLinkDef>> {
+    /// List the entity link definitions, as per the provided pagination options.<br/>
+    /// If no pagination is provided, all the entity link definitions are returned.
+    pub async fn list(&self, pagination_opts: Option<&Pagination>) -> AppResult<Page<EntityLinkDef>> {
         //
-        let query = "SELECT id, name, description, cardinality, source_entity_def_id, target_entity_def_id  
-                     FROM entity_link_defs ORDER BY name";
-        let mut items = sqlx::query_as::<_, EntityLinkDef>(query)
+        let (offset, limit) = Pagination::get_offset_limit_opt(pagination_opts);
+        let order_by = Pagination::get_order_by(pagination_opts, Self::SORTABLE, "name");
+        let query = format!(
+            "SELECT id, name, description, cardinality, source_entity_def_id, target_entity_def_id  
+             FROM entity_link_defs ORDER BY {order_by}, id LIMIT $1 OFFSET $2"
+        );
+        let mut items = sqlx::query_as::<_, EntityLinkDef>(query.as_str())
+            .bind(limit)
+            .bind(offset)
             .fetch_all(self.dbcp.as_ref())
             .await
             .map_err(|e| AppError::from(e))?;
+        let total = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM entity_link_defs")
+            .fetch_one(self.dbcp.as_ref())
+            .await?;
 
         for item in &mut items {
             let attrs = sqlx::query_as::<_, AttributeDef>(
@@ -40,7 +55,7 @@ impl EntityLinkDefRepo {
             .collect();
             item.attributes = Some(attrs);
         }
-        Ok(items)
+        Ok(Page::new(items, pagination_opts, total))
     }
 
     pub async fn add(&self, item: &EntityLinkDef) -> AppResult<()> {
@@ -82,6 +97,12 @@ impl EntityLinkDefRepo {
             }
         }
 
+        if let Err(e) = SearchRepo::index_definition(&mut *txn, ItemType::EntityLinkDef, &item.id, &item.name, &item.description).await {
+            txn.rollback().await?;
+            log::error!("Failed to index entity link def id:'{}' for search. Cause: '{}'.", item.id, e);
+            return AppResult::Err(e.into());
+        }
+
         txn.commit().await?;
 
         Ok(())
@@ -170,6 +191,23 @@ impl EntityLinkDefRepo {
             }
         }
 
+        if let Err(e) = SearchRepo::index_definition(&mut *txn, ItemType::EntityLinkDef, &item.id, &item.name, &item.description).await {
+            txn.rollback().await?;
+            log::error!("Failed to index entity link def id:'{}' for search. Cause: '{}'.", item.id, e);
+            return AppResult::Err(e.into());
+        }
+
+        // The links are indexed with their definition's name, that may have changed.
+        if let Err(e) = SearchRepo::index_entity_links_by_def_id(&mut *txn, &item.id).await {
+            txn.rollback().await?;
+            log::error!(
+                "Failed to index the links of entity link def id:'{}' for search. Cause: '{}'.",
+                item.id,
+                e
+            );
+            return AppResult::Err(e.into());
+        }
+
         txn.commit().await?;
 
         Ok(())
@@ -177,11 +215,14 @@ impl EntityLinkDefRepo {
 
     pub async fn remove(&self, id: &Id) -> AppResult<()> {
         //
+        let mut txn = self.dbcp.begin().await?;
+
         if let Err(e) = sqlx::query("DELETE FROM entity_link_defs WHERE id = $1")
             .bind(id.as_str())
-            .execute(self.dbcp.as_ref())
+            .execute(&mut *txn)
             .await
         {
+            txn.rollback().await?;
             if let Some(db_err) = e.as_database_error() {
                 if let Some(db_err_code) = db_err.code() {
                     if db_err_code == "23503" {
@@ -193,6 +234,14 @@ impl EntityLinkDefRepo {
             return AppResult::Err(e.into());
         }
 
+        if let Err(e) = SearchRepo::unindex(&mut *txn, id).await {
+            txn.rollback().await?;
+            log::error!("Failed to unindex entity link def id:'{}' from search. Cause: '{}'.", id, e);
+            return AppResult::Err(e.into());
+        }
+
+        txn.commit().await?;
+
         Ok(())
     }
 }
@@ -1,8 +1,11 @@
 use crate::{
-    domain::model::{BooleanAttribute, EntityLink, Id, IntegerAttribute, ItemType, SmallintAttribute, TextAttribute},
-    server::{AppResult, Pagination},
+    domain::model::{
+        BooleanAttribute, Cursor, CursorPage, EntityLink, Id, IntegerAttribute, ItemType, Page, Pagination, SmallintAttribute,
+        TextAttribute,
+    },
+    server::{AppResult, SearchRepo},
 };
-use sqlx::{postgres::PgRow, FromRow, PgPool, Row};
+use sqlx::{postgres::PgRow, FromRow, PgPool, Postgres, QueryBuilder, Row};
 use std::sync::Arc;
 
 pub struct EntityLinkRepo {
@@ -11,43 +14,216 @@ pub struct EntityLinkRepo {
 
 impl EntityLinkRepo {
     //
+    /// The fields that the entity links can be sorted by, and their columns.
+    pub const SORTABLE: &'static [(&'static str, &'static str)] = &[
+        ("kind", "eld.name"),
+        ("source", "se.listing_attr_value"),
+        ("target", "te.listing_attr_value"),
+    ];
+
     pub fn new(dbcp: Arc<PgPool>) -> Self {
         Self { dbcp }
     }
 
-    /// List all the entities.<br/>
-    /// Note that the attributes of the entities are not loaded.
-    pub async fn list(&self, pagination_opts: Option<&Pagination>) -> AppResult<Vec<EntityLink>> {
+    /// List the entity links, as per the provided pagination options.<br/>
+    /// If no pagination is provided, all the entity links are returned.<br/>
+    /// Note that their attributes are not loaded.
+    pub async fn list(&self, pagination_opts: Option<&Pagination>) -> AppResult<Page<EntityLink>> {
         //
-        let (offset, limit) = Pagination::from(pagination_opts).get_offset_limit();
+        let (offset, limit) = Pagination::get_offset_limit_opt(pagination_opts);
+        let order_by = Pagination::get_order_by(pagination_opts, Self::SORTABLE, "eld.name");
         let query = format!(
             "SELECT el.id, el.def_id, el.source_entity_id, el.target_entity_id, eld.name as kind 
              FROM entity_links el 
              JOIN entity_link_defs eld ON el.def_id = eld.id 
-             ORDER BY name LIMIT {limit} OFFSET {offset}"
+             LEFT JOIN entities se ON el.source_entity_id = se.id 
+             LEFT JOIN entities te ON el.target_entity_id = te.id 
+             ORDER BY {order_by}, el.id LIMIT $1 OFFSET $2"
         );
 
-        sqlx::query_as::<_, EntityLink>(query.as_str())
+        let items = sqlx::query_as::<_, EntityLink>(query.as_str())
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(self.dbcp.as_ref())
+            .await?;
+        let total = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM entity_links")
+            .fetch_one(self.dbcp.as_ref())
+            .await?;
+
+        Ok(Page::new(items, pagination_opts, total))
+    }
+
+    /// List the entity links by `def_id`, as per the provided pagination options.<br/>
+    /// If no pagination is provided, all the entity links with that definition are returned.<br/>
+    /// Note that their attributes are not loaded.
+    pub async fn list_by_def_id(&self, def_id: &Id, pagination_opts: Option<&Pagination>) -> AppResult<Page<EntityLink>> {
+        //
+        let (offset, limit) = Pagination::get_offset_limit_opt(pagination_opts);
+        let order_by = Pagination::get_order_by(pagination_opts, Self::SORTABLE, "el.id");
+        let query = format!(
+            "SELECT el.id, el.def_id, el.source_entity_id, el.target_entity_id, eld.name as kind 
+             FROM entity_links el 
+             JOIN entity_link_defs eld ON el.def_id = eld.id 
+             LEFT JOIN entities se ON el.source_entity_id = se.id 
+             LEFT JOIN entities te ON el.target_entity_id = te.id 
+             WHERE el.def_id = $1 
+             ORDER BY {order_by}, el.id LIMIT $2 OFFSET $3"
+        );
+
+        let items = sqlx::query_as::<_, EntityLink>(query.as_str())
+            .bind(&def_id.as_str())
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(self.dbcp.as_ref())
+            .await?;
+        let total = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM entity_links WHERE def_id = $1")
+            .bind(&def_id.as_str())
+            .fetch_one(self.dbcp.as_ref())
+            .await?;
+
+        Ok(Page::new(items, pagination_opts, total))
+    }
+
+    /// List the entity links (optionally, only the ones with `def_id`) using keyset pagination.<br/>
+    /// They are sorted by their definition id and id, and the page starts right after the provided cursor.<br/>
+    /// Note that their attributes are not loaded.
+    pub async fn list_after(&self, def_id: Option<&Id>, after: Option<&Cursor>, limit: i64) -> AppResult<CursorPage<EntityLink>> {
+        //
+        // FYI: The conditions are included only when needed, so that the supporting index can be used.
+        let mut qb = QueryBuilder::<Postgres>::new(
+            "SELECT el.id, el.def_id, el.source_entity_id, el.target_entity_id, eld.name as kind 
+             FROM entity_links el 
+             JOIN entity_link_defs eld ON el.def_id = eld.id 
+             WHERE TRUE",
+        );
+        if let Some(def_id) = def_id {
+            qb.push(" AND el.def_id = ").push_bind(def_id.as_str());
+        }
+        if let Some(cursor) = after {
+            qb.push(" AND (el.def_id, el.id) > (")
+                .push_bind(cursor.sort_key.as_str())
+                .push(", ")
+                .push_bind(cursor.id.as_str())
+                .push(")");
+        }
+        qb.push(" ORDER BY el.def_id, el.id LIMIT ").push_bind(limit + 1);
+
+        let items = qb.build_query_as::<EntityLink>().fetch_all(self.dbcp.as_ref()).await?;
+
+        Ok(CursorPage::new(items, limit, |link| {
+            Cursor::new(link.def_id.to_string(), link.id.clone())
+        }))
+    }
+
+    /// List the entity links that have both ends within the provided entity ids.<br/>
+    /// If `def_ids` is not empty, only the links with these definitions are included.<br/>
+    /// Note that their attributes are not loaded.
+    pub async fn list_by_entity_ids(&self, ent_ids: &[Id], def_ids: &[Id]) -> AppResult<Vec<EntityLink>> {
+        //
+        let ent_ids: Vec<&str> = ent_ids.iter().map(|id| id.as_str()).collect();
+        let def_ids: Vec<&str> = def_ids.iter().map(|id| id.as_str()).collect();
+        let query = "SELECT el.id, el.def_id, el.source_entity_id, el.target_entity_id, eld.name as kind 
+                     FROM entity_links el 
+                     JOIN entity_link_defs eld ON el.def_id = eld.id 
+                     WHERE el.source_entity_id = ANY($1) AND el.target_entity_id = ANY($1)
+                       AND (cardinality($2::text[]) = 0 OR el.def_id = ANY($2))";
+        sqlx::query_as::<_, EntityLink>(query)
+            .bind(ent_ids)
+            .bind(def_ids)
             .fetch_all(self.dbcp.as_ref())
             .await
             .map(|res| AppResult::Ok(res))?
     }
 
-    /// List all the entity links by `def_id`.<br/>
+    /// List the ids of the entities that are reachable from the provided one through at most `depth` links,
+    /// in any direction, including the starting entity itself.<br/>
+    /// If `def_ids` is not empty, only the links with these definitions are followed.
+    pub async fn list_reachable_entity_ids(&self, start_ent_id: &Id, depth: i32, def_ids: &[Id]) -> AppResult<Vec<Id>> {
+        //
+        let def_ids: Vec<&str> = def_ids.iter().map(|id| id.as_str()).collect();
+        let query = "WITH RECURSIVE reach(ent_id, depth) AS (
+                         SELECT $1::text, 0
+                         UNION
+                         SELECT (CASE WHEN el.source_entity_id = r.ent_id THEN el.target_entity_id ELSE el.source_entity_id END)::text,
+                                r.depth + 1
+                         FROM reach r
+                         JOIN entity_links el ON el.source_entity_id = r.ent_id OR el.target_entity_id = r.ent_id
+                         WHERE r.depth < $2 AND (cardinality($3::text[]) = 0 OR el.def_id = ANY($3))
+                     )
+                     SELECT DISTINCT ent_id FROM reach";
+        let rows = sqlx::query(query)
+            .bind(start_ent_id.as_str())
+            .bind(depth)
+            .bind(def_ids)
+            .fetch_all(self.dbcp.as_ref())
+            .await?;
+        Ok(rows.into_iter().map(|row| Id::new_from(row.get("ent_id"))).collect())
+    }
+
+    /// List the entity links that have the provided entity as source or target.<br/>
     /// Note that their attributes are not loaded.
-    pub async fn list_by_def_id(&self, def_id: &Id) -> AppResult<Vec<EntityLink>> {
+    pub async fn list_by_entity_id(&self, ent_id: &Id) -> AppResult<Vec<EntityLink>> {
         //
         let query = "SELECT el.id, el.def_id, el.source_entity_id, el.target_entity_id, eld.name as kind 
                      FROM entity_links el 
-                     JOIN entity_link_defs eld ON el.def_id = eld.id  
-                     WHERE el.def_id = $1";
+                     JOIN entity_link_defs eld ON el.def_id = eld.id 
+                     WHERE el.source_entity_id = $1 OR el.target_entity_id = $1";
         sqlx::query_as::<_, EntityLink>(query)
-            .bind(&def_id.as_str())
+            .bind(ent_id.as_str())
             .fetch_all(self.dbcp.as_ref())
             .await
             .map(|res| AppResult::Ok(res))?
     }
 
+    /// List the entity links that have the provided entity as source (if `outgoing`) or as target (if `incoming`),
+    /// optionally only the ones with `def_id`, using keyset pagination.<br/>
+    /// They are sorted by their definition id and id, and the page starts right after the provided cursor.<br/>
+    /// Note that their attributes are not loaded.
+    pub async fn list_by_entity_id_after(
+        &self,
+        ent_id: &Id,
+        outgoing: bool,
+        incoming: bool,
+        def_id: Option<&Id>,
+        after: Option<&Cursor>,
+        limit: i64,
+    ) -> AppResult<CursorPage<EntityLink>> {
+        //
+        let mut qb = QueryBuilder::<Postgres>::new(
+            "SELECT el.id, el.def_id, el.source_entity_id, el.target_entity_id, eld.name as kind 
+             FROM entity_links el 
+             JOIN entity_link_defs eld ON el.def_id = eld.id 
+             WHERE (",
+        );
+        match (outgoing, incoming) {
+            (true, false) => qb.push("el.source_entity_id = ").push_bind(ent_id.as_str()),
+            (false, true) => qb.push("el.target_entity_id = ").push_bind(ent_id.as_str()),
+            _ => qb
+                .push("el.source_entity_id = ")
+                .push_bind(ent_id.as_str())
+                .push(" OR el.target_entity_id = ")
+                .push_bind(ent_id.as_str()),
+        };
+        qb.push(")");
+        if let Some(def_id) = def_id {
+            qb.push(" AND el.def_id = ").push_bind(def_id.as_str());
+        }
+        if let Some(cursor) = after {
+            qb.push(" AND (el.def_id, el.id) > (")
+                .push_bind(cursor.sort_key.as_str())
+                .push(", ")
+                .push_bind(cursor.id.as_str())
+                .push(")");
+        }
+        qb.push(" ORDER BY el.def_id, el.id LIMIT ").push_bind(limit + 1);
+
+        let items = qb.build_query_as::<EntityLink>().fetch_all(self.dbcp.as_ref()).await?;
+
+        Ok(CursorPage::new(items, limit, |link| {
+            Cursor::new(link.def_id.to_string(), link.id.clone())
+        }))
+    }
+
     pub async fn add(&self, ent_link: &EntityLink) -> AppResult<()> {
         //
         log::debug!("Adding entity link: '{:?}'.", ent_link);
@@ -135,6 +311,12 @@ impl EntityLinkRepo {
             }
         }
 
+        if let Err(e) = SearchRepo::index_entity_link(&mut *txn, &ent_link.id).await {
+            txn.rollback().await?;
+            log::error!("Failed to index entity link id:'{}' for search. Cause: '{}'.", &ent_link.id, e);
+            return AppResult::Err(e.into());
+        }
+
         txn.commit().await?;
 
         Ok(())
@@ -313,6 +495,12 @@ impl EntityLinkRepo {
             }
         }
 
+        if let Err(e) = SearchRepo::index_entity_link(&mut *txn, &item.id).await {
+            txn.rollback().await?;
+            log::error!("Failed to index entity link id:'{}' for search. Cause: '{}'.", &item.id, e);
+            return AppResult::Err(e.into());
+        }
+
         txn.commit().await?;
 
         Ok(())
@@ -343,6 +531,11 @@ impl EntityLinkRepo {
             return AppResult::Err(e.into());
         }
 
+        if let Err(e) = SearchRepo::unindex(&mut *txn, id).await {
+            log::error!("Failed to remove entity link id:'{}' from search index. Cause: '{}'.", id, e);
+            return AppResult::Err(e.into());
+        }
+
         txn.commit().await?;
 
         Ok(())
@@ -1,9 +1,12 @@
 use crate::{
-    domain::model::{AttributeValueType, BooleanAttribute, Entity, Id, IntegerAttribute, SmallintAttribute, TextAttribute},
-    server::{AppResult, Pagination},
+    domain::model::{
+        AttributeDef, AttributeFilter, AttributeRef, AttributeValueType, BooleanAttribute, Cursor, CursorPage, Entity, EntityGraphFilter,
+        FilterOp, FilterValue, GraphExportAttribute, Id, IntegerAttribute, Page, Pagination, SmallintAttribute, TextAttribute,
+    },
+    server::{AppError, AppResult, SearchRepo},
     ui::pages::Name,
 };
-use sqlx::{postgres::PgRow, FromRow, PgPool, Row};
+use sqlx::{postgres::PgRow, FromRow, PgConnection, PgPool, Postgres, QueryBuilder, Row};
 use std::sync::Arc;
 
 pub struct EntityRepo {
@@ -12,43 +15,283 @@ pub struct EntityRepo {
 
 impl EntityRepo {
     //
+    /// The maximum number of rows inserted by one statement, when adding entities in batch.
+    pub const BATCH_SIZE: usize = 500;
+
+    /// The fields that the entities can be sorted by, and their columns.
+    pub const SORTABLE: &'static [(&'static str, &'static str)] = &[
+        ("kind", "ed.name"),
+        ("listing_attr_name", "e.listing_attr_name"),
+        ("listing_attr_value", "e.listing_attr_value"),
+    ];
+
     pub fn new(dbcp: Arc<PgPool>) -> Self {
         Self { dbcp }
     }
 
-    /// List all the entities.<br/>
+    /// List the entities, as per the provided pagination options.<br/>
+    /// If no pagination is provided, all the entities are returned.<br/>
     /// Note that the attributes of the entities are not loaded.
-    pub async fn list(&self, pagination_opts: Option<&Pagination>) -> AppResult<Vec<Entity>> {
+    pub async fn list(&self, pagination_opts: Option<&Pagination>) -> AppResult<Page<Entity>> {
         //
-        let (offset, limit) = Pagination::from(pagination_opts).get_offset_limit();
+        let (offset, limit) = Pagination::get_offset_limit_opt(pagination_opts);
+        let order_by = self.get_order_by(pagination_opts).await?;
         let query = format!(
             "SELECT e.id, e.def_id, e.listing_attr_def_id, e.listing_attr_name, e.listing_attr_value, ed.name as kind 
              FROM entities e 
              JOIN entity_defs ed ON e.def_id = ed.id 
-             ORDER BY name LIMIT {limit} OFFSET {offset}"
+             ORDER BY {order_by}, e.id LIMIT $1 OFFSET $2"
+        );
+
+        let items = sqlx::query_as::<_, Entity>(query.as_str())
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(self.dbcp.as_ref())
+            .await?;
+        let total = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM entities")
+            .fetch_one(self.dbcp.as_ref())
+            .await?;
+
+        Ok(Page::new(items, pagination_opts, total))
+    }
+
+    /// List the entities by `def_id`, as per the provided pagination options.<br/>
+    /// If no pagination is provided, all the entities with that definition are returned.<br/>
+    /// Note that the attributes of the entities are not loaded.
+    pub async fn list_by_def_id(&self, def_id: &Id, pagination_opts: Option<&Pagination>) -> AppResult<Page<Entity>> {
+        //
+        let (offset, limit) = Pagination::get_offset_limit_opt(pagination_opts);
+        let order_by = self.get_order_by(pagination_opts).await?;
+        let query = format!(
+            "SELECT e.id, e.def_id, e.listing_attr_def_id, e.listing_attr_name, e.listing_attr_value, ed.name as kind 
+             FROM entities e 
+             JOIN entity_defs ed ON e.def_id = ed.id 
+             WHERE e.def_id = $1 
+             ORDER BY {order_by}, e.id LIMIT $2 OFFSET $3"
+        );
+
+        let items = sqlx::query_as::<_, Entity>(query.as_str())
+            .bind(&def_id.as_str())
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(self.dbcp.as_ref())
+            .await?;
+        let total = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM entities WHERE def_id = $1")
+            .bind(&def_id.as_str())
+            .fetch_one(self.dbcp.as_ref())
+            .await?;
+
+        Ok(Page::new(items, pagination_opts, total))
+    }
+
+    /// List the entities (optionally, only the ones with `def_id`) using keyset pagination.<br/>
+    /// They are sorted by their listing value and id, and the page starts right after the provided cursor.
+    /// Unlike the offset based paging, the pages are stable while new entities are added.<br/>
+    /// Note that the attributes of the entities are not loaded.
+    pub async fn list_after(&self, def_id: Option<&Id>, after: Option<&Cursor>, limit: i64) -> AppResult<CursorPage<Entity>> {
+        //
+        // FYI: The conditions are included only when needed, so that the supporting indexes can be used.
+        let mut qb = QueryBuilder::<Postgres>::new(
+            "SELECT e.id, e.def_id, e.listing_attr_def_id, e.listing_attr_name, e.listing_attr_value, ed.name as kind 
+             FROM entities e 
+             JOIN entity_defs ed ON e.def_id = ed.id 
+             WHERE TRUE",
+        );
+        if let Some(def_id) = def_id {
+            qb.push(" AND e.def_id = ").push_bind(def_id.as_str());
+        }
+        if let Some(cursor) = after {
+            qb.push(" AND (e.listing_attr_value, e.id) > (")
+                .push_bind(cursor.sort_key.as_str())
+                .push(", ")
+                .push_bind(cursor.id.as_str())
+                .push(")");
+        }
+        qb.push(" ORDER BY e.listing_attr_value, e.id LIMIT ").push_bind(limit + 1);
+
+        let items = qb.build_query_as::<Entity>().fetch_all(self.dbcp.as_ref()).await?;
+
+        Ok(CursorPage::new(items, limit, |ent| {
+            Cursor::new(ent.listing_attr_value.clone(), ent.id.clone())
+        }))
+    }
+
+    /// Get the `ORDER BY` expression for listing the entities.<br/>
+    /// Besides the `SORTABLE` fields, the entities can be sorted by the value of an attribute, using its definition id.
+    async fn get_order_by(&self, pagination_opts: Option<&Pagination>) -> AppResult<String> {
+        //
+        if let Some(pagination) = pagination_opts {
+            if let (Some(sort_by), None) = (&pagination.sort_by, pagination.get_sort_column(Self::SORTABLE)) {
+                let attr_def = sqlx::query_as::<_, (String, String)>("SELECT id, value_type FROM attribute_defs WHERE id = $1")
+                    .bind(sort_by)
+                    .fetch_optional(self.dbcp.as_ref())
+                    .await?;
+                if let Some((attr_def_id, value_type)) = attr_def {
+                    // FYI: The id included in the query is the one that is stored, not the one provided by the caller.
+                    return Ok(format!(
+                        "(SELECT a.value FROM {}_attributes a WHERE a.owner_id = e.id AND a.def_id = '{}') {} NULLS LAST",
+                        AttributeValueType::from(value_type),
+                        attr_def_id,
+                        pagination.get_sort_direction()
+                    ));
+                }
+            }
+        }
+        Ok(Pagination::get_order_by(pagination_opts, Self::SORTABLE, "ed.name"))
+    }
+
+    /// List the entities by `def_id` whose attributes values match the provided filter.<br/>
+    /// The attributes referred by the filter must be part of the entity definition.<br/>
+    /// Note that the attributes of the entities are not loaded.
+    pub async fn list_by_def_id_and_filter(
+        &self,
+        def_id: &Id,
+        filter: &AttributeFilter,
+        pagination_opts: Option<&Pagination>,
+    ) -> AppResult<Page<Entity>> {
+        //
+        let attr_defs = sqlx::query_as::<_, AttributeDef>(
+            "SELECT id, name, description, value_type, default_value, required, tag_id 
+             FROM attribute_defs ad JOIN entity_defs_attribute_defs_xref edad ON ad.id = edad.attribute_def_id 
+             WHERE edad.entity_def_id = $1",
+        )
+        .bind(def_id.as_str())
+        .fetch_all(self.dbcp.as_ref())
+        .await?;
+
+        let mut qb = QueryBuilder::<Postgres>::new(
+            "SELECT e.id, e.def_id, e.listing_attr_def_id, e.listing_attr_name, e.listing_attr_value, ed.name as kind 
+             FROM entities e 
+             JOIN entity_defs ed ON e.def_id = ed.id 
+             WHERE e.def_id = ",
         );
+        qb.push_bind(def_id.as_str()).push(" AND ");
+        push_attr_filter(&mut qb, filter, &attr_defs)?;
+        let (offset, limit) = Pagination::get_offset_limit_opt(pagination_opts);
+        let order_by = self.get_order_by(pagination_opts).await?;
+        qb.push(format!(" ORDER BY {order_by}, e.id LIMIT "));
+        qb.push_bind(limit).push(" OFFSET ").push_bind(offset);
+        log::debug!("[list_by_def_id_and_filter] Query: '{}'.", qb.sql());
+
+        let items = qb.build_query_as::<Entity>().fetch_all(self.dbcp.as_ref()).await?;
+
+        let mut count_qb = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM entities e WHERE e.def_id = ");
+        count_qb.push_bind(def_id.as_str()).push(" AND ");
+        push_attr_filter(&mut count_qb, filter, &attr_defs)?;
+        let total = count_qb.build_query_scalar::<i64>().fetch_one(self.dbcp.as_ref()).await?;
 
-        sqlx::query_as::<_, Entity>(query.as_str())
+        Ok(Page::new(items, pagination_opts, total))
+    }
+
+    /// List the entities that match the provided graph filter.<br/>
+    /// An entity matches a tag if its definition, or any of its definition's attributes, is tagged with it.<br/>
+    /// Note that the attributes of the entities are not loaded.
+    pub async fn list_by_graph_filter(&self, filter: &EntityGraphFilter) -> AppResult<Vec<Entity>> {
+        //
+        let def_ids: Vec<&str> = filter.ent_def_ids.iter().map(|id| id.as_str()).collect();
+        let tag_ids: Vec<&str> = filter.tag_ids.iter().map(|id| id.as_str()).collect();
+        let query = "SELECT e.id, e.def_id, e.listing_attr_def_id, e.listing_attr_name, e.listing_attr_value, ed.name as kind 
+                     FROM entities e 
+                     JOIN entity_defs ed ON e.def_id = ed.id 
+                     WHERE (cardinality($1::text[]) = 0 OR e.def_id = ANY($1))
+                       AND (cardinality($2::text[]) = 0
+                            OR EXISTS (SELECT 1 FROM entity_def_tags_xref edt 
+                                       WHERE edt.entity_def_id = e.def_id AND edt.tag_id = ANY($2))
+                            OR EXISTS (SELECT 1 FROM entity_defs_attribute_defs_xref edad 
+                                       JOIN attribute_defs ad ON ad.id = edad.attribute_def_id 
+                                       WHERE edad.entity_def_id = e.def_id AND ad.tag_id = ANY($2)))
+                     ORDER BY e.listing_attr_value";
+        sqlx::query_as::<_, Entity>(query)
+            .bind(def_ids)
+            .bind(tag_ids)
             .fetch_all(self.dbcp.as_ref())
             .await
             .map(|res| AppResult::Ok(res))?
     }
 
-    /// List all the entities by `def_id`.<br/>
+    /// List the entities with the provided ids.<br/>
     /// Note that the attributes of the entities are not loaded.
-    pub async fn list_by_def_id(&self, def_id: &Id) -> AppResult<Vec<Entity>> {
+    pub async fn list_by_ids(&self, ids: &[Id]) -> AppResult<Vec<Entity>> {
         //
+        let ids: Vec<&str> = ids.iter().map(|id| id.as_str()).collect();
         let query = "SELECT e.id, e.def_id, e.listing_attr_def_id, e.listing_attr_name, e.listing_attr_value, ed.name as kind 
                      FROM entities e 
                      JOIN entity_defs ed ON e.def_id = ed.id 
-                     WHERE e.def_id = $1";
+                     WHERE e.id = ANY($1)";
         sqlx::query_as::<_, Entity>(query)
-            .bind(&def_id.as_str())
+            .bind(ids)
             .fetch_all(self.dbcp.as_ref())
             .await
             .map(|res| AppResult::Ok(res))?
     }
 
+    /// Get the values (as text) of the provided attributes of the provided entities.<br/>
+    /// The result contains `(entity id, attribute definition id, value)` entries, just for the existing values.
+    pub async fn list_attributes_values(&self, ent_ids: &[Id], attr_def_ids: &[Id]) -> AppResult<Vec<(Id, Id, String)>> {
+        //
+        if ent_ids.is_empty() || attr_def_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let ent_ids: Vec<&str> = ent_ids.iter().map(|id| id.as_str()).collect();
+        let attr_def_ids: Vec<&str> = attr_def_ids.iter().map(|id| id.as_str()).collect();
+        let query = ["text", "smallint", "integer", "bigint", "real", "boolean", "date", "timestamp"]
+            .iter()
+            .map(|value_type| {
+                format!(
+                    "SELECT owner_id, def_id, value::text AS value FROM {value_type}_attributes 
+                 WHERE owner_id = ANY($1) AND def_id = ANY($2) AND value IS NOT NULL"
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(" UNION ALL ");
+
+        let rows = sqlx::query(query.as_str())
+            .bind(ent_ids)
+            .bind(attr_def_ids)
+            .fetch_all(self.dbcp.as_ref())
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| (Id::new_from(row.get("owner_id")), Id::new_from(row.get("def_id")), row.get("value")))
+            .collect())
+    }
+
+    /// Get all the attributes (as name, type and text value) of the provided owners.<br/>
+    /// Since the attributes tables are shared, the owners can be entities as well as entity links.
+    pub async fn list_attributes_by_owner_ids(&self, owner_ids: &[Id]) -> AppResult<Vec<(Id, GraphExportAttribute)>> {
+        //
+        if owner_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let owner_ids: Vec<&str> = owner_ids.iter().map(|id| id.as_str()).collect();
+        let query = ["text", "smallint", "integer", "bigint", "real", "boolean", "date", "timestamp"]
+            .iter()
+            .map(|value_type| {
+                format!(
+                    "SELECT a.owner_id, ad.id AS def_id, ad.name, ad.value_type, a.value::text AS value FROM {value_type}_attributes a 
+                 JOIN attribute_defs ad ON a.def_id = ad.id 
+                 WHERE a.owner_id = ANY($1) AND a.value IS NOT NULL"
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(" UNION ALL ");
+        let query = format!("{query} ORDER BY owner_id, name");
+
+        let rows = sqlx::query(query.as_str()).bind(owner_ids).fetch_all(self.dbcp.as_ref()).await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let attr = GraphExportAttribute {
+                    def_id: Id::new_from(row.get("def_id")),
+                    name: row.get("name"),
+                    value_type: AttributeValueType::from(row.get::<&str, &str>("value_type")),
+                    value: row.get("value"),
+                };
+                (Id::new_from(row.get("owner_id")), attr)
+            })
+            .collect())
+    }
+
     pub async fn list_refs_by_def_id(&self, def_id: &Id) -> AppResult<Vec<(Id, Name)>> {
         //
         let res = sqlx::query_as::<_, (String, Name)>(
@@ -171,9 +414,20 @@ impl EntityRepo {
 
     pub async fn add(&self, ent: &Entity) -> AppResult<()> {
         //
-        log::debug!("Adding entity: '{:?}'.", ent);
-
         let mut txn = self.dbcp.begin().await?;
+        if let Err(e) = Self::add_in(&mut txn, ent).await {
+            txn.rollback().await?;
+            return AppResult::Err(e);
+        }
+        txn.commit().await?;
+        Ok(())
+    }
+
+    /// Add an entity (with its attributes and its search document) using the provided connection,
+    /// thus as part of the caller's transaction.
+    pub async fn add_in(conn: &mut PgConnection, ent: &Entity) -> AppResult<()> {
+        //
+        log::debug!("Adding entity: '{:?}'.", ent);
 
         if let Err(e) = sqlx::query(
             "INSERT INTO entities (id, def_id, listing_attr_def_id, listing_attr_name, listing_attr_value) 
@@ -184,10 +438,9 @@ impl EntityRepo {
         .bind(&ent.listing_attr_def_id.as_str())
         .bind(&ent.listing_attr_name)
         .bind(&ent.listing_attr_value)
-        .execute(&mut *txn)
+        .execute(&mut *conn)
         .await
         {
-            txn.rollback().await?;
             log::error!("Failed to add entity. Cause: '{}'.", e);
             return AppResult::Err(e.into());
         }
@@ -198,10 +451,9 @@ impl EntityRepo {
                 .bind(&ent.id.as_str())
                 .bind(attr.def_id.as_str())
                 .bind(&attr.value)
-                .execute(&mut *txn)
+                .execute(&mut *conn)
                 .await
             {
-                txn.rollback().await?;
                 log::error!("Failed to add entity's text attribute: {}", e);
                 return AppResult::Err(e.into());
             }
@@ -213,10 +465,9 @@ impl EntityRepo {
                 .bind(&ent.id.as_str())
                 .bind(attr.def_id.as_str())
                 .bind(attr.value)
-                .execute(&mut *txn)
+                .execute(&mut *conn)
                 .await
             {
-                txn.rollback().await?;
                 log::error!("Failed to add an entity smallint attribute. Cause: {}", e);
                 return AppResult::Err(e.into());
             }
@@ -228,10 +479,9 @@ impl EntityRepo {
                 .bind(&ent.id.as_str())
                 .bind(attr.def_id.as_str())
                 .bind(attr.value)
-                .execute(&mut *txn)
+                .execute(&mut *conn)
                 .await
             {
-                txn.rollback().await?;
                 log::error!("Failed to add an entity integer attribute. Cause: {}", e);
                 return AppResult::Err(e.into());
             }
@@ -243,19 +493,107 @@ impl EntityRepo {
                 .bind(&ent.id.as_str())
                 .bind(attr.def_id.as_str())
                 .bind(attr.value)
-                .execute(&mut *txn)
+                .execute(&mut *conn)
                 .await
             {
-                txn.rollback().await?;
                 log::error!("Failed to add an entity boolean attribute. Cause: {}", e);
                 return AppResult::Err(e.into());
             }
         }
 
+        if let Err(e) = SearchRepo::index_entity(&mut *conn, &ent.id).await {
+            log::error!("Failed to index entity id:'{}' for search. Cause: '{}'.", &ent.id, e);
+            return AppResult::Err(e.into());
+        }
+
+        Ok(())
+    }
+
+    /// Add multiple entities in one transaction.<br/>
+    /// The rows are inserted using multi-row `INSERT` statements, each one having at most `BATCH_SIZE` rows.
+    pub async fn add_batch(&self, ents: &[Entity]) -> AppResult<()> {
+        //
+        log::debug!("Adding a batch of {} entities.", ents.len());
+
+        let mut txn = self.dbcp.begin().await?;
+
+        for chunk in ents.chunks(Self::BATCH_SIZE) {
+            let mut qb = QueryBuilder::<Postgres>::new(
+                "INSERT INTO entities (id, def_id, listing_attr_def_id, listing_attr_name, listing_attr_value) ",
+            );
+            qb.push_values(chunk, |mut b, ent| {
+                b.push_bind(ent.id.as_str().to_string())
+                    .push_bind(ent.def_id.as_str().to_string())
+                    .push_bind(ent.listing_attr_def_id.as_str().to_string())
+                    .push_bind(ent.listing_attr_name.clone())
+                    .push_bind(ent.listing_attr_value.clone());
+            });
+            if let Err(e) = qb.build().execute(&mut *txn).await {
+                txn.rollback().await?;
+                log::error!("Failed to add a batch of entities. Cause: '{}'.", e);
+                return AppResult::Err(e.into());
+            }
+        }
+
+        let text_attrs: Vec<(&Id, &Id, String)> = ents
+            .iter()
+            .flat_map(|ent| ent.text_attributes.iter().map(move |a| (&ent.id, &a.def_id, a.value.clone())))
+            .collect();
+        let smallint_attrs: Vec<(&Id, &Id, i16)> = ents
+            .iter()
+            .flat_map(|ent| ent.smallint_attributes.iter().map(move |a| (&ent.id, &a.def_id, a.value)))
+            .collect();
+        let int_attrs: Vec<(&Id, &Id, i32)> = ents
+            .iter()
+            .flat_map(|ent| ent.int_attributes.iter().map(move |a| (&ent.id, &a.def_id, a.value)))
+            .collect();
+        let boolean_attrs: Vec<(&Id, &Id, bool)> = ents
+            .iter()
+            .flat_map(|ent| ent.boolean_attributes.iter().map(move |a| (&ent.id, &a.def_id, a.value)))
+            .collect();
+
+        let res = async {
+            Self::add_attributes_batch(&mut *txn, "text_attributes", &text_attrs).await?;
+            Self::add_attributes_batch(&mut *txn, "smallint_attributes", &smallint_attrs).await?;
+            Self::add_attributes_batch(&mut *txn, "integer_attributes", &int_attrs).await?;
+            Self::add_attributes_batch(&mut *txn, "boolean_attributes", &boolean_attrs).await
+        }
+        .await;
+        if let Err(e) = res {
+            txn.rollback().await?;
+            log::error!("Failed to add the attributes of a batch of entities. Cause: '{}'.", e);
+            return AppResult::Err(e.into());
+        }
+
+        let ids: Vec<Id> = ents.iter().map(|ent| ent.id.clone()).collect();
+        if let Err(e) = SearchRepo::index_entities(&mut *txn, &ids).await {
+            txn.rollback().await?;
+            log::error!("Failed to index a batch of entities for search. Cause: '{}'.", e);
+            return AppResult::Err(e.into());
+        }
+
         txn.commit().await?;
         Ok(())
     }
 
+    /// Insert the provided (owner id, definition id, value) attributes into the provided attributes table.
+    async fn add_attributes_batch<T>(conn: &mut PgConnection, table: &str, attrs: &[(&Id, &Id, T)]) -> Result<(), sqlx::Error>
+    where
+        T: for<'q> sqlx::Encode<'q, Postgres> + sqlx::Type<Postgres> + Clone + Send + 'static,
+    {
+        for chunk in attrs.chunks(Self::BATCH_SIZE) {
+            let mut qb = QueryBuilder::<Postgres>::new(format!("INSERT INTO {} (id, owner_id, def_id, value) ", table));
+            qb.push_values(chunk, |mut b, (owner_id, def_id, value)| {
+                b.push_bind(Id::new().to_string())
+                    .push_bind(owner_id.as_str().to_string())
+                    .push_bind(def_id.as_str().to_string())
+                    .push_bind(value.clone());
+            });
+            qb.build().execute(&mut *conn).await?;
+        }
+        Ok(())
+    }
+
     pub async fn update(&self, ent: &Entity) -> AppResult<()> {
         //
         let mut txn = self.dbcp.begin().await?;
@@ -404,13 +742,19 @@ impl EntityRepo {
             }
         }
 
+        if let Err(e) = SearchRepo::index_entity(&mut *txn, &ent.id).await {
+            txn.rollback().await?;
+            log::error!("Failed to index entity id:'{}' for search. Cause: '{}'.", &ent.id, e);
+            return AppResult::Err(e.into());
+        }
+
         txn.commit().await?;
         Ok(())
     }
 
     pub async fn update_listing_attr_name_value_by_ent_def_id(&self, ent_def_id: &Id, attr_id: &Id) -> AppResult<()> {
         //
-        let ents = self.list_by_def_id(&ent_def_id).await?;
+        let ents = self.list_by_def_id(&ent_def_id, None).await?.items;
         log::debug!("[update_listing_attr_name_value] Found ents: {:?}", ents);
         if ents.is_empty() {
             return AppResult::Ok(());
@@ -456,6 +800,10 @@ impl EntityRepo {
                 log::error!("Failed to update listing attr name and value, based on attr_id: '{attr_id}' of all entities with def_id: '{ent_def_id}'. Cause: {e}");
                 return AppResult::Err(e.into());
             }
+            if let Err(e) = SearchRepo::index_entity(&mut *txn, &ent.id).await {
+                log::error!("Failed to index entity id:'{}' for search. Cause: '{}'.", &ent.id, e);
+                return AppResult::Err(e.into());
+            }
             log::debug!(
                 "Updated listing attr name:'{}' and value:'{}' of entity w/ id: '{}'.",
                 &ent.listing_attr_name,
@@ -482,7 +830,15 @@ impl EntityRepo {
     pub async fn remove(&self, id: &Id) -> AppResult<()> {
         //
         let mut txn = self.dbcp.begin().await?;
+        Self::remove_in(&mut txn, id).await?;
+        txn.commit().await?;
+        Ok(())
+    }
 
+    /// Remove an entity (with its attributes and its search document) using the provided connection,
+    /// thus as part of the caller's transaction.
+    pub async fn remove_in(conn: &mut PgConnection, id: &Id) -> AppResult<()> {
+        //
         if let Err(e) = sqlx::query(
             "WITH del_text_attrs AS (DELETE FROM text_attributes WHERE owner_id = $1 RETURNING *),
               del_smallint_attrs AS (DELETE FROM smallint_attributes WHERE owner_id = $1 RETURNING *),
@@ -491,7 +847,7 @@ impl EntityRepo {
             SELECT * FROM del_text_attrs, del_smallint_attrs, del_int_attrs, del_boolean_attrs",
         )
         .bind(id.as_str())
-        .execute(&mut *txn)
+        .execute(&mut *conn)
         .await
         {
             log::error!("Failed to delete the attributes of entity id:'{}': '{}'.", id, e);
@@ -500,14 +856,18 @@ impl EntityRepo {
 
         if let Err(e) = sqlx::query("DELETE FROM entities WHERE id = $1")
             .bind(id.as_str())
-            .execute(&mut *txn)
+            .execute(&mut *conn)
             .await
         {
             log::error!("Failed to delete entity by id:'{}': '{}'.", id, e);
             return AppResult::Err(e.into());
         }
 
-        txn.commit().await?;
+        if let Err(e) = SearchRepo::unindex(&mut *conn, id).await {
+            log::error!("Failed to remove entity id:'{}' from search index. Cause: '{}'.", id, e);
+            return AppResult::Err(e.into());
+        }
+
         Ok(())
     }
 }
@@ -530,6 +890,132 @@ impl FromRow<'_, PgRow> for Entity {
     }
 }
 
+/// Compile an attribute filter into the (parameterized) SQL condition of a query on `entities e`.<br/>
+/// Each condition is checked against the typed attributes table of the referred attribute definition.
+fn push_attr_filter(qb: &mut QueryBuilder<'_, Postgres>, filter: &AttributeFilter, attr_defs: &[AttributeDef]) -> AppResult<()> {
+    //
+    match filter {
+        AttributeFilter::And(filters) | AttributeFilter::Or(filters) => {
+            let is_and = matches!(filter, AttributeFilter::And(_));
+            if filters.is_empty() {
+                qb.push(if is_and { "TRUE" } else { "FALSE" });
+                return Ok(());
+            }
+            qb.push("(");
+            for (i, f) in filters.iter().enumerate() {
+                if i > 0 {
+                    qb.push(if is_and { " AND " } else { " OR " });
+                }
+                push_attr_filter(qb, f, attr_defs)?;
+            }
+            qb.push(")");
+        }
+        AttributeFilter::Condition { attr, op } => {
+            let attr_def = attr_defs
+                .iter()
+                .find(|ad| match attr {
+                    AttributeRef::Id(id) => ad.id == *id,
+                    AttributeRef::Name(name) => ad.name == *name,
+                })
+                .ok_or_else(|| AppError::Err(format!("Attribute '{}' is not part of the entity definition.", attr)))?;
+            // The attributes tables are named after the (PostgreSQL) value type.
+            let table = format!("{}_attributes", attr_def.value_type);
+            if *op == FilterOp::IsNull {
+                qb.push(format!(
+                    "NOT EXISTS (SELECT 1 FROM {table} a WHERE a.owner_id = e.id AND a.def_id = "
+                ));
+                qb.push_bind(attr_def.id.to_string());
+                qb.push(")");
+                return Ok(());
+            }
+            qb.push(format!("EXISTS (SELECT 1 FROM {table} a WHERE a.owner_id = e.id AND a.def_id = "));
+            qb.push_bind(attr_def.id.to_string());
+            qb.push(" AND ");
+            match op {
+                FilterOp::Eq(value) => push_attr_comparison(qb, attr_def, "=", value)?,
+                FilterOp::Neq(value) => push_attr_comparison(qb, attr_def, "<>", value)?,
+                FilterOp::Lt(value) => push_attr_comparison(qb, attr_def, "<", value)?,
+                FilterOp::Gt(value) => push_attr_comparison(qb, attr_def, ">", value)?,
+                FilterOp::Contains(text) => {
+                    if attr_def.value_type != AttributeValueType::Text {
+                        return Err(AppError::Err(format!(
+                            "The 'contains' operation cannot be used on attribute '{}' of type {}.",
+                            attr_def.name,
+                            attr_def.value_type.label()
+                        )));
+                    }
+                    qb.push("strpos(lower(a.value), lower(");
+                    qb.push_bind(text.clone());
+                    qb.push(")) > 0");
+                }
+                FilterOp::In(values) => {
+                    if values.is_empty() {
+                        qb.push("FALSE");
+                    } else {
+                        qb.push("a.value IN (");
+                        for (i, value) in values.iter().enumerate() {
+                            if i > 0 {
+                                qb.push(", ");
+                            }
+                            push_attr_value(qb, attr_def, value)?;
+                        }
+                        qb.push(")");
+                    }
+                }
+                FilterOp::IsNull => {}
+            }
+            qb.push(")");
+        }
+    }
+    Ok(())
+}
+
+fn push_attr_comparison(
+    qb: &mut QueryBuilder<'_, Postgres>,
+    attr_def: &AttributeDef,
+    operator: &str,
+    value: &FilterValue,
+) -> AppResult<()> {
+    //
+    qb.push(format!("a.value {operator} "));
+    push_attr_value(qb, attr_def, value)
+}
+
+/// Bind a filter value, cast to the value type of the attribute definition.
+fn push_attr_value(qb: &mut QueryBuilder<'_, Postgres>, attr_def: &AttributeDef, value: &FilterValue) -> AppResult<()> {
+    //
+    let value_type = &attr_def.value_type;
+    match (value_type, value) {
+        (AttributeValueType::Text, FilterValue::Text(text)) => {
+            qb.push_bind(text.clone());
+        }
+        (AttributeValueType::Date | AttributeValueType::DateTime, FilterValue::Text(text)) => {
+            qb.push_bind(text.clone()).push(format!("::{value_type}"));
+        }
+        (AttributeValueType::SmallInteger | AttributeValueType::Integer | AttributeValueType::BigInteger, FilterValue::Integer(num)) => {
+            qb.push_bind(*num).push(format!("::{value_type}"));
+        }
+        (AttributeValueType::Decimal, FilterValue::Integer(num)) => {
+            qb.push_bind(*num as f64).push("::real");
+        }
+        (AttributeValueType::Decimal, FilterValue::Real(num)) => {
+            qb.push_bind(*num).push("::real");
+        }
+        (AttributeValueType::Boolean, FilterValue::Boolean(b)) => {
+            qb.push_bind(*b);
+        }
+        _ => {
+            return Err(AppError::Err(format!(
+                "The value {:?} does not match the type ({}) of attribute '{}'.",
+                value,
+                value_type.label(),
+                attr_def.name
+            )));
+        }
+    }
+    Ok(())
+}
+
 fn fill_in_entity_attributes(item: &mut Entity, rows: Vec<PgRow>) {
     //
     item.attributes_order = Vec::with_capacity(rows.len());
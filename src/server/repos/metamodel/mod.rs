@@ -1,12 +1,18 @@
 mod attr_def_repo;
 pub use attr_def_repo::*;
 
+mod bundle_repo;
+pub use bundle_repo::*;
+
 mod tags_repo;
 pub use tags_repo::*;
 
 mod ent_def_repo;
 pub use ent_def_repo::*;
 
+mod ent_export_repo;
+pub use ent_export_repo::*;
+
 mod ent_repo;
 pub use ent_repo::*;
 
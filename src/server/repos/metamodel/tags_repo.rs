@@ -3,8 +3,8 @@ use std::sync::Arc;
 use sqlx::{postgres::PgRow, FromRow, PgPool, Row};
 
 use crate::{
-    domain::model::{Id, Tag},
-    server::{AppError, AppResult, Pagination},
+    domain::model::{Id, Page, Pagination, Tag},
+    server::{AppError, AppResult},
 };
 
 pub struct TagsRepo {
@@ -13,6 +13,9 @@ pub struct TagsRepo {
 
 impl TagsRepo {
     //
+    /// The fields that the tags can be sorted by, and their columns.
+    pub const SORTABLE: &'static [(&'static str, &'static str)] = &[("name", "name"), ("description", "description")];
+
     pub fn new(dbcp: Arc<PgPool>) -> Self {
         Self { dbcp }
     }
@@ -30,16 +33,25 @@ impl TagsRepo {
         }
     }
 
-    pub async fn list(&self, pagination_opts: Option<&Pagination>) -> AppResult<Vec<Tag>> {
+    /// List the tags, as per the provided pagination options.<br/>
+    /// If no pagination is provided, all the tags are returned.
+    pub async fn list(&self, pagination_opts: Option<&Pagination>) -> AppResult<Page<Tag>> {
         //
-        let (offset, limit) = Pagination::from(pagination_opts).get_offset_limit();
-        let query = format!("SELECT id, name, description FROM tags ORDER BY name LIMIT {limit} OFFSET {offset}");
-        log::debug!("Listing tags w/ limit: {}, offset: {}.", limit, offset);
+        let (offset, limit) = Pagination::get_offset_limit_opt(pagination_opts);
+        let order_by = Pagination::get_order_by(pagination_opts, Self::SORTABLE, "name");
+        let query = format!("SELECT id, name, description FROM tags ORDER BY {order_by}, id LIMIT $1 OFFSET $2");
+        log::debug!("Listing tags w/ limit: {:?}, offset: {}, order by: {}.", limit, offset, order_by);
 
-        sqlx::query_as::<_, Tag>(query.as_str()) // FYI: Binding (such as .bind(limit) didn't work, that's why the query.
+        let items = sqlx::query_as::<_, Tag>(query.as_str())
+            .bind(limit)
+            .bind(offset)
             .fetch_all(self.dbcp.as_ref())
-            .await
-            .map(|res| AppResult::Ok(res))?
+            .await?;
+        let total = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM tags")
+            .fetch_one(self.dbcp.as_ref())
+            .await?;
+
+        Ok(Page::new(items, pagination_opts, total))
     }
 
     pub async fn update(&self, tag: Tag) -> AppResult<()> {
@@ -1,5 +1,23 @@
 mod users_repo;
 pub use users_repo::*;
 
+mod backup_repo;
+pub use backup_repo::*;
+
+mod config_repo;
+pub use config_repo::*;
+
+mod consistency_repo;
+pub use consistency_repo::*;
+
 mod metamodel;
 pub use metamodel::*;
+
+mod registry_repo;
+pub use registry_repo::*;
+
+mod saved_views_repo;
+pub use saved_views_repo::*;
+
+mod search_repo;
+pub use search_repo::*;
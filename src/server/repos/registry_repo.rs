@@ -0,0 +1,277 @@
+use std::sync::Arc;
+
+use sqlx::{postgres::PgRow, PgConnection, PgPool, Row};
+
+use crate::{
+    domain::model::{Entity, HealthStatus, Id, ServiceInstance, ServiceInstanceDef as Def},
+    server::{AppResult, EntityRepo, SearchRepo},
+};
+
+/// The repository of the service instances, that are the entities of the built-in "Service Instance" definition.<br/>
+/// Their attributes are read as columns, and the ones that change often (on heartbeats) are written directly,
+/// without going through the (more expensive) entity update.
+pub struct ServiceRegistryRepo {
+    dbcp: Arc<PgPool>,
+}
+
+impl ServiceRegistryRepo {
+    //
+    pub fn new(dbcp: Arc<PgPool>) -> Self {
+        Self { dbcp }
+    }
+
+    /// List the instances (optionally, only the ones of the provided service), sorted by service and address.
+    pub async fn list(&self, service: Option<&str>) -> AppResult<Vec<ServiceInstance>> {
+        //
+        let query = format!(
+            "SELECT * FROM ({}) i WHERE $1::TEXT IS NULL OR i.service = $1 ORDER BY i.service, i.address, i.id",
+            select_instances()
+        );
+        let items = sqlx::query(&query)
+            .bind(service)
+            .map(|row: PgRow| instance_from_row(&row))
+            .fetch_all(self.dbcp.as_ref())
+            .await?;
+        Ok(items)
+    }
+
+    pub async fn get(&self, id: &Id) -> AppResult<Option<ServiceInstance>> {
+        //
+        let query = format!("SELECT * FROM ({}) i WHERE i.id = $1", select_instances());
+        let item = sqlx::query(&query)
+            .bind(id.as_str())
+            .map(|row: PgRow| instance_from_row(&row))
+            .fetch_optional(self.dbcp.as_ref())
+            .await?;
+        Ok(item)
+    }
+
+    /// List the (distinct) services that have registered instances.
+    pub async fn list_services(&self) -> AppResult<Vec<String>> {
+        //
+        let services = sqlx::query_scalar::<_, String>(
+            "SELECT DISTINCT a.value FROM text_attributes a
+             JOIN entities e ON e.id = a.owner_id AND e.def_id = $1
+             WHERE a.def_id = $2",
+        )
+        .bind(Def::ID)
+        .bind(Def::SERVICE_ATTR_ID)
+        .fetch_all(self.dbcp.as_ref())
+        .await?;
+        Ok(services)
+    }
+
+    /// Register an instance, in a single transaction: if the service already has an instance at the address,
+    /// its provided text values and TTL are updated, otherwise the provided (new) entity is added.
+    /// It returns the id of the instance, and whether it was added.<br/>
+    /// The registrations of the same service and address are serialized by an advisory lock (held until the end of the transaction),
+    /// thus concurrent ones never add duplicate instances.
+    pub async fn register(
+        &self,
+        service: &str,
+        address: &str,
+        text_values: &[(&str, String)],
+        ttl_secs: i32,
+        new_ent: &Entity,
+    ) -> AppResult<(Id, bool)> {
+        //
+        let mut txn = self.dbcp.begin().await?;
+        match Self::register_in(&mut txn, service, address, text_values, ttl_secs, new_ent).await {
+            Ok(res) => {
+                txn.commit().await?;
+                Ok(res)
+            }
+            Err(e) => {
+                txn.rollback().await?;
+                log::error!("Failed to register an instance of '{}' at '{}'. Cause: '{}'.", service, address, e);
+                Err(e)
+            }
+        }
+    }
+
+    async fn register_in(
+        conn: &mut PgConnection,
+        service: &str,
+        address: &str,
+        text_values: &[(&str, String)],
+        ttl_secs: i32,
+        new_ent: &Entity,
+    ) -> AppResult<(Id, bool)> {
+        //
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1 || '@' || $2))")
+            .bind(service)
+            .bind(address)
+            .execute(&mut *conn)
+            .await?;
+        let id = sqlx::query_scalar::<_, String>(
+            "SELECT s.owner_id FROM text_attributes s
+             JOIN text_attributes a ON a.owner_id = s.owner_id AND a.def_id = $3
+             WHERE s.def_id = $1 AND s.value = $2 AND a.value = $4
+             LIMIT 1",
+        )
+        .bind(Def::SERVICE_ATTR_ID)
+        .bind(service)
+        .bind(Def::ADDRESS_ATTR_ID)
+        .bind(address)
+        .fetch_optional(&mut *conn)
+        .await?;
+        let Some(id) = id.map(Id::new_from) else {
+            EntityRepo::add_in(conn, new_ent).await?;
+            return Ok((new_ent.id.clone(), true));
+        };
+
+        let (def_ids, values): (Vec<&str>, Vec<&str>) = text_values.iter().map(|(def_id, value)| (*def_id, value.as_str())).unzip();
+        sqlx::query(
+            "UPDATE text_attributes t SET value = v.value
+             FROM UNNEST($2::TEXT[], $3::TEXT[]) AS v(def_id, value)
+             WHERE t.owner_id = $1 AND t.def_id = v.def_id",
+        )
+        .bind(id.as_str())
+        .bind(def_ids)
+        .bind(values)
+        .execute(&mut *conn)
+        .await?;
+        sqlx::query("UPDATE integer_attributes SET value = $3 WHERE owner_id = $1 AND def_id = $2")
+            .bind(id.as_str())
+            .bind(Def::TTL_ATTR_ID)
+            .bind(ttl_secs)
+            .execute(&mut *conn)
+            .await?;
+        SearchRepo::index_entity(&mut *conn, &id).await?;
+        Ok((id, false))
+    }
+
+    /// Renew the registration of an instance, by setting its latest heartbeat and expiration times.<br/>
+    /// It returns false if there is no such instance.
+    pub async fn renew(&self, id: &Id, last_heartbeat: &str, expires_at: &str) -> AppResult<bool> {
+        //
+        let res = sqlx::query(
+            "UPDATE text_attributes SET value = CASE WHEN def_id = $2 THEN $3 ELSE $5 END
+             WHERE owner_id = $1 AND def_id IN ($2, $4)
+               AND EXISTS (SELECT 1 FROM entities e WHERE e.id = $1 AND e.def_id = $6)",
+        )
+        .bind(id.as_str())
+        .bind(Def::HEARTBEAT_ATTR_ID)
+        .bind(last_heartbeat)
+        .bind(Def::EXPIRES_AT_ATTR_ID)
+        .bind(expires_at)
+        .bind(Def::ID)
+        .execute(self.dbcp.as_ref())
+        .await?;
+        Ok(res.rows_affected() > 0)
+    }
+
+    /// Record the outcome of the latest health check of an instance.
+    pub async fn record_health(
+        &self,
+        id: &Id,
+        status: HealthStatus,
+        latency_ms: i32,
+        failed_checks: i32,
+        last_checked: &str,
+    ) -> AppResult<()> {
+        //
+        let mut txn = self.dbcp.begin().await?;
+        sqlx::query(
+            "UPDATE text_attributes SET value = CASE WHEN def_id = $2 THEN $3 ELSE $5 END
+             WHERE owner_id = $1 AND def_id IN ($2, $4)",
+        )
+        .bind(id.as_str())
+        .bind(Def::HEALTH_STATUS_ATTR_ID)
+        .bind(status.to_string())
+        .bind(Def::LAST_CHECKED_ATTR_ID)
+        .bind(last_checked)
+        .execute(&mut *txn)
+        .await?;
+        sqlx::query(
+            "UPDATE integer_attributes SET value = CASE WHEN def_id = $2 THEN $3 ELSE $5 END
+             WHERE owner_id = $1 AND def_id IN ($2, $4)",
+        )
+        .bind(id.as_str())
+        .bind(Def::LATENCY_ATTR_ID)
+        .bind(latency_ms)
+        .bind(Def::FAILED_CHECKS_ATTR_ID)
+        .bind(failed_checks)
+        .execute(&mut *txn)
+        .await?;
+        txn.commit().await?;
+        Ok(())
+    }
+
+    /// Remove the instances that expired before the provided time, in a single transaction. It returns their ids.<br/>
+    /// The expiry times are locked (and checked again) before removing, thus an instance that is renewed concurrently is not removed.
+    /// The times are compared as text, which is correct since they are all stored as RFC 3339 in UTC, with the same precision.
+    pub async fn remove_expired(&self, now: &str) -> AppResult<Vec<Id>> {
+        //
+        let mut txn = self.dbcp.begin().await?;
+        let ids = sqlx::query_scalar::<_, String>(
+            "SELECT a.owner_id FROM text_attributes a
+             JOIN entities e ON e.id = a.owner_id AND e.def_id = $1
+             WHERE a.def_id = $2 AND a.value < $3
+             FOR UPDATE OF a",
+        )
+        .bind(Def::ID)
+        .bind(Def::EXPIRES_AT_ATTR_ID)
+        .bind(now)
+        .fetch_all(&mut *txn)
+        .await?;
+        let ids: Vec<Id> = ids.into_iter().map(Id::new_from).collect();
+        for id in ids.iter() {
+            EntityRepo::remove_in(&mut txn, id).await?;
+        }
+        txn.commit().await?;
+        Ok(ids)
+    }
+}
+
+/// The query that selects the instances, with their attributes as columns.
+fn select_instances() -> String {
+    //
+    let int =
+        |def_id: &str| format!("COALESCE((SELECT a.value FROM integer_attributes a WHERE a.owner_id = e.id AND a.def_id = '{def_id}'), 0)");
+    let text =
+        |def_id: &str| format!("COALESCE((SELECT a.value FROM text_attributes a WHERE a.owner_id = e.id AND a.def_id = '{def_id}'), '')");
+    format!(
+        "SELECT e.id, {} AS service, {} AS version, {} AS address, {} AS metadata, {} AS tags, {} AS ttl_secs,
+                {} AS last_heartbeat, {} AS expires_at, {} AS health_check, {} AS health_status, {} AS latency_ms,
+                {} AS failed_checks, {} AS last_checked
+         FROM entities e WHERE e.def_id = '{}'",
+        text(Def::SERVICE_ATTR_ID),
+        text(Def::VERSION_ATTR_ID),
+        text(Def::ADDRESS_ATTR_ID),
+        text(Def::METADATA_ATTR_ID),
+        text(Def::TAGS_ATTR_ID),
+        int(Def::TTL_ATTR_ID),
+        text(Def::HEARTBEAT_ATTR_ID),
+        text(Def::EXPIRES_AT_ATTR_ID),
+        text(Def::HEALTH_CHECK_ATTR_ID),
+        text(Def::HEALTH_STATUS_ATTR_ID),
+        int(Def::LATENCY_ATTR_ID),
+        int(Def::FAILED_CHECKS_ATTR_ID),
+        text(Def::LAST_CHECKED_ATTR_ID),
+        Def::ID
+    )
+}
+
+fn instance_from_row(row: &PgRow) -> ServiceInstance {
+    //
+    let metadata: String = row.get("metadata");
+    let tags: String = row.get("tags");
+    let health_status: String = row.get("health_status");
+    ServiceInstance {
+        id: Id::new_from(row.get("id")),
+        service: row.get("service"),
+        version: row.get("version"),
+        address: row.get("address"),
+        metadata: serde_json::from_str(&metadata).unwrap_or_default(),
+        tags: tags.split(',').map(str::trim).filter(|t| !t.is_empty()).map(String::from).collect(),
+        ttl_secs: row.get("ttl_secs"),
+        last_heartbeat: row.get("last_heartbeat"),
+        expires_at: row.get("expires_at"),
+        health_check: row.get("health_check"),
+        health_status: HealthStatus::from(health_status.as_str()),
+        latency_ms: row.get("latency_ms"),
+        failed_checks: row.get("failed_checks"),
+        last_checked: row.get("last_checked"),
+    }
+}
@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use sqlx::{postgres::PgRow, types::Json, FromRow, PgPool, Row};
+
+use crate::{
+    domain::model::{AttributeFilter, Id, SavedView},
+    server::{AppError, AppResult},
+};
+
+pub struct SavedViewsRepo {
+    pub dbcp: Arc<PgPool>,
+}
+
+impl SavedViewsRepo {
+    //
+    pub fn new(dbcp: Arc<PgPool>) -> Self {
+        Self { dbcp }
+    }
+
+    pub async fn get(&self, id: &Id) -> AppResult<Option<SavedView>> {
+        //
+        match sqlx::query_as::<_, SavedView>(
+            "SELECT id, name, owner_id, shared, def_id, filter, sort_by, sort_desc, columns FROM saved_views WHERE id = $1",
+        )
+        .bind(id.as_str())
+        .fetch_one(self.dbcp.as_ref())
+        .await
+        {
+            Ok(view) => Ok(Some(view)),
+            Err(sqlx::Error::RowNotFound) => Ok(None),
+            Err(err) => Err(AppError::from(err)),
+        }
+    }
+
+    /// List the views that are visible to the user, that is the owned ones and the ones shared by others.
+    pub async fn list_visible_to(&self, user_id: &Id) -> AppResult<Vec<SavedView>> {
+        //
+        sqlx::query_as::<_, SavedView>(
+            "SELECT id, name, owner_id, shared, def_id, filter, sort_by, sort_desc, columns FROM saved_views 
+             WHERE owner_id = $1 OR shared 
+             ORDER BY owner_id <> $1, name, id",
+        )
+        .bind(user_id.as_str())
+        .fetch_all(self.dbcp.as_ref())
+        .await
+        .map(|res| AppResult::Ok(res))?
+    }
+
+    pub async fn add(&self, view: &SavedView) -> AppResult<()> {
+        //
+        sqlx::query(
+            "INSERT INTO saved_views (id, name, owner_id, shared, def_id, filter, sort_by, sort_desc, columns) 
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+        )
+        .bind(view.id.as_str())
+        .bind(&view.name)
+        .bind(view.owner_id.as_str())
+        .bind(view.shared)
+        .bind(view.def_id.as_ref().map(|id| id.as_str()))
+        .bind(view.filter.as_ref().map(Json))
+        .bind(&view.sort_by)
+        .bind(view.sort_desc)
+        .bind(view.columns.iter().map(|id| id.as_str()).collect::<Vec<&str>>())
+        .execute(self.dbcp.as_ref())
+        .await
+        .map(|_| Ok(()))?
+    }
+
+    /// Update a view. Its owner cannot be changed.
+    pub async fn update(&self, view: &SavedView) -> AppResult<()> {
+        //
+        sqlx::query(
+            "UPDATE saved_views SET name = $2, shared = $3, def_id = $4, filter = $5, sort_by = $6, sort_desc = $7, columns = $8 
+             WHERE id = $1",
+        )
+        .bind(view.id.as_str())
+        .bind(&view.name)
+        .bind(view.shared)
+        .bind(view.def_id.as_ref().map(|id| id.as_str()))
+        .bind(view.filter.as_ref().map(Json))
+        .bind(&view.sort_by)
+        .bind(view.sort_desc)
+        .bind(view.columns.iter().map(|id| id.as_str()).collect::<Vec<&str>>())
+        .execute(self.dbcp.as_ref())
+        .await
+        .map(|_| Ok(()))?
+    }
+
+    pub async fn remove(&self, id: &Id) -> AppResult<()> {
+        //
+        sqlx::query("DELETE FROM saved_views WHERE id = $1")
+            .bind(id.as_str())
+            .execute(self.dbcp.as_ref())
+            .await
+            .map(|_| Ok(()))?
+    }
+}
+
+impl FromRow<'_, PgRow> for SavedView {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: Id::new_from(row.get("id")),
+            name: row.get("name"),
+            owner_id: Id::new_from(row.get("owner_id")),
+            shared: row.get("shared"),
+            def_id: row.get::<Option<String>, &str>("def_id").map(Id::new_from),
+            filter: row.get::<Option<Json<AttributeFilter>>, &str>("filter").map(|json| json.0),
+            sort_by: row.get("sort_by"),
+            sort_desc: row.get("sort_desc"),
+            columns: row.get::<Vec<String>, &str>("columns").into_iter().map(Id::new_from).collect(),
+        })
+    }
+}
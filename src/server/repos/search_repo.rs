@@ -0,0 +1,213 @@
+use sqlx::{postgres::PgRow, Executor, FromRow, PgConnection, PgPool, Postgres, Row};
+use std::sync::Arc;
+
+use crate::{
+    domain::model::{Id, ItemType, SearchHit},
+    server::AppResult,
+};
+
+/// The repository of the full-text search index (stored in `search_documents` table).<br/>
+/// Besides querying, it provides the functions used by the other repositories to keep the index in sync
+/// with their writes. These accept any executor, so that they can be part of the writes' transactions.
+pub struct SearchRepo {
+    pub dbcp: Arc<PgPool>,
+}
+
+impl SearchRepo {
+    //
+    pub fn new(dbcp: Arc<PgPool>) -> Self {
+        Self { dbcp }
+    }
+
+    /// Search the items that match the provided (already prepared) `tsquery`.<br/>
+    /// The results are ordered by their relevance.
+    pub async fn search(&self, tsquery: &str, item_types: &[ItemType], def_ids: &[Id], limit: i64) -> AppResult<Vec<SearchHit>> {
+        //
+        let item_types: Vec<String> = item_types.iter().map(|it| it.value()).collect();
+        let def_ids: Vec<&str> = def_ids.iter().map(|id| id.as_str()).collect();
+        let query = format!(
+            "SELECT sd.item_id, sd.item_type, sd.def_id, sd.title, ts_rank(sd.tsv, q) AS rank,
+                    ts_headline('simple', sd.title || ' ' || sd.content, q,
+                                'StartSel={}, StopSel={}, MaxFragments=2, MaxWords=20, MinWords=5') AS snippet
+             FROM search_documents sd, to_tsquery('simple', $1) q
+             WHERE sd.tsv @@ q
+               AND (cardinality($2::text[]) = 0 OR sd.item_type = ANY($2))
+               AND (cardinality($3::text[]) = 0 OR sd.def_id = ANY($3))
+             ORDER BY rank DESC, sd.title
+             LIMIT $4",
+            SearchHit::HIGHLIGHT_START,
+            SearchHit::HIGHLIGHT_STOP
+        );
+        sqlx::query_as::<_, SearchHit>(query.as_str())
+            .bind(tsquery)
+            .bind(item_types)
+            .bind(def_ids)
+            .bind(limit)
+            .fetch_all(self.dbcp.as_ref())
+            .await
+            .map(|res| AppResult::Ok(res))?
+    }
+
+    /// (Re)index an entity, based on its listing attribute value and text attributes.
+    pub async fn index_entity<'c, E>(executor: E, id: &Id) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query(
+            "INSERT INTO search_documents (item_id, item_type, def_id, title, content)
+             SELECT e.id, $2, e.def_id, COALESCE(e.listing_attr_value, ''),
+                    COALESCE((SELECT string_agg(ta.value, ' ') FROM text_attributes ta WHERE ta.owner_id = e.id), '')
+             FROM entities e WHERE e.id = $1
+             ON CONFLICT (item_id) DO UPDATE SET def_id = EXCLUDED.def_id, title = EXCLUDED.title, content = EXCLUDED.content",
+        )
+        .bind(id.as_str())
+        .bind(ItemType::Entity.value())
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    /// (Re)index a set of entities, the same way as `index_entity` does for one.
+    pub async fn index_entities<'c, E>(executor: E, ids: &[Id]) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let ids: Vec<&str> = ids.iter().map(|id| id.as_str()).collect();
+        sqlx::query(
+            "INSERT INTO search_documents (item_id, item_type, def_id, title, content)
+             SELECT e.id, $2, e.def_id, COALESCE(e.listing_attr_value, ''),
+                    COALESCE((SELECT string_agg(ta.value, ' ') FROM text_attributes ta WHERE ta.owner_id = e.id), '')
+             FROM entities e WHERE e.id = ANY($1)
+             ON CONFLICT (item_id) DO UPDATE SET def_id = EXCLUDED.def_id, title = EXCLUDED.title, content = EXCLUDED.content",
+        )
+        .bind(ids)
+        .bind(ItemType::Entity.value())
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    /// (Re)index an entity link, based on its definition name and text attributes.
+    pub async fn index_entity_link<'c, E>(executor: E, id: &Id) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query(
+            "INSERT INTO search_documents (item_id, item_type, def_id, title, content)
+             SELECT el.id, $2, el.def_id, COALESCE(eld.name, ''),
+                    COALESCE((SELECT string_agg(ta.value, ' ') FROM text_attributes ta WHERE ta.owner_id = el.id), '')
+             FROM entity_links el JOIN entity_link_defs eld ON el.def_id = eld.id WHERE el.id = $1
+             ON CONFLICT (item_id) DO UPDATE SET def_id = EXCLUDED.def_id, title = EXCLUDED.title, content = EXCLUDED.content",
+        )
+        .bind(id.as_str())
+        .bind(ItemType::EntityLink.value())
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    /// (Re)index all the links of an entity link definition, as their title is the definition's name.
+    pub async fn index_entity_links_by_def_id<'c, E>(executor: E, def_id: &Id) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query(
+            "INSERT INTO search_documents (item_id, item_type, def_id, title, content)
+             SELECT el.id, $2, el.def_id, COALESCE(eld.name, ''),
+                    COALESCE((SELECT string_agg(ta.value, ' ') FROM text_attributes ta WHERE ta.owner_id = el.id), '')
+             FROM entity_links el JOIN entity_link_defs eld ON el.def_id = eld.id WHERE el.def_id = $1
+             ON CONFLICT (item_id) DO UPDATE SET def_id = EXCLUDED.def_id, title = EXCLUDED.title, content = EXCLUDED.content",
+        )
+        .bind(def_id.as_str())
+        .bind(ItemType::EntityLink.value())
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    /// (Re)index a definition (of an entity, entity link, or attribute), based on its name and description.
+    pub async fn index_definition<'c, E>(
+        executor: E,
+        item_type: ItemType,
+        id: &Id,
+        name: &str,
+        description: &Option<String>,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query(
+            "INSERT INTO search_documents (item_id, item_type, title, content) VALUES ($1, $2, $3, $4)
+             ON CONFLICT (item_id) DO UPDATE SET title = EXCLUDED.title, content = EXCLUDED.content",
+        )
+        .bind(id.as_str())
+        .bind(item_type.value())
+        .bind(name)
+        .bind(description.clone().unwrap_or_default())
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    /// Rebuild the whole index, from all the entities, entity links and definitions.
+    pub async fn reindex_all(conn: &mut PgConnection) -> Result<(), sqlx::Error> {
+        //
+        sqlx::query("DELETE FROM search_documents").execute(&mut *conn).await?;
+        sqlx::query(
+            "INSERT INTO search_documents (item_id, item_type, def_id, title, content)
+             SELECT e.id, $1, e.def_id, COALESCE(e.listing_attr_value, ''),
+                    COALESCE((SELECT string_agg(ta.value, ' ') FROM text_attributes ta WHERE ta.owner_id = e.id), '')
+             FROM entities e",
+        )
+        .bind(ItemType::Entity.value())
+        .execute(&mut *conn)
+        .await?;
+        sqlx::query(
+            "INSERT INTO search_documents (item_id, item_type, def_id, title, content)
+             SELECT el.id, $1, el.def_id, COALESCE(eld.name, ''),
+                    COALESCE((SELECT string_agg(ta.value, ' ') FROM text_attributes ta WHERE ta.owner_id = el.id), '')
+             FROM entity_links el JOIN entity_link_defs eld ON el.def_id = eld.id",
+        )
+        .bind(ItemType::EntityLink.value())
+        .execute(&mut *conn)
+        .await?;
+        for (item_type, table) in [
+            (ItemType::EntityDef, "entity_defs"),
+            (ItemType::EntityLinkDef, "entity_link_defs"),
+            (ItemType::AttributeDef, "attribute_defs"),
+        ] {
+            let query = format!(
+                "INSERT INTO search_documents (item_id, item_type, title, content)
+                 SELECT id, $1, COALESCE(name, ''), COALESCE(description, '') FROM {}",
+                table
+            );
+            sqlx::query(&query).bind(item_type.value()).execute(&mut *conn).await?;
+        }
+        Ok(())
+    }
+
+    /// Remove an item from the index.
+    pub async fn unindex<'c, E>(executor: E, id: &Id) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query("DELETE FROM search_documents WHERE item_id = $1")
+            .bind(id.as_str())
+            .execute(executor)
+            .await
+            .map(|_| ())
+    }
+}
+
+impl FromRow<'_, PgRow> for SearchHit {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            item_id: Id::new_from(row.get("item_id")),
+            item_type: ItemType::from(row.get::<&str, &str>("item_type")),
+            def_id: row.get::<Option<String>, &str>("def_id").map(Id::new_from),
+            title: row.get("title"),
+            snippet: row.get("snippet"),
+            rank: row.get("rank"),
+        })
+    }
+}
@@ -46,6 +46,16 @@ impl UsersRepo {
         Some(user_account)
     }
 
+    pub async fn get_id_by_email(&self, email: &str) -> AppResult<Option<Id>> {
+        //
+        sqlx::query("SELECT id FROM user_accounts WHERE email = $1")
+            .bind(email)
+            .map(|r: PgRow| Id::new_from(r.get("id")))
+            .fetch_optional(self.dbcp.as_ref())
+            .await
+            .map_err(|err| AppError::from(err))
+    }
+
     pub async fn get_password_by_id(&self, user_id: &Id) -> AppResult<UserPasswordSalt> {
         //
         sqlx::query_as::<_, UserPasswordSalt>("SELECT password, salt FROM user_accounts WHERE id = $1")
@@ -69,6 +79,18 @@ impl UsersRepo {
         }
     }
 
+    pub async fn update_password_salt(&self, user_id: &Id, pwd: String, salt: String) -> AppResult<()> {
+        //
+        sqlx::query("UPDATE user_accounts SET password = $1, salt = $2 WHERE id = $3")
+            .bind(pwd)
+            .bind(salt)
+            .bind(user_id.as_str())
+            .execute(self.dbcp.as_ref())
+            .await
+            .map(|_| ())
+            .map_err(|err| AppError::from(err))
+    }
+
     pub async fn save(&self, email: String, username: String, pwd: String, salt: String) -> AppResult<Id> {
         //
         let id = Id::new();
@@ -142,6 +164,22 @@ impl UsersRepo {
         AppResult::Ok(id)
     }
 
+    /// Add the permissions to the user. The ones that the user already has are ignored.
+    pub async fn add_permissions(&self, user_id: &Id, permissions: &[String]) -> AppResult<()> {
+        //
+        sqlx::query(
+            "INSERT INTO user_permissions (user_id, permission) 
+             SELECT $1, UNNEST($2::VARCHAR[]) 
+             ON CONFLICT (user_id, permission) DO NOTHING",
+        )
+        .bind(user_id.as_str())
+        .bind(permissions)
+        .execute(self.dbcp.as_ref())
+        .await
+        .map(|_| ())
+        .map_err(|err| AppError::from(err))
+    }
+
     pub async fn update(&self, ua: UserAccount) -> AppResult<()> {
         //
         match sqlx::query("UPDATE user_accounts SET username=$1, email=$2, bio=$3 WHERE id = $4")
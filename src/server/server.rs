@@ -1,14 +1,21 @@
 #[cfg(feature = "server")]
 use dioxus::dioxus_core::Element;
 
-use super::{AppError, AppResult, UserMgmt};
+use std::sync::Arc;
+
+use super::{AppError, AppResult, DbMigrationsStatus, HealthCheckMgmt, ServiceRegistryMgmt, UserMgmt};
 
 #[cfg(feature = "server")]
 pub fn start_web_server(app_fn: fn() -> Element) {
     //
     use crate::{
         domain::model::{Id, UserAccount},
-        server::{connect_to_pgdb, ws_handler, ServerState},
+        server::{
+            api::{api_docs_router, api_v1_router},
+            apply_db_migrations, connect_to_pgdb, db_migrations_status,
+            graphql::graphql_router,
+            ws_handler, ServerState,
+        },
     };
     use axum::{routing::*, Extension};
     use axum_session::{SessionConfig, SessionLayer};
@@ -16,7 +23,7 @@ pub fn start_web_server(app_fn: fn() -> Element) {
     use axum_session_sqlx::{SessionPgPool, SessionPgSessionStore};
     use dioxus::prelude::*;
     use sqlx::PgPool;
-    use std::{net::SocketAddr, sync::Arc};
+    use std::net::SocketAddr;
 
     init_logging();
     log::info!("Starting up the server ...");
@@ -32,6 +39,21 @@ pub fn start_web_server(app_fn: fn() -> Element) {
         let pg_pool = pg_pool.unwrap();
         log::info!("Connected to the database.");
 
+        if std::env::args().any(|arg| arg == "--check-migrations") {
+            let up_to_date = report_db_migrations(db_migrations_status(&pg_pool).await);
+            std::process::exit(if up_to_date { 0 } else { 1 });
+        }
+        match db_migrations_auto_apply() {
+            true => {
+                log::info!("Applying the pending database migrations ...");
+                if let Err(e) = apply_db_migrations(&pg_pool).await {
+                    log::error!("{} Exiting now!", e);
+                    return;
+                }
+            }
+            false => log::info!("Skipped applying the database migrations, as DB_MIGRATIONS_AUTO_APPLY is disabled."),
+        }
+
         // This defaults as normal cookies.
         let session_config = SessionConfig::default().with_table_name("user_sessions");
         let session_store = SessionPgSessionStore::new(Some(pg_pool.clone().into()), session_config)
@@ -44,9 +66,18 @@ pub fn start_web_server(app_fn: fn() -> Element) {
             .await
             .expect("Self registering admin user failed");
 
+        start_registry_expiry(state.registry_mgmt.clone());
+        start_health_checks(state.health_check_mgmt.clone());
+
         let auth_config = AuthConfig::<Id>::default().with_anonymous_user_id(Some("iH26rJ8Cp".into()));
 
+        // GraphQL router.
+        let graphql_router = graphql_router(state.clone());
+
         let web_api_router = Router::new()
+            // The public REST API.
+            .nest("/api/v1", api_v1_router())
+            .merge(api_docs_router())
             // Server side render the application, serve static assets, and register server functions.
             .serve_dioxus_application(ServeConfigBuilder::default(), app_fn)
             .layer(AuthSessionLayer::<UserAccount, Id, SessionPgPool, PgPool>::new(Some(pg_pool)).with_config(auth_config))
@@ -56,7 +87,7 @@ pub fn start_web_server(app_fn: fn() -> Element) {
         // WebSocket router.
         let ws_router = Router::new().route("/", get(ws_handler));
 
-        let router = web_api_router.nest("/ws", ws_router);
+        let router = web_api_router.nest("/ws", ws_router).nest("/graphql", graphql_router);
 
         // Connect to the IP and PORT environment variables.
         let socket_addr = dioxus_cli_config::fullstack_address_or_localhost();
@@ -68,6 +99,74 @@ pub fn start_web_server(app_fn: fn() -> Element) {
     });
 }
 
+/// Whether the pending database migrations are applied on startup. It is enabled, unless `DB_MIGRATIONS_AUTO_APPLY` is `false` (or `0`).
+#[cfg(feature = "server")]
+fn db_migrations_auto_apply() -> bool {
+    !matches!(
+        std::env::var("DB_MIGRATIONS_AUTO_APPLY").as_deref().map(str::trim),
+        Ok("false") | Ok("0")
+    )
+}
+
+/// Report the state of the database migrations. It returns whether the database is up to date.
+#[cfg(feature = "server")]
+fn report_db_migrations(status: AppResult<DbMigrationsStatus>) -> bool {
+    //
+    let status = match status {
+        Ok(status) => status,
+        Err(e) => {
+            log::error!("{}", e);
+            return false;
+        }
+    };
+    log::info!("{} database migrations are applied.", status.applied.len());
+    for (version, description) in status.pending.iter() {
+        log::warn!("The database migration {} ({}) is pending.", version, description);
+    }
+    if let Some(problems) = status.problems() {
+        log::error!("{}", problems);
+    }
+    if status.is_up_to_date() {
+        log::info!("The database is up to date.");
+    }
+    status.is_up_to_date()
+}
+
+/// Start the background task that periodically removes the expired service instances from the registry.
+fn start_registry_expiry(registry_mgmt: Arc<ServiceRegistryMgmt>) {
+    //
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(ServiceRegistryMgmt::EXPIRY_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            if let Err(e) = registry_mgmt.expire_instances().await {
+                log::error!("Failed to remove the expired service instances. Cause: '{}'.", e);
+            }
+        }
+    });
+}
+
+/// Start the background task that periodically checks the health of the registered service instances.
+fn start_health_checks(health_check_mgmt: Arc<HealthCheckMgmt>) {
+    //
+    let config = health_check_mgmt.config().clone();
+    log::info!(
+        "Checking the health of the service instances every {} seconds, {} at a time.",
+        config.interval.as_secs(),
+        config.concurrency
+    );
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(config.interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(e) = health_check_mgmt.check_all().await {
+                log::error!("Failed to check the health of the service instances. Cause: '{}'.", e);
+            }
+        }
+    });
+}
+
 async fn register_admin_user(user_mgmt: &UserMgmt) -> AppResult<()> {
     //
     let email = "admin@localhost".to_string();
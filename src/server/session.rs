@@ -8,8 +8,8 @@ use sqlx::PgPool;
 use crate::domain::model::{Id, UserAccount};
 
 use super::{
-    AttributeDefMgmt, AuthSessionLayerNotFound, EntityDefMgmt, EntityLinkDefMgmt, EntityLinkMgmt, EntityMgmt, ServerState, TagMgmt,
-    UserMgmt,
+    AppError, AppResult, AttributeDefMgmt, AuthSessionLayerNotFound, EntityDefMgmt, EntityImportMgmt, EntityLinkDefMgmt, EntityLinkMgmt,
+    EntityMgmt, MetamodelBundleMgmt, SavedViewMgmt, SearchMgmt, ServerState, TagMgmt, UserMgmt,
 };
 
 pub struct Session(
@@ -22,6 +22,10 @@ pub struct Session(
     pub Arc<EntityMgmt>,
     pub Arc<EntityLinkDefMgmt>,
     pub Arc<EntityLinkMgmt>,
+    pub Arc<SearchMgmt>,
+    pub Arc<SavedViewMgmt>,
+    pub Arc<MetamodelBundleMgmt>,
+    pub Arc<EntityImportMgmt>,
 );
 
 impl Session {
@@ -29,6 +33,22 @@ impl Session {
         self.0.current_user.clone()
     }
 
+    /// Get the id of the current (authenticated, not anonymous) user.
+    pub fn current_user_id(&self) -> AppResult<Id> {
+        match &self.0.current_user {
+            Some(user) if !user.is_anonymous => Ok(user.id.clone()),
+            _ => Err(AppError::Unauthorized("login is required".into())),
+        }
+    }
+
+    /// Get the username of the current (authenticated, not anonymous) user.
+    pub fn current_username(&self) -> AppResult<String> {
+        match &self.0.current_user {
+            Some(user) if !user.is_anonymous => Ok(user.username.clone()),
+            _ => Err(AppError::Unauthorized("login is required".into())),
+        }
+    }
+
     pub fn user_mgmt(&self) -> Arc<UserMgmt> {
         self.1.clone()
     }
@@ -56,6 +76,22 @@ impl Session {
     pub fn ent_link_mgmt(&self) -> Arc<EntityLinkMgmt> {
         self.7.clone()
     }
+
+    pub fn search_mgmt(&self) -> Arc<SearchMgmt> {
+        self.8.clone()
+    }
+
+    pub fn saved_view_mgmt(&self) -> Arc<SavedViewMgmt> {
+        self.9.clone()
+    }
+
+    pub fn bundle_mgmt(&self) -> Arc<MetamodelBundleMgmt> {
+        self.10.clone()
+    }
+
+    pub fn ent_import_mgmt(&self) -> Arc<EntityImportMgmt> {
+        self.11.clone()
+    }
 }
 
 impl std::ops::Deref for Session {
@@ -88,6 +124,10 @@ impl<S: Sync + Send> axum::extract::FromRequestParts<S> for Session {
                 let ent_mgmt = server_state.ent_mgmt.clone();
                 let ent_link_def_mgmt = server_state.ent_link_def_mgmt.clone();
                 let ent_link_mgmt = server_state.ent_link_mgmt.clone();
+                let search_mgmt = server_state.search_mgmt.clone();
+                let saved_view_mgmt = server_state.saved_view_mgmt.clone();
+                let bundle_mgmt = server_state.bundle_mgmt.clone();
+                let ent_import_mgmt = server_state.ent_import_mgmt.clone();
                 Session(
                     auth_session,
                     user_mgmt,
@@ -97,6 +137,10 @@ impl<S: Sync + Send> axum::extract::FromRequestParts<S> for Session {
                     ent_mgmt,
                     ent_link_def_mgmt,
                     ent_link_mgmt,
+                    search_mgmt,
+                    saved_view_mgmt,
+                    bundle_mgmt,
+                    ent_import_mgmt,
                 )
             })
             .map_err(|_| AuthSessionLayerNotFound)
@@ -10,9 +10,14 @@ use http::{request::Parts, StatusCode};
 #[cfg(feature = "server")]
 use sqlx::PgPool;
 
+use crate::domain::model::RdfVocabulary;
+
 use super::{
-    AttributeDefMgmt, AttributeDefRepo, EntityDefMgmt, EntityDefRepo, EntityLinkDefMgmt, EntityLinkDefRepo, EntityLinkMgmt, EntityLinkRepo,
-    EntityMgmt, EntityRepo, TagMgmt, TagsRepo, UserMgmt, UsersRepo,
+    AttributeDefMgmt, AttributeDefRepo, BackupMgmt, BackupRepo, ConfigMgmt, ConfigRepo, ConsistencyMgmt, ConsistencyRepo, EntityDefMgmt,
+    EntityDefRepo, EntityExportMgmt, EntityExportRepo, EntityImportMgmt, EntityLinkDefMgmt, EntityLinkDefRepo, EntityLinkMgmt,
+    EntityLinkRepo, EntityMgmt, EntityRepo, GraphExportMgmt, HealthCheckConfig, HealthCheckMgmt, MetamodelBundleMgmt, MetamodelBundleRepo,
+    RdfExportMgmt, SavedViewMgmt, SavedViewsRepo, SearchMgmt, SearchRepo, ServiceRegistryMgmt, ServiceRegistryRepo, TagMgmt, TagsRepo,
+    UserMgmt, UsersRepo,
 };
 
 #[cfg(feature = "server")]
@@ -25,6 +30,18 @@ pub struct ServerState {
     pub ent_mgmt: Arc<EntityMgmt>,
     pub ent_link_def_mgmt: Arc<EntityLinkDefMgmt>,
     pub ent_link_mgmt: Arc<EntityLinkMgmt>,
+    pub search_mgmt: Arc<SearchMgmt>,
+    pub saved_view_mgmt: Arc<SavedViewMgmt>,
+    pub bundle_mgmt: Arc<MetamodelBundleMgmt>,
+    pub ent_import_mgmt: Arc<EntityImportMgmt>,
+    pub ent_export_mgmt: Arc<EntityExportMgmt>,
+    pub graph_export_mgmt: Arc<GraphExportMgmt>,
+    pub rdf_export_mgmt: Arc<RdfExportMgmt>,
+    pub consistency_mgmt: Arc<ConsistencyMgmt>,
+    pub backup_mgmt: Arc<BackupMgmt>,
+    pub registry_mgmt: Arc<ServiceRegistryMgmt>,
+    pub health_check_mgmt: Arc<HealthCheckMgmt>,
+    pub config_mgmt: Arc<ConfigMgmt>,
 }
 
 impl ServerState {
@@ -37,19 +54,51 @@ impl ServerState {
         let tag_mgmt = Arc::new(TagMgmt::new(tag_repo));
 
         let attr_def_repo = Arc::new(AttributeDefRepo::new(db_pool.clone()));
-        let attr_def_mgmt = Arc::new(AttributeDefMgmt::new(attr_def_repo));
+        let attr_def_mgmt = Arc::new(AttributeDefMgmt::new(attr_def_repo.clone()));
 
         let ent_def_repo = Arc::new(EntityDefRepo::new(db_pool.clone()));
-        let ent_def_mgmt = Arc::new(EntityDefMgmt::new(ent_def_repo));
+        let ent_def_mgmt = Arc::new(EntityDefMgmt::new(ent_def_repo.clone()));
 
         let ent_repo = Arc::new(EntityRepo::new(db_pool.clone()));
-        let ent_mgmt = Arc::new(EntityMgmt::new(ent_repo));
+        let ent_mgmt = Arc::new(EntityMgmt::new(ent_repo.clone()));
 
         let ent_link_def_repo = Arc::new(EntityLinkDefRepo::new(db_pool.clone()));
         let ent_link_def_mgmt = Arc::new(EntityLinkDefMgmt::new(ent_link_def_repo));
 
         let ent_link_repo = Arc::new(EntityLinkRepo::new(db_pool.clone()));
-        let ent_link_mgmt = Arc::new(EntityLinkMgmt::new(ent_link_repo));
+        let ent_link_mgmt = Arc::new(EntityLinkMgmt::new(ent_link_repo.clone()));
+
+        let search_repo = Arc::new(SearchRepo::new(db_pool.clone()));
+        let search_mgmt = Arc::new(SearchMgmt::new(search_repo));
+
+        let saved_views_repo = Arc::new(SavedViewsRepo::new(db_pool.clone()));
+        let saved_view_mgmt = Arc::new(SavedViewMgmt::new(saved_views_repo, ent_repo.clone()));
+
+        let bundle_repo = Arc::new(MetamodelBundleRepo::new(db_pool.clone()));
+        let bundle_mgmt = Arc::new(MetamodelBundleMgmt::new(bundle_repo.clone()));
+
+        let ent_import_mgmt = Arc::new(EntityImportMgmt::new(ent_def_repo, ent_repo.clone()));
+
+        let ent_export_repo = Arc::new(EntityExportRepo::new(db_pool.clone()));
+        let ent_export_mgmt = Arc::new(EntityExportMgmt::new(ent_export_repo.clone()));
+
+        let graph_export_mgmt = Arc::new(GraphExportMgmt::new(ent_repo.clone(), ent_link_repo));
+
+        let rdf_base_iri = std::env::var("RDF_BASE_IRI").unwrap_or(RdfVocabulary::DEFAULT_BASE_IRI.to_string());
+        let rdf_export_mgmt = Arc::new(RdfExportMgmt::new(bundle_repo, ent_export_repo, rdf_base_iri));
+
+        let consistency_repo = Arc::new(ConsistencyRepo::new(db_pool.clone()));
+        let consistency_mgmt = Arc::new(ConsistencyMgmt::new(consistency_repo));
+
+        let backup_repo = Arc::new(BackupRepo::new(db_pool.clone()));
+        let backup_mgmt = Arc::new(BackupMgmt::new(backup_repo));
+
+        let registry_repo = Arc::new(ServiceRegistryRepo::new(db_pool.clone()));
+        let registry_mgmt = Arc::new(ServiceRegistryMgmt::new(registry_repo, ent_repo));
+        let health_check_mgmt = Arc::new(HealthCheckMgmt::new(registry_mgmt.clone(), HealthCheckConfig::from_env()));
+
+        let config_repo = Arc::new(ConfigRepo::new(db_pool.clone()));
+        let config_mgmt = Arc::new(ConfigMgmt::new(config_repo, attr_def_repo));
 
         Self {
             user_mgmt,
@@ -59,6 +108,18 @@ impl ServerState {
             ent_mgmt,
             ent_link_def_mgmt,
             ent_link_mgmt,
+            search_mgmt,
+            saved_view_mgmt,
+            bundle_mgmt,
+            ent_import_mgmt,
+            ent_export_mgmt,
+            graph_export_mgmt,
+            rdf_export_mgmt,
+            consistency_mgmt,
+            backup_mgmt,
+            registry_mgmt,
+            health_check_mgmt,
+            config_mgmt,
         }
     }
 }
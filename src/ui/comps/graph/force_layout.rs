@@ -0,0 +1,152 @@
+use indexmap::IndexMap;
+
+use crate::domain::model::{EntityGraph, Id};
+
+/// A position in the (SVG) drawing area.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+}
+
+/// A force-directed layout (based on Fruchterman-Reingold algorithm) of an `EntityGraph`.<br/>
+/// It runs entirely on the client side, and it is deterministic: the same input produces the same positions.
+pub struct ForceLayout {
+    pub width: f64,
+    pub height: f64,
+    pub iterations: usize,
+    /// The space kept free on each side of the drawing area.
+    pub margin: f64,
+}
+
+impl ForceLayout {
+    //
+    pub fn new(width: f64, height: f64) -> Self {
+        Self {
+            width,
+            height,
+            iterations: 300,
+            margin: 30.0,
+        }
+    }
+
+    /// Compute the positions of the graph's entities.<br/>
+    /// The already known positions (provided through `initial`) are used as starting points,
+    /// so that expanding a graph does not shuffle the nodes that were already shown.
+    pub fn run(&self, graph: &EntityGraph, initial: &IndexMap<Id, Point>) -> IndexMap<Id, Point> {
+        //
+        let ids: Vec<Id> = graph.entities.iter().map(|ent| ent.id.clone()).collect();
+        let count = ids.len();
+        if count == 0 {
+            return IndexMap::new();
+        }
+
+        let index_of: IndexMap<&Id, usize> = ids.iter().enumerate().map(|(i, id)| (id, i)).collect();
+        let edges: Vec<(usize, usize)> = graph
+            .links
+            .iter()
+            .filter_map(
+                |link| match (index_of.get(&link.source_entity_id), index_of.get(&link.target_entity_id)) {
+                    (Some(s), Some(t)) if s != t => Some((*s, *t)),
+                    _ => None,
+                },
+            )
+            .collect();
+
+        let mut positions = self.initial_positions(&ids, &edges, initial);
+
+        let center = Point::new(self.width / 2.0, self.height / 2.0);
+        let area = (self.width - 2.0 * self.margin) * (self.height - 2.0 * self.margin);
+        let k = (area / count as f64).sqrt();
+        let mut temperature = self.width.min(self.height) / 10.0;
+        let cooling = temperature / (self.iterations as f64 + 1.0);
+
+        for _ in 0..self.iterations {
+            let mut disp = vec![Point::default(); count];
+
+            // Repulsive forces, between every pair of nodes.
+            for i in 0..count {
+                for j in (i + 1)..count {
+                    let dx = positions[i].x - positions[j].x;
+                    let dy = positions[i].y - positions[j].y;
+                    let dist = (dx * dx + dy * dy).sqrt().max(0.01);
+                    let force = k * k / dist;
+                    disp[i].x += dx / dist * force;
+                    disp[i].y += dy / dist * force;
+                    disp[j].x -= dx / dist * force;
+                    disp[j].y -= dy / dist * force;
+                }
+            }
+
+            // Attractive forces, along the edges.
+            for (s, t) in edges.iter() {
+                let dx = positions[*s].x - positions[*t].x;
+                let dy = positions[*s].y - positions[*t].y;
+                let dist = (dx * dx + dy * dy).sqrt().max(0.01);
+                let force = dist * dist / k;
+                disp[*s].x -= dx / dist * force;
+                disp[*s].y -= dy / dist * force;
+                disp[*t].x += dx / dist * force;
+                disp[*t].y += dy / dist * force;
+            }
+
+            // A light gravity that keeps the disconnected parts of the graph close to the center.
+            for i in 0..count {
+                disp[i].x += (center.x - positions[i].x) * 0.05 * k / 10.0;
+                disp[i].y += (center.y - positions[i].y) * 0.05 * k / 10.0;
+            }
+
+            // Move the nodes, limiting the displacement to the current temperature.
+            for i in 0..count {
+                let len = (disp[i].x * disp[i].x + disp[i].y * disp[i].y).sqrt();
+                if len > 0.0 {
+                    let step = len.min(temperature);
+                    positions[i].x += disp[i].x / len * step;
+                    positions[i].y += disp[i].y / len * step;
+                }
+                positions[i].x = positions[i].x.clamp(self.margin, self.width - self.margin);
+                positions[i].y = positions[i].y.clamp(self.margin, self.height - self.margin);
+            }
+
+            temperature -= cooling;
+        }
+
+        ids.into_iter().zip(positions).collect()
+    }
+
+    /// The starting positions: the known ones are kept, a new node is placed next to an already placed neighbor
+    /// (if any), and all the others are evenly spread on a circle.
+    fn initial_positions(&self, ids: &[Id], edges: &[(usize, usize)], initial: &IndexMap<Id, Point>) -> Vec<Point> {
+        //
+        let count = ids.len();
+        let center = Point::new(self.width / 2.0, self.height / 2.0);
+        let radius = (self.width.min(self.height) / 2.0 - self.margin) * 0.8;
+
+        let mut positions: Vec<Option<Point>> = ids.iter().map(|id| initial.get(id).copied()).collect();
+        for i in 0..count {
+            if positions[i].is_some() {
+                continue;
+            }
+            let angle = 2.0 * std::f64::consts::PI * (i as f64) / (count as f64);
+            let neighbor = edges
+                .iter()
+                .filter_map(|(s, t)| match (*s == i, *t == i) {
+                    (true, _) => positions[*t],
+                    (_, true) => positions[*s],
+                    _ => None,
+                })
+                .next();
+            positions[i] = Some(match neighbor {
+                Some(p) => Point::new(p.x + 40.0 * angle.cos(), p.y + 40.0 * angle.sin()),
+                None => Point::new(center.x + radius * angle.cos(), center.y + radius * angle.sin()),
+            });
+        }
+        positions.into_iter().map(|p| p.unwrap_or(center)).collect()
+    }
+}
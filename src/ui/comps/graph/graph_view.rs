@@ -0,0 +1,156 @@
+use dioxus::prelude::*;
+use indexmap::IndexMap;
+
+use crate::{
+    domain::model::{EntityGraph, Id},
+    ui::{comps::Point, routes::Route},
+};
+
+/// The radius of a node (entity) circle.
+const NODE_RADIUS: f64 = 10.0;
+
+/// The fill colors used for distinguishing the entities by their definitions.
+const PALETTE: [&str; 8] = [
+    "#60a5fa", "#34d399", "#fbbf24", "#f87171", "#a78bfa", "#f472b6", "#2dd4bf", "#a3a3a3",
+];
+
+#[derive(Clone, Debug, Props, PartialEq)]
+pub struct EntityGraphViewProps {
+    pub graph: EntityGraph,
+
+    pub positions: IndexMap<Id, Point>,
+
+    pub width: f64,
+
+    pub height: f64,
+
+    /// The id of the currently selected entity (node).
+    pub selected_ent_id: Signal<Option<Id>>,
+}
+
+/// An SVG based view of an `EntityGraph`, having the entities as nodes and their links as edges.<br/>
+/// A click on a node selects it, and a double click navigates to its `EntityPage`.
+pub fn EntityGraphView(props: EntityGraphViewProps) -> Element {
+    //
+    let EntityGraphViewProps {
+        graph,
+        positions,
+        width,
+        height,
+        mut selected_ent_id,
+    } = props;
+
+    let mut def_colors = IndexMap::<Id, &str>::new();
+    for ent in graph.entities.iter() {
+        if !def_colors.contains_key(&ent.def_id) {
+            let color = PALETTE[def_colors.len() % PALETTE.len()];
+            def_colors.insert(ent.def_id.clone(), color);
+        }
+    }
+
+    let edges: Vec<(Id, String, Point, Point)> = graph
+        .links
+        .iter()
+        .filter_map(
+            |link| match (positions.get(&link.source_entity_id), positions.get(&link.target_entity_id)) {
+                (Some(s), Some(t)) => {
+                    let (s, t) = shorten(*s, *t, NODE_RADIUS + 2.0);
+                    Some((link.id.clone(), link.kind.clone(), s, t))
+                }
+                _ => None,
+            },
+        )
+        .collect();
+
+    rsx! {
+        svg {
+            class: "bg-white border rounded-lg",
+            width: "{width}",
+            height: "{height}",
+            view_box: "0 0 {width} {height}",
+            onclick: move |_| selected_ent_id.set(None),
+            defs {
+                marker {
+                    id: "arrow",
+                    view_box: "0 0 10 10",
+                    "refX": "9",
+                    "refY": "5",
+                    "markerWidth": "6",
+                    "markerHeight": "6",
+                    orient: "auto-start-reverse",
+                    path { d: "M 0 0 L 10 5 L 0 10 z", fill: "#94a3b8" }
+                }
+            }
+            for (id , kind , s , t) in edges {
+                g { key: "{id}",
+                    line {
+                        x1: "{s.x}",
+                        y1: "{s.y}",
+                        x2: "{t.x}",
+                        y2: "{t.y}",
+                        stroke: "#cbd5e1",
+                        stroke_width: "1.5",
+                        "marker-end": "url(#arrow)",
+                    }
+                    text {
+                        x: "{(s.x + t.x) / 2.0}",
+                        y: "{(s.y + t.y) / 2.0 - 3.0}",
+                        text_anchor: "middle",
+                        font_size: "9",
+                        fill: "#94a3b8",
+                        "{kind}"
+                    }
+                }
+            }
+            for ent in graph.entities.iter() {
+                if let Some(p) = positions.get(&ent.id) {
+                    g { key: "{ent.id}",
+                        circle {
+                            class: "cursor-pointer",
+                            cx: "{p.x}",
+                            cy: "{p.y}",
+                            r: "{NODE_RADIUS}",
+                            fill: "{def_colors.get(&ent.def_id).unwrap_or(&PALETTE[0])}",
+                            stroke: if selected_ent_id() == Some(ent.id.clone()) { "#1f2937" } else { "#ffffff" },
+                            stroke_width: "2",
+                            onclick: {
+                                let id = ent.id.clone();
+                                move |evt: Event<MouseData>| {
+                                    evt.stop_propagation();
+                                    selected_ent_id.set(Some(id.clone()));
+                                }
+                            },
+                            ondoubleclick: {
+                                let id = ent.id.clone();
+                                move |_| {
+                                    navigator().push(Route::EntityPage { id: id.clone() });
+                                }
+                            },
+                            title { "{ent.kind}: {ent.listing_attr_value}" }
+                        }
+                        text {
+                            x: "{p.x}",
+                            y: "{p.y + NODE_RADIUS + 12.0}",
+                            text_anchor: "middle",
+                            font_size: "11",
+                            fill: "#4b5563",
+                            "{ent.listing_attr_value}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Shorten the segment between the two points on both ends, so that an edge stops at the border of the nodes.
+fn shorten(s: Point, t: Point, by: f64) -> (Point, Point) {
+    let dx = t.x - s.x;
+    let dy = t.y - s.y;
+    let len = (dx * dx + dy * dy).sqrt();
+    if len <= 2.0 * by {
+        return (s, t);
+    }
+    let (ux, uy) = (dx / len, dy / len);
+    (Point::new(s.x + ux * by, s.y + uy * by), Point::new(t.x - ux * by, t.y - uy * by))
+}
@@ -0,0 +1,222 @@
+use dioxus::prelude::*;
+use indexmap::IndexMap;
+
+use crate::{
+    domain::model::{EntityDef, Id, MetamodelDiagram},
+    ui::{comps::Point, routes::Route},
+};
+
+const BOX_WIDTH: f64 = 200.0;
+const BOX_HEADER_HEIGHT: f64 = 26.0;
+const BOX_ROW_HEIGHT: f64 = 18.0;
+const BOX_GAP_X: f64 = 120.0;
+const BOX_GAP_Y: f64 = 90.0;
+const MARGIN: f64 = 30.0;
+
+/// The position (top-left corner) and size of an entity definition's box.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct BoxArea {
+    pos: Point,
+    width: f64,
+    height: f64,
+}
+
+impl BoxArea {
+    fn center(&self) -> Point {
+        Point::new(self.pos.x + self.width / 2.0, self.pos.y + self.height / 2.0)
+    }
+
+    /// The point where the segment from the center of the box towards `to` crosses the box border.
+    fn border_point(&self, to: Point) -> Point {
+        let c = self.center();
+        let (dx, dy) = (to.x - c.x, to.y - c.y);
+        if dx == 0.0 && dy == 0.0 {
+            return c;
+        }
+        let scale_x = if dx != 0.0 { (self.width / 2.0) / dx.abs() } else { f64::MAX };
+        let scale_y = if dy != 0.0 { (self.height / 2.0) / dy.abs() } else { f64::MAX };
+        let scale = scale_x.min(scale_y);
+        Point::new(c.x + dx * scale, c.y + dy * scale)
+    }
+}
+
+#[derive(Clone, Debug, Props, PartialEq)]
+pub struct MetamodelDiagramViewProps {
+    pub diagram: MetamodelDiagram,
+}
+
+/// An SVG based entity relationship diagram of the metamodel.<br/>
+/// Each entity definition is a box listing its attributes definitions, and each entity link definition
+/// is an arrow labelled with its name and cardinality.
+pub fn MetamodelDiagramView(props: MetamodelDiagramViewProps) -> Element {
+    //
+    let MetamodelDiagramViewProps { diagram } = props;
+
+    let (boxes, width, height) = grid_layout(&diagram.ent_defs);
+
+    let mut arrows = Vec::<(Id, String)>::new(); // (id, svg path)
+    let mut labels = Vec::<(Id, Point, String)>::new();
+    for eld in diagram.ent_link_defs.iter() {
+        let (Some(source), Some(target)) = (boxes.get(&eld.source_entity_def_id), boxes.get(&eld.target_entity_def_id)) else {
+            continue;
+        };
+        let label = format!("{} ({})", eld.name, eld.cardinality.as_string());
+        if eld.source_entity_def_id == eld.target_entity_def_id {
+            // A self link is drawn as a loop on the right side of the box.
+            let x = source.pos.x + source.width;
+            let y = source.pos.y + BOX_HEADER_HEIGHT / 2.0;
+            let path = format!("M {x} {y} C {} {} {} {} {x} {}", x + 60.0, y - 20.0, x + 60.0, y + 40.0, y + 20.0);
+            labels.push((eld.id.clone(), Point::new(x + 50.0, y + 10.0), label));
+            arrows.push((eld.id.clone(), path));
+        } else {
+            let s = source.border_point(target.center());
+            let t = target.border_point(source.center());
+            labels.push((eld.id.clone(), Point::new((s.x + t.x) / 2.0, (s.y + t.y) / 2.0 - 4.0), label));
+            arrows.push((eld.id.clone(), format!("M {} {} L {} {}", s.x, s.y, t.x, t.y)));
+        }
+    }
+
+    rsx! {
+        svg {
+            class: "bg-white border rounded-lg",
+            width: "{width}",
+            height: "{height}",
+            view_box: "0 0 {width} {height}",
+            defs {
+                marker {
+                    id: "er_arrow",
+                    view_box: "0 0 10 10",
+                    "refX": "9",
+                    "refY": "5",
+                    "markerWidth": "7",
+                    "markerHeight": "7",
+                    orient: "auto-start-reverse",
+                    path { d: "M 0 0 L 10 5 L 0 10 z", fill: "#64748b" }
+                }
+            }
+            for ent_def in diagram.ent_defs.iter() {
+                if let Some(area) = boxes.get(&ent_def.id) {
+                    EntityDefBox { ent_def: ent_def.clone(), x: area.pos.x, y: area.pos.y }
+                }
+            }
+            for (id , d) in arrows {
+                path {
+                    key: "{id}",
+                    d: "{d}",
+                    fill: "none",
+                    stroke: "#64748b",
+                    stroke_width: "1.5",
+                    "marker-end": "url(#er_arrow)",
+                }
+            }
+            for (id , p , label) in labels {
+                text {
+                    key: "lbl_{id}",
+                    x: "{p.x}",
+                    y: "{p.y}",
+                    text_anchor: "middle",
+                    font_size: "11",
+                    fill: "#475569",
+                    "{label}"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn EntityDefBox(ent_def: EntityDef, x: f64, y: f64) -> Element {
+    //
+    let height = box_height(&ent_def);
+    rsx! {
+        g {
+            class: "cursor-pointer",
+            onclick: {
+                let id = ent_def.id.clone();
+                move |_| {
+                    navigator().push(Route::EntityDefPage { id: id.clone() });
+                }
+            },
+            rect {
+                x: "{x}",
+                y: "{y}",
+                width: "{BOX_WIDTH}",
+                height: "{height}",
+                rx: "6",
+                fill: "#ffffff",
+                stroke: "#94a3b8",
+            }
+            rect {
+                x: "{x}",
+                y: "{y}",
+                width: "{BOX_WIDTH}",
+                height: "{BOX_HEADER_HEIGHT}",
+                rx: "6",
+                fill: "#e2e8f0",
+            }
+            text {
+                x: "{x + BOX_WIDTH / 2.0}",
+                y: "{y + 17.0}",
+                text_anchor: "middle",
+                font_size: "13",
+                font_weight: "600",
+                fill: "#334155",
+                "{ent_def.name}"
+            }
+            for (i , attr_def) in ent_def.attributes.iter().enumerate() {
+                text {
+                    key: "{attr_def.id}",
+                    x: "{x + 8.0}",
+                    y: "{y + BOX_HEADER_HEIGHT + 14.0 + i as f64 * BOX_ROW_HEIGHT}",
+                    font_size: "11",
+                    fill: "#475569",
+                    if attr_def.is_required {
+                        "* {attr_def.name}"
+                    } else {
+                        "{attr_def.name}"
+                    }
+                }
+                text {
+                    key: "type_{attr_def.id}",
+                    x: "{x + BOX_WIDTH - 8.0}",
+                    y: "{y + BOX_HEADER_HEIGHT + 14.0 + i as f64 * BOX_ROW_HEIGHT}",
+                    text_anchor: "end",
+                    font_size: "10",
+                    fill: "#94a3b8",
+                    "{attr_def.value_type}"
+                }
+            }
+        }
+    }
+}
+
+fn box_height(ent_def: &EntityDef) -> f64 {
+    BOX_HEADER_HEIGHT + 8.0 + ent_def.attributes.len().max(1) as f64 * BOX_ROW_HEIGHT
+}
+
+/// Place the boxes on a grid, returning their areas and the overall width and height of the drawing.
+fn grid_layout(ent_defs: &[EntityDef]) -> (IndexMap<Id, BoxArea>, f64, f64) {
+    //
+    let count = ent_defs.len().max(1);
+    let columns = (count as f64).sqrt().ceil() as usize;
+
+    let mut boxes = IndexMap::new();
+    let mut y = MARGIN;
+    let mut width: f64 = 0.0;
+    for row in ent_defs.chunks(columns) {
+        let row_height = row.iter().map(box_height).fold(0.0, f64::max);
+        for (col, ent_def) in row.iter().enumerate() {
+            let x = MARGIN + col as f64 * (BOX_WIDTH + BOX_GAP_X);
+            let area = BoxArea {
+                pos: Point::new(x, y),
+                width: BOX_WIDTH,
+                height: box_height(ent_def),
+            };
+            width = width.max(x + BOX_WIDTH + MARGIN);
+            boxes.insert(ent_def.id.clone(), area);
+        }
+        y += row_height + BOX_GAP_Y;
+    }
+    // The extra space on the right is for the self links.
+    (boxes, width.max(600.0) + 60.0, (y - BOX_GAP_Y + MARGIN).max(200.0))
+}
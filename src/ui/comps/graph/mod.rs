@@ -0,0 +1,8 @@
+mod force_layout;
+pub use force_layout::*;
+
+mod graph_view;
+pub use graph_view::*;
+
+mod metamodel_diagram_view;
+pub use metamodel_diagram_view::*;
@@ -0,0 +1,91 @@
+use dioxus::prelude::*;
+
+use crate::domain::model::Pagination;
+
+#[derive(Clone, Debug, Props, PartialEq)]
+pub struct ListControlsProps {
+    /// The fields that the list can be sorted by, as (field name, label) pairs.<br/>
+    /// The first one is expected to be the default sorting field.
+    pub sort_fields: Vec<(String, String)>,
+
+    /// The pagination options, updated on user's actions.
+    pub pagination: Signal<Pagination>,
+
+    /// The total number of items in the list.
+    pub total: i64,
+}
+
+/// The sorting and pagination controls of a list.<br/>
+/// Changing the sorting goes back to the first page.
+pub fn ListControls(props: ListControlsProps) -> Element {
+    //
+    let ListControlsProps {
+        sort_fields,
+        mut pagination,
+        total,
+    } = props;
+
+    let page = pagination().get_page();
+    let limit = pagination().get_limit() as i64;
+    let page_count = ((total + limit - 1) / limit).max(1) as i32;
+    let sort_by = pagination().sort_by.unwrap_or_default();
+    let sort_desc = pagination().sort_desc.unwrap_or_default();
+
+    rsx! {
+        div { class: "flex justify-between items-center text-sm text-gray-500 mb-2",
+            div { class: "flex items-center",
+                "Sort by"
+                select {
+                    class: "px-2 py-1 mx-2 rounded-md border bg-white",
+                    oninput: move |evt| {
+                        let value = evt.value();
+                        pagination
+                            .with_mut(|p| {
+                                p.sort_by = if value.is_empty() { None } else { Some(value) };
+                                p.page = Some(1);
+                            });
+                    },
+                    for (field , label) in sort_fields {
+                        option { value: "{field}", selected: field == sort_by, "{label}" }
+                    }
+                }
+                button {
+                    class: "px-2 rounded-md hover:bg-gray-100",
+                    title: if sort_desc { "Descending" } else { "Ascending" },
+                    onclick: move |_| {
+                        pagination
+                            .with_mut(|p| {
+                                p.sort_desc = Some(!sort_desc);
+                                p.page = Some(1);
+                            });
+                    },
+                    if sort_desc {
+                        "↓"
+                    } else {
+                        "↑"
+                    }
+                }
+            }
+            div { class: "flex items-center",
+                button {
+                    class: "px-2 rounded-md hover:bg-gray-100 disabled:text-gray-300 disabled:hover:bg-white",
+                    disabled: page <= 1,
+                    onclick: move |_| pagination.with_mut(|p| p.page = Some(page - 1)),
+                    "‹ Prev"
+                }
+                span { class: "px-3", "Page {page} of {page_count} ({total} items)" }
+                button {
+                    class: "px-2 rounded-md hover:bg-gray-100 disabled:text-gray-300 disabled:hover:bg-white",
+                    disabled: page >= page_count,
+                    onclick: move |_| pagination.with_mut(|p| p.page = Some(page + 1)),
+                    "Next ›"
+                }
+            }
+        }
+    }
+}
+
+/// Convert the (field name, label) pairs to the owned version expected by `ListControls`.
+pub fn sort_fields(fields: &[(&str, &str)]) -> Vec<(String, String)> {
+    fields.iter().map(|(field, label)| (field.to_string(), label.to_string())).collect()
+}
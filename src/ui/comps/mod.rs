@@ -4,9 +4,15 @@ pub use nav::*;
 mod breadcrumb;
 pub use breadcrumb::*;
 
+mod graph;
+pub use graph::*;
+
 mod gt_sep;
 pub use gt_sep::*;
 
+mod list_controls;
+pub use list_controls::*;
+
 mod metamodel;
 pub use metamodel::*;
 
@@ -1,7 +1,9 @@
 mod common;
 mod nav;
+mod nav_search;
 mod nav_user_menu;
 
 pub use common::*;
 pub use nav::*;
+pub use nav_search::*;
 pub use nav_user_menu::*;
@@ -1,4 +1,4 @@
-use crate::ui::comps::NavUserMenu;
+use crate::ui::comps::{NavSearch, NavUserMenu};
 use crate::ui::routes::Route;
 use crate::ui::{UiStorage, UI_STATE};
 use dioxus::prelude::*;
@@ -50,6 +50,7 @@ fn render_itself() -> Element {
                     }
                 }
             }
+            NavSearch {}
             NavUserMenu {}
         }
     }
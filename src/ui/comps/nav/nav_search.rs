@@ -0,0 +1,122 @@
+use dioxus::prelude::*;
+
+use crate::domain::model::{ItemType, SearchHit};
+use crate::server::fns::search;
+use crate::ui::routes::Route;
+use crate::ui::{UiStorage, UI_STATE};
+
+/// The global search box, shown in the navigation bar to the authenticated users.
+pub fn NavSearch() -> Element {
+    //
+    let state = use_context::<Signal<UiStorage>>();
+    let mut query = use_signal(|| String::new());
+    let mut hits = use_signal(|| Vec::<SearchHit>::new());
+    let mut show_results = use_signal(|| false);
+    // Incremented on every input, so that the responses to outdated queries are ignored.
+    let mut query_seq = use_signal(|| 0_u64);
+
+    if *UI_STATE.app_ready.read() == false || state().current_user.is_none() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "relative text-sm sm:ml-auto sm:mr-3",
+            input {
+                class: "px-3 py-1.5 w-64 rounded-lg border border-gray-200 bg-gray-50 text-gray-600 outline-none focus:bg-white focus:border-gray-300",
+                r#type: "search",
+                placeholder: "Search",
+                value: "{query}",
+                oninput: move |evt| {
+                    let text = evt.value();
+                    query.set(text.clone());
+                    let seq = query_seq() + 1;
+                    query_seq.set(seq);
+                    if text.trim().is_empty() {
+                        hits.set(vec![]);
+                        show_results.set(false);
+                        return;
+                    }
+                    spawn(async move {
+                        match search(text, vec![], vec![], None).await {
+                            Ok(res) => {
+                                if query_seq() == seq {
+                                    hits.set(res);
+                                    show_results.set(true);
+                                }
+                            }
+                            Err(e) => log::error!(">>> [NavSearch] Failed to search. Cause: {}", e),
+                        }
+                    });
+                },
+                onfocusin: move |_| show_results.set(!query().trim().is_empty()),
+                onkeydown: move |evt| {
+                    if evt.key() == Key::Escape {
+                        show_results.set(false);
+                    }
+                },
+            }
+            if show_results() {
+                div {
+                    "style": "width: 100%; height: 1000%; padding: 0; position: fixed; top: 0; left: 0",
+                    onclick: move |_| show_results.set(false),
+                }
+                ul { class: "absolute right-0 mt-2 w-96 max-h-96 overflow-auto bg-white rounded-lg shadow-2xl py-2 z-[1000]",
+                    if hits().is_empty() {
+                        li { class: "px-4 py-2 text-gray-500", "No results." }
+                    }
+                    for hit in hits() {
+                        li {
+                            key: "{hit.item_id}",
+                            class: "px-4 py-2 hover:bg-gray-100 cursor-pointer",
+                            onclick: {
+                                let route = hit_route(&hit);
+                                move |_| {
+                                    show_results.set(false);
+                                    if let Some(route) = route.clone() {
+                                        navigator().push(route);
+                                    }
+                                }
+                            },
+                            div { class: "flex justify-between",
+                                span { class: "text-gray-700 font-medium", "{hit.title}" }
+                                span { class: "text-xs text-gray-400", "{hit_kind_label(&hit.item_type)}" }
+                            }
+                            p { class: "text-xs text-gray-500",
+                                for (fragment , highlighted) in hit.snippet_fragments() {
+                                    if highlighted {
+                                        mark { class: "bg-yellow-100 text-gray-700", "{fragment}" }
+                                    } else {
+                                        "{fragment}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn hit_route(hit: &SearchHit) -> Option<Route> {
+    let id = hit.item_id.clone();
+    match hit.item_type {
+        ItemType::Entity => Some(Route::EntityPage { id }),
+        ItemType::EntityLink => Some(Route::EntityLinkPage { id }),
+        ItemType::EntityDef => Some(Route::EntityDefPage { id }),
+        ItemType::EntityLinkDef => Some(Route::EntityLinkDefPage { id }),
+        ItemType::AttributeDef => Some(Route::AttributeDefPage { attr_def_id: id }),
+        _ => None,
+    }
+}
+
+fn hit_kind_label(item_type: &ItemType) -> &'static str {
+    match item_type {
+        ItemType::Entity => "Entity",
+        ItemType::EntityLink => "Entity Link",
+        ItemType::EntityDef => "Entity Definition",
+        ItemType::EntityLinkDef => "Entity Link Definition",
+        ItemType::AttributeDef => "Attribute Definition",
+        _ => "",
+    }
+}
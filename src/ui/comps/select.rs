@@ -107,8 +107,8 @@ pub fn SelectWithHandler(props: SelectWithHandlerProps) -> Element {
             oninput: move |evt| async move {
                 selected_item_id.set(evt.value().into());
                 log::debug!("[SelectWithHandler] set selected_item_id: {:?}", evt.value());
-                if let Ok(source_entities) = list_entities_by_def_id(selected_item_id()).await {
-                    log::debug!("[SelectWithHandler] source_entities: {:#?}", source_entities);
+                if let Ok(source_entities) = list_entities_by_def_id(selected_item_id(), None).await {
+                    log::debug!("[SelectWithHandler] source_entities: {:#?}", source_entities.items);
                 }
                 select_handler(());
             },
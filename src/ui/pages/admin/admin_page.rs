@@ -59,6 +59,11 @@ fn render_page() -> Element {
                                     to: Route::AttributeDefListPage {},
                                     "Attributes"
                                 }
+                                Link {
+                                    class: "py-2 px-4 rounded-lg transition duration-200",
+                                    to: Route::MetamodelDiagramPage {},
+                                    "Diagram"
+                                }
                             }
                             div { class: "pr-3 flex flex-col grow ml-1",
                                 h6 { class: "px-4 mb-2 pt-2 pb-1 block font-medium leading-snug tracking-normal text-gray-500 antialiased",
@@ -79,6 +84,11 @@ fn render_page() -> Element {
                                     to: Route::TagListPage {},
                                     "Tags"
                                 }
+                                Link {
+                                    class: "py-2 px-4 rounded-lg transition duration-200",
+                                    to: Route::GraphPage {},
+                                    "Graph"
+                                }
                             }
                         }
                     }
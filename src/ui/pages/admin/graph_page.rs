@@ -0,0 +1,210 @@
+use dioxus::prelude::*;
+use indexmap::IndexMap;
+
+use crate::{
+    domain::model::{EntityGraph, EntityGraphFilter, Id},
+    server::fns::{get_entity_graph, get_entity_neighborhood},
+    ui::{
+        comps::{Breadcrumb, EntityGraphView, ForceLayout, Nav, Point, Select},
+        pages::Name,
+        routes::Route,
+        UI_STATE,
+    },
+};
+
+const GRAPH_WIDTH: f64 = 1000.0;
+const GRAPH_HEIGHT: f64 = 640.0;
+
+#[component]
+pub fn GraphPage() -> Element {
+    //
+    let mut ent_defs = use_signal::<IndexMap<Id, Name>>(|| IndexMap::new());
+    let mut ent_link_defs = use_signal::<IndexMap<Id, Name>>(|| IndexMap::new());
+    let mut tags = use_signal::<IndexMap<Id, Name>>(|| IndexMap::new());
+
+    let selected_ent_def_id = use_signal(|| Id::default());
+    let selected_ent_link_def_id = use_signal(|| Id::default());
+    let selected_tag_id = use_signal(|| Id::default());
+
+    let mut graph = use_signal(|| EntityGraph::default());
+    let mut positions = use_signal::<IndexMap<Id, Point>>(|| IndexMap::new());
+    let selected_ent_id = use_signal::<Option<Id>>(|| None);
+    let mut err: Signal<Option<String>> = use_signal(|| None);
+
+    use_future(move || async move {
+        ent_defs.set(UI_STATE.get_ent_defs().await);
+        ent_link_defs.set(
+            UI_STATE
+                .get_ent_link_def_list()
+                .await
+                .into_iter()
+                .map(|eld| (eld.id, eld.name))
+                .collect(),
+        );
+        tags.set(UI_STATE.get_tags().await.into_iter().map(|(id, tag)| (id, tag.name)).collect());
+    });
+
+    // (Re)load the graph every time a filter changes.
+    use_effect(move || {
+        let filter = EntityGraphFilter {
+            ent_def_ids: to_filter_ids(selected_ent_def_id()),
+            ent_link_def_ids: to_filter_ids(selected_ent_link_def_id()),
+            tag_ids: to_filter_ids(selected_tag_id()),
+        };
+        spawn(async move {
+            match get_entity_graph(filter).await {
+                Ok(g) => {
+                    positions.set(ForceLayout::new(GRAPH_WIDTH, GRAPH_HEIGHT).run(&g, &IndexMap::new()));
+                    graph.set(g);
+                    err.set(None);
+                }
+                Err(e) => {
+                    log::error!("[GraphPage] Failed to get the entity graph. Cause: '{}'.", e);
+                    err.set(Some(e.to_string()));
+                }
+            }
+        });
+    });
+
+    let selected_ent = selected_ent_id().and_then(|id| graph().entities.into_iter().find(|ent| ent.id == id));
+
+    rsx! {
+        div { class: "flex flex-col min-h-screen bg-gray-100",
+            Nav {}
+            Breadcrumb { paths: Route::get_path(Route::GraphPage {}) }
+            div { class: "flex flex-col min-h-screen justify-center items-center drop-shadow-2xl",
+                div { class: "bg-white rounded-lg p-3 min-w-[600px] mt-[min(100px)]",
+                    div { class: "p-6",
+                        div { class: "flex justify-between mb-6",
+                            p { class: "text-lg font-medium leading-snug tracking-normal text-gray-500 antialiased",
+                                "Entities Graph"
+                            }
+                            p { class: "text-xs text-gray-400 pt-2",
+                                "{graph().entities.len()} entities, {graph().links.len()} links"
+                            }
+                        }
+                        div { class: "flex justify-between mb-4 text-sm text-gray-500",
+                            div { class: "flex flex-col",
+                                label { class: "pl-3 pb-1", "Entity Definition" }
+                                Select {
+                                    items: ent_defs,
+                                    selected_item_id: selected_ent_def_id,
+                                }
+                            }
+                            div { class: "flex flex-col",
+                                label { class: "pl-3 pb-1", "Entity Link Definition" }
+                                Select {
+                                    items: ent_link_defs,
+                                    selected_item_id: selected_ent_link_def_id,
+                                }
+                            }
+                            div { class: "flex flex-col",
+                                label { class: "pl-3 pb-1", "Tag" }
+                                Select { items: tags, selected_item_id: selected_tag_id }
+                            }
+                        }
+                        if let Some(e) = err() {
+                            p { class: "pb-4 text-red-600 text-sm", "{e}" }
+                        }
+                        EntityGraphView {
+                            graph: graph(),
+                            positions: positions(),
+                            width: GRAPH_WIDTH,
+                            height: GRAPH_HEIGHT,
+                            selected_ent_id,
+                        }
+                        div { class: "flex justify-between mt-4 min-h-8 text-sm text-gray-500",
+                            if let Some(ent) = selected_ent {
+                                p { class: "pt-1", "{ent.kind}: {ent.listing_attr_value}" }
+                                div { class: "flex",
+                                    button {
+                                        class: "bg-gray-100 hover:bg-green-100 drop-shadow-sm px-4 mr-2 rounded-md",
+                                        onclick: {
+                                            let id = ent.id.clone();
+                                            move |_| {
+                                                let id = id.clone();
+                                                async move {
+                                                    handle_expand(id, graph, positions, err).await;
+                                                }
+                                            }
+                                        },
+                                        "Expand"
+                                    }
+                                    Link {
+                                        class: "bg-gray-100 hover:bg-green-100 drop-shadow-sm px-4 rounded-md",
+                                        to: Route::EntityPage { id: ent.id.clone() },
+                                        "Open"
+                                    }
+                                }
+                            } else {
+                                p { class: "pt-1 text-xs text-gray-400",
+                                    "Click on an entity to select it, double click to open it."
+                                }
+                            }
+                        }
+                        div { class: "flex gap-3 mt-2 text-xs text-gray-500",
+                            span { "Export:" }
+                            for (format , label) in [("graphml", "GraphML"), ("dot", "DOT"), ("json", "JSON")] {
+                                a {
+                                    class: "hover:text-gray-800",
+                                    href: export_url(
+                                        format,
+                                        selected_ent_def_id(),
+                                        selected_ent_link_def_id(),
+                                        selected_tag_id(),
+                                    ),
+                                    "{label}"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Add the neighborhood of an entity to the graph, and recompute the layout,
+/// starting from the positions of the nodes that are already shown.
+async fn handle_expand(
+    id: Id,
+    mut graph: Signal<EntityGraph>,
+    mut positions: Signal<IndexMap<Id, Point>>,
+    mut err: Signal<Option<String>>,
+) {
+    //
+    match get_entity_neighborhood(id.clone()).await {
+        Ok(neighborhood) => {
+            let mut g = graph();
+            g.merge(neighborhood);
+            let mut layout = ForceLayout::new(GRAPH_WIDTH, GRAPH_HEIGHT);
+            layout.iterations = 100;
+            positions.set(layout.run(&g, &positions()));
+            graph.set(g);
+        }
+        Err(e) => {
+            log::error!(
+                "[GraphPage] Failed to get the neighborhood of entity w/ id:'{}'. Cause: '{}'.",
+                id,
+                e
+            );
+            err.set(Some(e.to_string()));
+        }
+    }
+}
+
+/// The URL of exporting the graph (in the provided format) that matches the current filters.
+fn export_url(format: &str, ent_def_id: Id, ent_link_def_id: Id, tag_id: Id) -> String {
+    format!(
+        "/api/v1/graph/export?format={}&ent_def_ids={}&ent_link_def_ids={}&tag_ids={}",
+        format, ent_def_id, ent_link_def_id, tag_id
+    )
+}
+
+fn to_filter_ids(id: Id) -> Vec<Id> {
+    if id.is_empty() {
+        vec![]
+    } else {
+        vec![id]
+    }
+}
@@ -0,0 +1,103 @@
+use dioxus::prelude::*;
+
+use crate::{
+    domain::model::MetamodelDiagram,
+    ui::{
+        comps::{Breadcrumb, MetamodelDiagramView, Nav},
+        routes::Route,
+        UI_STATE,
+    },
+};
+
+#[component]
+pub fn MetamodelDiagramPage() -> Element {
+    //
+    let mut diagram = use_signal(|| MetamodelDiagram::default());
+    let mut loaded = use_signal(|| false);
+    // The exported content, as (file name, content) pair.
+    let mut export = use_signal::<Option<(String, String)>>(|| None);
+
+    use_future(move || async move {
+        let ent_defs = UI_STATE.get_ent_defs_list().await;
+        let ent_link_defs = UI_STATE.get_ent_link_def_list().await;
+        diagram.set(MetamodelDiagram::new(ent_defs, ent_link_defs));
+        loaded.set(true);
+    });
+
+    rsx! {
+        div { class: "flex flex-col min-h-screen bg-gray-100",
+            Nav {}
+            Breadcrumb { paths: Route::get_path(Route::MetamodelDiagramPage {}) }
+            div { class: "flex flex-col min-h-screen justify-center items-center drop-shadow-2xl",
+                div { class: "bg-white rounded-lg p-3 min-w-[600px] mt-[min(100px)]",
+                    div { class: "p-6",
+                        div { class: "flex justify-between mb-6",
+                            p { class: "text-lg font-medium leading-snug tracking-normal text-gray-500 antialiased",
+                                "Definitions Diagram"
+                            }
+                            div { class: "flex text-sm",
+                                button {
+                                    class: "bg-gray-100 hover:bg-green-100 drop-shadow-sm px-4 mr-2 rounded-md",
+                                    onclick: move |_| {
+                                        export.set(Some(("metamodel.mmd".into(), diagram().to_mermaid())));
+                                    },
+                                    "Export as Mermaid"
+                                }
+                                button {
+                                    class: "bg-gray-100 hover:bg-green-100 drop-shadow-sm px-4 rounded-md",
+                                    onclick: move |_| {
+                                        export.set(Some(("metamodel.puml".into(), diagram().to_plantuml())));
+                                    },
+                                    "Export as PlantUML"
+                                }
+                            }
+                        }
+                        if !loaded() {
+                            p { class: "pb-4 text-gray-500", "Loading..." }
+                        } else if diagram().ent_defs.is_empty() {
+                            p { class: "pb-4 text-gray-500", "There are no entities definitions." }
+                        } else {
+                            div { class: "overflow-auto max-w-[90vw] max-h-[75vh]",
+                                MetamodelDiagramView { diagram: diagram() }
+                            }
+                        }
+                        if let Some((file_name, content)) = export() {
+                            div { class: "flex flex-col mt-6",
+                                div { class: "flex justify-between mb-2 text-sm",
+                                    a {
+                                        class: "text-gray-500 hover:text-gray-800 underline",
+                                        href: "data:text/plain;charset=utf-8,{percent_encode(&content)}",
+                                        download: "{file_name}",
+                                        "Download {file_name}"
+                                    }
+                                    button {
+                                        class: "text-gray-500 hover:text-gray-800 px-2",
+                                        onclick: move |_| export.set(None),
+                                        "X"
+                                    }
+                                }
+                                textarea {
+                                    class: "px-3 py-2 min-h-60 font-mono text-xs text-gray-600 rounded-md border",
+                                    readonly: true,
+                                    value: "{content}",
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Percent-encode a text, so that it can be used as the content of a `data:` URL.
+fn percent_encode(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() * 3);
+    for b in text.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => out.push(b as char),
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    out
+}
@@ -1,2 +1,8 @@
 mod admin_page;
 pub use admin_page::*;
+
+mod graph_page;
+pub use graph_page::*;
+
+mod metamodel_diagram_page;
+pub use metamodel_diagram_page::*;
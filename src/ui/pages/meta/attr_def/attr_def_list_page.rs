@@ -1,10 +1,10 @@
 use crate::domain::model::Id;
 
 use crate::{
-    domain::model::{AttributeDef, Tag},
+    domain::model::{AttributeDef, Pagination, Tag},
     server::fns::list_attribute_defs,
     ui::{
-        comps::{Breadcrumb, Nav},
+        comps::{sort_fields, Breadcrumb, ListControls, Nav},
         routes::Route,
         UI_STATE,
     },
@@ -16,16 +16,24 @@ use indexmap::IndexMap;
 pub fn AttributeDefListPage() -> Element {
     //
     let mut entries = use_signal::<Vec<AttributeDef>>(|| vec![]);
+    let mut total = use_signal(|| 0_i64);
+    let pagination = use_signal(|| Pagination::default());
 
     let mut tags = use_signal(|| IndexMap::new());
 
     use_future(move || async move {
         tags.set(UI_STATE.get_tags().await);
+    });
 
-        if let Ok(attr_defs) = list_attribute_defs().await {
-            log::debug!(">>> Got from get_attribute_defs(): {:?}", attr_defs);
-            entries.set(attr_defs);
-        }
+    use_effect(move || {
+        let pagination = pagination();
+        spawn(async move {
+            if let Ok(page) = list_attribute_defs(Some(pagination)).await {
+                log::debug!(">>> Got from get_attribute_defs(): {:?}", page.items);
+                total.set(page.total);
+                entries.set(page.items);
+            }
+        });
     });
 
     rsx! {
@@ -45,6 +53,11 @@ pub fn AttributeDefListPage() -> Element {
                                 "+"
                             }
                         }
+                        ListControls {
+                            sort_fields: sort_fields(&[("name", "Name"), ("description", "Description"), ("value_type", "Type")]),
+                            pagination,
+                            total: total(),
+                        }
                         if entries.is_empty() {
                             p { class: "pb-4 text-gray-500", "There are no entries." }
                         }
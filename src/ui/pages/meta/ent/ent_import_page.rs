@@ -0,0 +1,216 @@
+use crate::{
+    domain::model::{AttributeDef, CsvColumnMapping, CsvImportMode, CsvImportReport, Id},
+    server::fns::{import_entities_csv, preview_entities_csv_import},
+    ui::{
+        comps::{Breadcrumb, Nav, Select},
+        routes::Route,
+        UI_STATE,
+    },
+};
+use dioxus::prelude::*;
+use indexmap::IndexMap;
+
+/// The page for importing entities (of one kind) in bulk, from a CSV file.<br/>
+/// The columns are first mapped to the attributes and the rows are validated (as a preview), before being imported.
+pub fn EntityImportPage() -> Element {
+    //
+    let mut ent_kinds = use_signal::<IndexMap<Id, String>>(|| IndexMap::new());
+    let selected_kind_id = use_signal(|| Id::default());
+    let mut attr_defs = use_signal::<Vec<AttributeDef>>(|| Vec::new());
+    let mut content = use_signal(|| String::new());
+    let mut mapping = use_signal::<Vec<CsvColumnMapping>>(|| Vec::new());
+    let mut mode = use_signal(|| CsvImportMode::AllOrNothing);
+    let mut report = use_signal::<Option<CsvImportReport>>(|| None);
+    let mut err: Signal<Option<String>> = use_signal(|| None);
+
+    use_future(move || async move {
+        ent_kinds.set(UI_STATE.get_ent_defs().await);
+    });
+
+    use_effect(move || {
+        let kind_id = selected_kind_id();
+        let attrs = UI_STATE
+            .get_ent_def_sync(&kind_id)
+            .map(|ent_def| ent_def.attributes)
+            .unwrap_or_default();
+        attr_defs.set(attrs);
+        // The mapping of a previous kind does not apply anymore.
+        mapping.set(Vec::new());
+        report.set(None);
+    });
+
+    let preview = move |_| async move {
+        match preview_entities_csv_import(selected_kind_id(), content(), mapping()).await {
+            Ok(rep) => {
+                mapping.set(rep.mapping.clone());
+                report.set(Some(rep));
+                err.set(None);
+            }
+            Err(e) => err.set(Some(e.to_string())),
+        }
+    };
+
+    let import = move |_| async move {
+        match import_entities_csv(selected_kind_id(), content(), mapping(), mode()).await {
+            Ok(rep) => {
+                report.set(Some(rep));
+                err.set(None);
+            }
+            Err(e) => err.set(Some(e.to_string())),
+        }
+    };
+
+    rsx! {
+        div { class: "flex flex-col min-h-screen bg-gray-100",
+            Nav {}
+            Breadcrumb { paths: Route::get_path(Route::EntityImportPage {}) }
+            div { class: "flex flex-col min-h-screen justify-start items-center drop-shadow-2xl",
+                div { class: "bg-white rounded-md p-3 min-w-[700px] mt-[min(100px)]",
+                    div { class: "p-6",
+                        div { class: "flex justify-between mb-8",
+                            p { class: "text-lg font-medium leading-snug tracking-normal text-gray-500 antialiased",
+                                "Import Entities from CSV"
+                            }
+                            Link {
+                                class: "text-gray-500 hover:text-gray-800 px-2 rounded-xl transition duration-200",
+                                to: Route::EntityListPage {},
+                                "X"
+                            }
+                        }
+                        div { class: "flex py-2",
+                            p { class: "py-2 pr-4 text-gray-600 block min-w-32", "Kind:" }
+                            Select { items: ent_kinds, selected_item_id: selected_kind_id }
+                        }
+                        div { class: "flex py-2",
+                            p { class: "py-2 pr-4 text-gray-600 block min-w-32", "CSV file:" }
+                            input {
+                                class: "py-1 text-gray-600",
+                                r#type: "file",
+                                accept: ".csv,text/csv",
+                                onchange: move |evt| async move {
+                                    if let Some(file_engine) = evt.files() {
+                                        if let Some(name) = file_engine.files().first() {
+                                            if let Some(text) = file_engine.read_file_to_string(name).await {
+                                                content.set(text);
+                                                mapping.set(Vec::new());
+                                                report.set(None);
+                                            }
+                                        }
+                                    }
+                                },
+                            }
+                        }
+                        div { class: "flex py-2",
+                            p { class: "py-2 pr-4 text-gray-600 block min-w-32", "Mode:" }
+                            select {
+                                class: "px-3 py-1 min-w-80",
+                                oninput: move |evt| {
+                                    mode.set(match evt.value().as_str() {
+                                        "skip" => CsvImportMode::SkipBadRows,
+                                        _ => CsvImportMode::AllOrNothing,
+                                    });
+                                },
+                                option {
+                                    value: "all",
+                                    selected: mode() == CsvImportMode::AllOrNothing,
+                                    "All or nothing"
+                                }
+                                option {
+                                    value: "skip",
+                                    selected: mode() == CsvImportMode::SkipBadRows,
+                                    "Skip the rows with errors"
+                                }
+                            }
+                        }
+                        if !mapping().is_empty() {
+                            p { class: "pt-6 pb-2 text-gray-500", "Columns mapping" }
+                            for (idx , m) in mapping().into_iter().enumerate() {
+                                div { class: "flex py-1",
+                                    p { class: "py-1 pr-4 text-gray-600 block min-w-32", "{m.column}" }
+                                    select {
+                                        class: "px-3 py-1 min-w-80",
+                                        oninput: move |evt| {
+                                            let value = evt.value();
+                                            mapping.write()[idx].attr_def_id = match value.is_empty() {
+                                                true => None,
+                                                false => Some(value.into()),
+                                            };
+                                            // The rows need to be validated again, as per the new mapping.
+                                            report.set(None);
+                                        },
+                                        option { value: "", selected: m.attr_def_id.is_none(), "(ignored)" }
+                                        for a in attr_defs() {
+                                            option {
+                                                value: "{a.id}",
+                                                selected: m.attr_def_id.as_ref() == Some(&a.id),
+                                                "{a.name}"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        div { class: "flex justify-end mt-8 gap-2",
+                            button {
+                                class: "bg-gray-100 hover:bg-green-100 disabled:text-gray-300 hover:disabled:bg-gray-100 drop-shadow-sm px-4 rounded-md",
+                                disabled: selected_kind_id().is_empty() || content().is_empty(),
+                                onclick: preview,
+                                "Preview"
+                            }
+                            button {
+                                class: "bg-gray-100 hover:bg-green-100 disabled:text-gray-300 hover:disabled:bg-gray-100 drop-shadow-sm px-4 rounded-md",
+                                // Once imported, the same rows are not imported again.
+                                disabled: report().is_none_or(|rep| rep.imported > 0),
+                                onclick: import,
+                                "Import"
+                            }
+                        }
+                        if let Some(e) = err() {
+                            p { class: "pt-4 text-red-500", "{e}" }
+                        }
+                        if let Some(rep) = report() {
+                            ImportReport { report: rep }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ImportReport(report: CsvImportReport) -> Element {
+    //
+    rsx! {
+        div { class: "pt-6 text-sm text-gray-600",
+            p {
+                "Rows: {report.total_rows}, valid: {report.valid_rows}, with errors: {report.total_rows - report.valid_rows}, imported: {report.imported}."
+            }
+            if !report.errors.is_empty() {
+                p { class: "pt-4 pb-2 text-gray-500", "Errors" }
+                for e in report.errors.iter() {
+                    p { class: "text-red-500",
+                        "Line {e.line}"
+                        if let Some(column) = &e.column {
+                            " (column '{column}')"
+                        }
+                        ": {e.message}"
+                    }
+                }
+            }
+            if !report.preview.is_empty() && report.imported == 0 {
+                p { class: "pt-4 pb-2 text-gray-500", "Preview of the valid rows" }
+                for row in report.preview.iter() {
+                    div { class: "flex flex-wrap gap-x-4 py-1 border-b",
+                        for (name , value) in row.iter() {
+                            span {
+                                span { class: "text-gray-400", "{name}: " }
+                                "{value}"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,59 +1,341 @@
+use std::collections::HashMap;
+
 use dioxus::prelude::*;
 
 use crate::{
-    domain::model::Entity,
-    server::fns::list_entities,
+    domain::model::{AttributeDef, Entity, Id, Pagination, SavedView},
+    server::fns::{
+        create_saved_view, get_saved_view, list_attribute_defs, list_entities, list_entities_attributes_values, list_saved_view_entities,
+        list_saved_views, remove_saved_view,
+    },
     ui::{
-        comps::{Breadcrumb, Nav},
+        comps::{sort_fields, Breadcrumb, ListControls, Nav},
         routes::Route,
-        UI_STATE,
+        UiStorage, UI_STATE,
     },
 };
 
 #[component]
 pub fn EntityListPage() -> Element {
+    rsx! {
+        EntityList { view_id: None }
+    }
+}
+
+/// The list of entities, as per a saved view.
+#[component]
+pub fn EntitySavedViewPage(id: Id) -> Element {
+    rsx! {
+        EntityList { view_id: Some(id) }
+    }
+}
+
+#[component]
+fn EntityList(view_id: Option<Id>) -> Element {
     //
     let mut entries = use_signal::<Vec<Entity>>(|| vec![]);
+    let mut total = use_signal(|| 0_i64);
+    let mut pagination = use_signal(|| Pagination::default());
+    let mut view = use_signal::<Option<SavedView>>(|| None);
+    let mut views = use_signal::<Vec<SavedView>>(|| vec![]);
+    // The attributes shown as columns, and their values as (entity id, attribute def id) -> value.
+    let mut columns = use_signal::<Vec<AttributeDef>>(|| vec![]);
+    let mut values = use_signal::<HashMap<(Id, Id), String>>(|| HashMap::new());
+    let mut err = use_signal(|| Option::<String>::None);
 
-    use_future(move || async move {
-        UI_STATE.get_ent_defs_list().await;
-        if let Ok(entitites) = list_entities().await {
-            entries.set(entitites);
-        }
+    use_effect(move || {
+        spawn(async move {
+            if let Ok(items) = list_saved_views().await {
+                views.set(items);
+            }
+        });
+    });
+
+    let paths = match &view_id {
+        Some(id) => Route::get_path(Route::EntitySavedViewPage { id: id.clone() }),
+        None => Route::get_path(Route::EntityListPage {}),
+    };
+    let current_view_id = view_id.clone();
+
+    // Load the view, if any, when navigating to another one.
+    use_effect(use_reactive!(|(view_id,)| {
+        spawn(async move {
+            err.set(None);
+            let mut columns_defs = vec![];
+            let loaded = match &view_id {
+                Some(id) => match get_saved_view(id.clone()).await {
+                    Ok(Some(loaded)) => {
+                        if !loaded.columns.is_empty() {
+                            if let Ok(page) = list_attribute_defs(None).await {
+                                columns_defs = loaded
+                                    .columns
+                                    .iter()
+                                    .filter_map(|id| page.items.iter().find(|attr_def| &attr_def.id == id).cloned())
+                                    .collect();
+                            }
+                        }
+                        Some(loaded)
+                    }
+                    Ok(None) => {
+                        err.set(Some("The saved view does not exist or it is not shared.".into()));
+                        None
+                    }
+                    Err(e) => {
+                        err.set(Some(e.to_string()));
+                        None
+                    }
+                },
+                None => None,
+            };
+            columns.set(columns_defs);
+            pagination.set(loaded.as_ref().map(|v| v.pagination()).unwrap_or_default());
+            view.set(loaded);
+        });
+    }));
+
+    use_effect(move || {
+        let pagination = pagination();
+        let view_id = view().map(|v| v.id);
+        spawn(async move {
+            UI_STATE.get_ent_defs_list().await;
+            let result = match view_id {
+                Some(id) => list_saved_view_entities(id, Some(pagination)).await,
+                None => list_entities(Some(pagination)).await,
+            };
+            match result {
+                Ok(page) => {
+                    let attr_def_ids: Vec<Id> = columns().iter().map(|attr_def| attr_def.id.clone()).collect();
+                    let ent_ids: Vec<Id> = page.items.iter().map(|ent| ent.id.clone()).collect();
+                    let mut vals = HashMap::new();
+                    if !attr_def_ids.is_empty() {
+                        if let Ok(entries) = list_entities_attributes_values(ent_ids, attr_def_ids).await {
+                            vals = entries
+                                .into_iter()
+                                .map(|(ent_id, def_id, value)| ((ent_id, def_id), value))
+                                .collect();
+                        }
+                    }
+                    values.set(vals);
+                    total.set(page.total);
+                    entries.set(page.items);
+                }
+                Err(e) => err.set(Some(e.to_string())),
+            }
+        });
     });
 
+    let title = view().map(|v| v.name).unwrap_or("Entities".into());
+
     rsx! {
         div { class: "flex flex-col min-h-screen bg-gray-100",
             Nav {}
-            Breadcrumb { paths: Route::get_path(Route::EntityListPage {}) }
-            div { class: "flex flex-col min-h-screen justify-center items-center drop-shadow-2xl",
+            Breadcrumb { paths }
+            div { class: "flex min-h-screen justify-center items-start drop-shadow-2xl",
+                SavedViewsSidebar {
+                    views,
+                    current: current_view_id,
+                    view,
+                    pagination,
+                }
                 div { class: "bg-white rounded-lg p-3 min-w-[600px]  mt-[min(100px)]",
                     div { class: "p-6",
                         div { class: "flex justify-between mb-8",
                             p { class: "text-lg font-medium leading-snug tracking-normal text-gray-500 antialiased",
-                                "Entities"
+                                "{title}"
                             }
-                            Link {
-                                class: "text-gray-500 text-3xl font-extralight hover:text-gray-800 px-2 rounded-xl transition duration-200",
-                                to: Route::EntityNewPage {},
-                                "+"
+                            div { class: "flex items-center",
+                                Link {
+                                    class: "text-gray-500 text-sm hover:text-gray-800 px-2 rounded-xl transition duration-200",
+                                    to: Route::EntityImportPage {},
+                                    "Import CSV"
+                                }
+                                Link {
+                                    class: "text-gray-500 text-3xl font-extralight hover:text-gray-800 px-2 rounded-xl transition duration-200",
+                                    to: Route::EntityNewPage {},
+                                    "+"
+                                }
                             }
                         }
+                        ListControls {
+                            sort_fields: sort_fields(&[("kind", "Kind"), ("listing_attr_value", "Name"), ("listing_attr_name", "Name Attribute")]),
+                            pagination,
+                            total: total(),
+                        }
+                        if let Some(e) = err() {
+                            p { class: "pb-4 text-red-500", "{e}" }
+                        }
                         if entries.is_empty() {
                             p { class: "pb-4 text-gray-500", "There are no entries." }
                         }
                         for e in entries() {
-                            EntityCard { ent: e.clone() }
+                            EntityCard {
+                                columns: columns()
+                                    .iter()
+                                    .map(|attr_def| {
+                                        let value = values().get(&(e.id.clone(), attr_def.id.clone())).cloned().unwrap_or_default();
+                                        (attr_def.name.clone(), value)
+                                    })
+                                    .collect::<Vec<(String, String)>>(),
+                                ent: e.clone(),
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The sidebar that lists the saved views (the own ones and the shared ones),
+/// and allows saving the current sorting, or the current view (with its filter and columns), as a new view.<br/>
+/// Only the own views can be removed.
+#[component]
+fn SavedViewsSidebar(
+    mut views: Signal<Vec<SavedView>>,
+    current: Option<Id>,
+    view: Signal<Option<SavedView>>,
+    pagination: Signal<Pagination>,
+) -> Element {
+    //
+    let state = use_context::<Signal<UiStorage>>();
+    let user_id = state().current_user.map(|user| user.id);
+    let mut name = use_signal(|| String::new());
+    let mut shared = use_signal(|| false);
+    let mut def_id = use_signal(|| String::new());
+    let mut err = use_signal(|| Option::<String>::None);
+    let mut ent_defs = use_signal::<Vec<(Id, String)>>(|| vec![]);
+
+    use_future(move || async move {
+        let defs = UI_STATE.get_ent_defs_list().await;
+        ent_defs.set(defs.into_iter().map(|ent_def| (ent_def.id, ent_def.name)).collect());
+    });
+
+    // Save the provided view (its definition, filter and columns) as a new one,
+    // with the entered name and the current sorting.
+    let save = move |new_view: SavedView| {
+        let p = pagination();
+        let new_view = SavedView {
+            name: name().trim().to_string(),
+            shared: shared(),
+            sort_by: p.sort_by,
+            sort_desc: p.sort_desc.unwrap_or_default(),
+            ..new_view
+        };
+        let owner_id = state().current_user.map(|user| user.id).unwrap_or_default();
+        spawn(async move {
+            match create_saved_view(new_view.clone()).await {
+                Ok(id) => {
+                    views.with_mut(|vs| vs.push(SavedView { id, owner_id, ..new_view }));
+                    name.set(String::new());
+                    err.set(None);
+                }
+                Err(e) => err.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    rsx! {
+        div { class: "bg-white rounded-lg p-4 w-60 mr-4 mt-[min(100px)] text-sm text-gray-600",
+            p { class: "font-medium text-gray-500 mb-3", "Saved Views" }
+            Link {
+                class: if current.is_none() { "block px-2 py-1 rounded-md bg-gray-100" } else { "block px-2 py-1 rounded-md hover:bg-gray-100" },
+                to: Route::EntityListPage {},
+                "All entities"
+            }
+            for v in views() {
+                div { class: "flex justify-between items-center",
+                    Link {
+                        class: if current.as_ref() == Some(&v.id) { "grow px-2 py-1 rounded-md bg-gray-100" } else { "grow px-2 py-1 rounded-md hover:bg-gray-100" },
+                        to: Route::EntitySavedViewPage {
+                            id: v.id.clone(),
+                        },
+                        "{v.name}"
+                        if v.shared {
+                            span { class: "text-xs text-gray-400 pl-1", "(shared)" }
+                        }
+                    }
+                    if user_id.as_ref() == Some(&v.owner_id) {
+                        button {
+                            class: "text-xs text-gray-400 hover:text-red-500 px-1",
+                            title: "Remove",
+                            onclick: {
+                                let id = v.id.clone();
+                                move |_| {
+                                    let id = id.clone();
+                                    spawn(async move {
+                                        match remove_saved_view(id.clone()).await {
+                                            Ok(_) => views.with_mut(|vs| vs.retain(|v| v.id != id)),
+                                            Err(e) => err.set(Some(e.to_string())),
+                                        }
+                                    });
+                                }
+                            },
+                            "x"
                         }
                     }
                 }
             }
+            div { class: "flex flex-col mt-4 pt-3 border-t",
+                input {
+                    class: "px-2 py-1 mb-2 rounded-md border",
+                    placeholder: "New view name",
+                    value: "{name}",
+                    oninput: move |evt| name.set(evt.value()),
+                }
+                select {
+                    class: "px-2 py-1 mb-2 rounded-md border bg-white",
+                    oninput: move |evt| def_id.set(evt.value()),
+                    option { value: "", "All definitions" }
+                    for (id , def_name) in ent_defs() {
+                        option { value: "{id}", selected: id.as_str() == def_id(), "{def_name}" }
+                    }
+                }
+                label { class: "flex items-center mb-2",
+                    input {
+                        class: "mr-2",
+                        r#type: "checkbox",
+                        checked: shared(),
+                        oninput: move |evt| shared.set(evt.checked()),
+                    }
+                    "Shared"
+                }
+                button {
+                    class: "bg-gray-100 hover:bg-green-100 disabled:text-gray-300 hover:disabled:bg-gray-100 drop-shadow-sm px-4 py-1 rounded-md",
+                    disabled: name().trim().is_empty(),
+                    onclick: move |_| {
+                        save(SavedView {
+                            def_id: if def_id().is_empty() { None } else { Some(Id::new_from(def_id())) },
+                            ..SavedView::default()
+                        })
+                    },
+                    "Save view"
+                }
+                button {
+                    class: "bg-gray-100 hover:bg-green-100 disabled:text-gray-300 hover:disabled:bg-gray-100 drop-shadow-sm px-4 py-1 mt-2 rounded-md",
+                    title: "Save the opened view, with its filter and columns, as a new one",
+                    disabled: name().trim().is_empty() || view().is_none(),
+                    onclick: move |_| {
+                        if let Some(current) = view() {
+                            save(SavedView {
+                                def_id: current.def_id,
+                                filter: current.filter,
+                                columns: current.columns,
+                                ..SavedView::default()
+                            })
+                        }
+                    },
+                    "Save current view"
+                }
+                if let Some(e) = err() {
+                    p { class: "pt-2 text-xs text-red-500", "{e}" }
+                }
+            }
         }
     }
 }
 
 #[component]
-fn EntityCard(ent: Entity) -> Element {
+fn EntityCard(ent: Entity, columns: Vec<(String, String)>) -> Element {
     //
     log::debug!("[EntityCard] ent: {:?}", ent);
     rsx! {
@@ -78,6 +360,16 @@ fn EntityCard(ent: Entity) -> Element {
                         img { class: "h-4 w-4 mt-1", src: "/assets/struct.png" }
                     }
                 }
+                if !columns.is_empty() {
+                    div { class: "flex flex-wrap text-xs text-gray-500 pt-1 pl-2",
+                        for (name , value) in columns {
+                            p { class: "pr-4",
+                                span { class: "text-gray-400", "{name}: " }
+                                "{value}"
+                            }
+                        }
+                    }
+                }
             }
         }
     }
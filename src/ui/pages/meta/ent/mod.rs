@@ -1,3 +1,6 @@
+mod ent_import_page;
+pub use ent_import_page::*;
+
 mod ent_list_page;
 pub use ent_list_page::*;
 
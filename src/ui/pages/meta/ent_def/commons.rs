@@ -10,8 +10,8 @@ use crate::server::fns::list_attribute_defs;
 pub async fn fetch_all_attr_defs() -> IndexMap<Id, (String, Option<String>)> {
     //
     let mut entries = IndexMap::new();
-    if let Ok(attr_defs) = list_attribute_defs().await {
-        attr_defs.iter().for_each(|attr_def| {
+    if let Ok(attr_defs) = list_attribute_defs(None).await {
+        attr_defs.items.iter().for_each(|attr_def| {
             entries.insert(attr_def.id.clone(), (attr_def.name.clone(), attr_def.description.clone()));
         });
     }
@@ -119,6 +119,29 @@ pub fn EntityDefPage(props: EntityDefPageProps) -> Element {
                             action_done,
                             err,
                         }
+                        div { class: "flex gap-3 mt-6 text-sm text-gray-500",
+                            span { "Export entities:" }
+                            a {
+                                class: "hover:text-gray-800",
+                                href: "/api/v1/entity-defs/{id}/export?format=csv",
+                                "CSV"
+                            }
+                            a {
+                                class: "hover:text-gray-800",
+                                href: "/api/v1/entity-defs/{id}/export?format=jsonl",
+                                "JSON Lines"
+                            }
+                            a {
+                                class: "hover:text-gray-800",
+                                href: "/api/v1/entity-defs/{id}/export?format=csv&links=true",
+                                "CSV with links"
+                            }
+                            a {
+                                class: "hover:text-gray-800",
+                                href: "/api/v1/entity-defs/{id}/export/links?format=csv",
+                                "Links (edges)"
+                            }
+                        }
                         div { class: "flex justify-between mt-8",
                             button {
                                 class: "text-red-300 hover:text-red-600 hover:bg-red-100 drop-shadow-sm px-4 rounded-md",
@@ -1,10 +1,10 @@
 use dioxus::prelude::*;
 
 use crate::{
-    domain::model::EntityLink,
+    domain::model::{EntityLink, Pagination},
     server::fns::list_entity_links,
     ui::{
-        comps::{Breadcrumb, Nav},
+        comps::{sort_fields, Breadcrumb, ListControls, Nav},
         routes::Route,
         UI_STATE,
     },
@@ -14,28 +14,35 @@ use crate::{
 pub fn EntityLinkListPage() -> Element {
     //
     let mut entries = use_signal::<Vec<(EntityLink, String, String)>>(|| vec![]);
+    let mut total = use_signal(|| 0_i64);
+    let pagination = use_signal(|| Pagination::default());
 
-    use_future(move || async move {
-        match list_entity_links().await {
-            Ok(items) => {
-                UI_STATE.get_ent_defs().await;
-                UI_STATE.get_ent_link_def_list().await;
-                let entries_tuples = items
-                    .into_iter()
-                    .map(|item| {
-                        let ent_link_def = UI_STATE.get_ent_link_def_sync(&item.def_id).unwrap();
-                        let source_ent_def = UI_STATE.get_ent_def_sync(&ent_link_def.source_entity_def_id).unwrap();
-                        let target_ent_def = UI_STATE.get_ent_def_sync(&ent_link_def.target_entity_def_id).unwrap();
-                        (item, source_ent_def.name, target_ent_def.name)
-                    })
-                    .collect();
-                entries.set(entries_tuples);
-            }
-            Err(e) => {
-                // TODO: Capture the error and display it.
-                log::error!("Failed to list entity links: {}", e)
+    use_effect(move || {
+        let pagination = pagination();
+        spawn(async move {
+            match list_entity_links(Some(pagination)).await {
+                Ok(page) => {
+                    total.set(page.total);
+                    let items = page.items;
+                    UI_STATE.get_ent_defs().await;
+                    UI_STATE.get_ent_link_def_list().await;
+                    let entries_tuples = items
+                        .into_iter()
+                        .map(|item| {
+                            let ent_link_def = UI_STATE.get_ent_link_def_sync(&item.def_id).unwrap();
+                            let source_ent_def = UI_STATE.get_ent_def_sync(&ent_link_def.source_entity_def_id).unwrap();
+                            let target_ent_def = UI_STATE.get_ent_def_sync(&ent_link_def.target_entity_def_id).unwrap();
+                            (item, source_ent_def.name, target_ent_def.name)
+                        })
+                        .collect();
+                    entries.set(entries_tuples);
+                }
+                Err(e) => {
+                    // TODO: Capture the error and display it.
+                    log::error!("Failed to list entity links: {}", e)
+                }
             }
-        }
+        });
     });
 
     rsx! {
@@ -55,6 +62,11 @@ pub fn EntityLinkListPage() -> Element {
                                 "+"
                             }
                         }
+                        ListControls {
+                            sort_fields: sort_fields(&[("kind", "Kind"), ("source", "Source"), ("target", "Target")]),
+                            pagination,
+                            total: total(),
+                        }
                         if entries.is_empty() {
                             p { class: "pb-4 text-gray-500", "There are no entries." }
                         }
@@ -64,10 +64,10 @@ pub fn EntityLinkNewPage() -> Element {
             let def_id = kind_id.clone();
             spawn(async move {
                 log::debug!("[EntityLinkNewPage] Loading source entities by def id:'{}' ...", def_id);
-                match list_entities_by_def_id(ent_link_def.source_entity_def_id).await {
+                match list_entities_by_def_id(ent_link_def.source_entity_def_id, None).await {
                     Ok(source_entities) => {
                         let mut id_name_map = IndexMap::new();
-                        for ent in source_entities {
+                        for ent in source_entities.items {
                             id_name_map.insert(ent.id, format!("{}: {}", ent.listing_attr_name, ent.listing_attr_value));
                         }
                         source_entities_id_name.set(id_name_map);
@@ -76,10 +76,10 @@ pub fn EntityLinkNewPage() -> Element {
                         log::error!("[EntityLinkNewPage] Error loading source entities by def id:'{}': {}", def_id, e);
                     }
                 }
-                match list_entities_by_def_id(ent_link_def.target_entity_def_id).await {
+                match list_entities_by_def_id(ent_link_def.target_entity_def_id, None).await {
                     Ok(target_entities) => {
                         let mut id_name_map = IndexMap::new();
-                        for ent in target_entities {
+                        for ent in target_entities.items {
                             id_name_map.insert(ent.id, format!("{}: {}", ent.listing_attr_name, ent.listing_attr_value));
                         }
                         target_entities_id_name.set(id_name_map);
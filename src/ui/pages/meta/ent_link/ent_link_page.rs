@@ -258,10 +258,10 @@ async fn init(
             return;
         }
     }
-    match list_entities_by_def_id(source_entity_def_id()).await {
+    match list_entities_by_def_id(source_entity_def_id(), None).await {
         Ok(source_entities) => {
             let mut id_name_map = IndexMap::new();
-            for ent in source_entities {
+            for ent in source_entities.items {
                 id_name_map.insert(ent.id, ent.listing_attr_value);
             }
             source_entities_id_name.set(id_name_map);
@@ -270,10 +270,10 @@ async fn init(
             log::error!("[EntityLinkNewPage] Error loading source entities by def id:'{}': {}", kind_id(), e);
         }
     }
-    match list_entities_by_def_id(target_entity_def_id()).await {
+    match list_entities_by_def_id(target_entity_def_id(), None).await {
         Ok(target_entities) => {
             let mut id_name_map = IndexMap::new();
-            for ent in target_entities {
+            for ent in target_entities.items {
                 id_name_map.insert(ent.id, ent.listing_attr_value);
             }
             target_entities_id_name.set(id_name_map);
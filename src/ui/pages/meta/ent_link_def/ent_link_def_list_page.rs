@@ -1,11 +1,11 @@
 use dioxus::prelude::*;
 
 use crate::{
-    domain::model::EntityLinkDef,
+    domain::model::{EntityLinkDef, Pagination},
+    server::fns::list_entity_link_defs,
     ui::{
-        comps::{Breadcrumb, Nav},
+        comps::{sort_fields, Breadcrumb, ListControls, Nav},
         routes::Route,
-        UI_STATE,
     },
 };
 
@@ -13,9 +13,17 @@ use crate::{
 pub fn EntityLinkDefListPage() -> Element {
     //
     let mut entries = use_signal::<Vec<EntityLinkDef>>(|| vec![]);
+    let mut total = use_signal(|| 0_i64);
+    let pagination = use_signal(|| Pagination::default());
 
-    use_future(move || async move {
-        entries.set(UI_STATE.get_ent_link_def_list().await);
+    use_effect(move || {
+        let pagination = pagination();
+        spawn(async move {
+            if let Ok(page) = list_entity_link_defs(Some(pagination)).await {
+                total.set(page.total);
+                entries.set(page.items);
+            }
+        });
     });
 
     rsx! {
@@ -35,6 +43,11 @@ pub fn EntityLinkDefListPage() -> Element {
                                 "+"
                             }
                         }
+                        ListControls {
+                            sort_fields: sort_fields(&[("name", "Name"), ("description", "Description"), ("cardinality", "Cardinality")]),
+                            pagination,
+                            total: total(),
+                        }
                         if entries.is_empty() {
                             p { class: "pb-4 text-gray-500", "There are no entries." }
                         }
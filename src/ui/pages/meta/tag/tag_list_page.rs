@@ -1,23 +1,30 @@
 use crate::{
-    domain::model::Tag,
+    domain::model::{Pagination, Tag},
+    server::fns::get_tags,
     ui::{
-        comps::{Breadcrumb, Nav},
+        comps::{sort_fields, Breadcrumb, ListControls, Nav},
         routes::Route,
-        UI_STATE,
     },
 };
 use dioxus::prelude::*;
-use std::sync::Arc;
 
 #[component]
 pub fn TagListPage() -> Element {
     //
-    let mut entries = use_signal(|| Arc::new(Vec::new()));
+    let mut entries = use_signal::<Vec<Tag>>(|| vec![]);
     let mut entries_loaded = use_signal(|| false);
+    let mut total = use_signal(|| 0_i64);
+    let pagination = use_signal(|| Pagination::default());
 
-    use_future(move || async move {
-        entries.set(UI_STATE.get_tags_list().await);
-        entries_loaded.set(true);
+    use_effect(move || {
+        let pagination = pagination();
+        spawn(async move {
+            if let Ok(page) = get_tags(Some(pagination)).await {
+                total.set(page.total);
+                entries.set(page.items);
+            }
+            entries_loaded.set(true);
+        });
     });
 
     rsx! {
@@ -37,6 +44,11 @@ pub fn TagListPage() -> Element {
                                 "+"
                             }
                         }
+                        ListControls {
+                            sort_fields: sort_fields(&[("name", "Name"), ("description", "Description")]),
+                            pagination,
+                            total: total(),
+                        }
                         if !entries_loaded() {
                             p { class: "pb-4 text-gray-500", "Loading tags ..." }
                         } else {
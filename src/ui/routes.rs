@@ -2,9 +2,9 @@ use crate::{
     domain::model::Id,
     ui::pages::{
         AdminPage, AttributeDefListPage, AttributeDefNewPage, AttributeDefPage, EntityDefListPage, EntityDefNewPage, EntityDefPage,
-        EntityLinkDefListPage, EntityLinkDefNewPage, EntityLinkDefPage, EntityLinkListPage, EntityLinkNewPage, EntityLinkPage,
-        EntityListPage, EntityNewPage, EntityPage, Home, Login, LoginIsRequiredPage, Logout, TagListPage, TagNewPage, TagPage,
-        UserProfilePage,
+        EntityImportPage, EntityLinkDefListPage, EntityLinkDefNewPage, EntityLinkDefPage, EntityLinkListPage, EntityLinkNewPage,
+        EntityLinkPage, EntityListPage, EntityNewPage, EntityPage, EntitySavedViewPage, GraphPage, Home, Login, LoginIsRequiredPage,
+        Logout, MetamodelDiagramPage, TagListPage, TagNewPage, TagPage, UserProfilePage,
     },
 };
 use dioxus::prelude::*;
@@ -29,6 +29,9 @@ pub enum Route {
     #[route("/admin")]
     AdminPage {},
 
+    #[route("/admin/graph")]
+    GraphPage {},
+
     // ---------------------
     // Attribute Definitions
     // ---------------------
@@ -65,15 +68,27 @@ pub enum Route {
     #[route("/admin/definitions/entity-links/:id")]
     EntityLinkDefPage { id: Id },
 
+    // ---------------------
+    // Definitions Diagram
+    // ---------------------
+    #[route("/admin/definitions/diagram")]
+    MetamodelDiagramPage {},
+
     // --------
     // Entities
     // --------
     #[route("/admin/entities")]
     EntityListPage {},
 
+    #[route("/admin/entities/views/:id")]
+    EntitySavedViewPage { id: Id },
+
     #[route("/admin/entities/new")]
     EntityNewPage {},
 
+    #[route("/admin/entities/import")]
+    EntityImportPage {},
+
     #[route("/admin/entities/:id")]
     EntityPage { id: Id },
 
@@ -109,6 +124,7 @@ impl Route {
             Route::Logout {} => vec![("Logout".into(), to)],
             Route::UserProfilePage { username: _ } => vec![("User Profile".into(), to)],
             Route::AdminPage {} => vec![("Admin".into(), to)],
+            Route::GraphPage {} => vec![("Admin".into(), Route::AdminPage {}), ("Graph".into(), to)],
 
             // ---------------------
             // Attribute Definitions
@@ -142,12 +158,23 @@ impl Route {
                 ("Entity Links Definitions".into(), Route::EntityLinkDefListPage {}),
                 ("New".into(), to),
             ],
+            Route::MetamodelDiagramPage {} => vec![("Admin".into(), Route::AdminPage {}), ("Definitions Diagram".into(), to)],
             Route::EntityListPage {} => vec![("Admin".into(), Route::AdminPage {}), ("Entities".into(), Route::EntityListPage {})],
+            Route::EntitySavedViewPage { .. } => vec![
+                ("Admin".into(), Route::AdminPage {}),
+                ("Entities".into(), Route::EntityListPage {}),
+                ("View".into(), to),
+            ],
             Route::EntityNewPage {} => vec![
                 ("Admin".into(), Route::AdminPage {}),
                 ("Entities".into(), Route::EntityListPage {}),
                 ("New".into(), to),
             ],
+            Route::EntityImportPage {} => vec![
+                ("Admin".into(), Route::AdminPage {}),
+                ("Entities".into(), Route::EntityListPage {}),
+                ("Import".into(), to),
+            ],
 
             // ------------
             // Entity Links
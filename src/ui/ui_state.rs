@@ -46,9 +46,10 @@ impl UiState {
 
     pub async fn get_tags(&self) -> IndexMap<Id, Tag> {
         if self.tags.read().is_empty() {
-            let res = get_tags().await;
+            let res = get_tags(None).await;
             match res {
-                Ok(tags) => {
+                Ok(page) => {
+                    let tags = page.items;
                     *self.tags_list.write() = Arc::new(tags.clone());
                     let tags_map: IndexMap<Id, Tag> = tags.into_iter().map(|tag| (tag.id.clone(), tag)).collect();
                     //let tags_map = Arc::new(tags_map);
@@ -153,10 +154,10 @@ impl UiState {
     }
 
     async fn get_ent_defs_from_server(&self) {
-        match list_entities_defs().await {
-            Ok(ent_defs) => {
-                log::debug!("[UiState.get_ent_defs_from_server] Got entity defs: {:?}", ent_defs);
-                *self.ent_defs.write() = ent_defs;
+        match list_entities_defs(None).await {
+            Ok(page) => {
+                log::debug!("[UiState.get_ent_defs_from_server] Got entity defs: {:?}", page.items);
+                *self.ent_defs.write() = page.items.into_iter().map(|item| (item.id.clone(), item)).collect();
             }
             Err(e) => {
                 log::error!("[UiState.get_ent_defs_from_server] Failed to fetch entity defs. Cause: '{e}'.");
@@ -205,10 +206,10 @@ impl UiState {
     }
 
     async fn get_ent_link_defs_from_server(&self) {
-        match list_entity_link_defs().await {
-            Ok(ent_link_defs) => {
-                log::debug!("[UiState.get_ent_link_defs_from_server] Got entity link defs: {:?}", ent_link_defs);
-                *self.ent_link_def_list.write() = ent_link_defs;
+        match list_entity_link_defs(None).await {
+            Ok(page) => {
+                log::debug!("[UiState.get_ent_link_defs_from_server] Got entity link defs: {:?}", page.items);
+                *self.ent_link_def_list.write() = page.items;
             }
             Err(e) => {
                 log::error!("[UiState.get_ent_link_defs_from_server] Failed to fetch entity link defs. Cause: '{e}'.");